@@ -0,0 +1,8 @@
+CREATE TABLE character_inventory
+(
+    character_id INTEGER NOT NULL,
+    item_name    TEXT    NOT NULL COLLATE NOCASE,
+    amount       INTEGER NOT NULL,
+    PRIMARY KEY (character_id, item_name),
+    FOREIGN KEY (character_id) REFERENCES character (id)
+);
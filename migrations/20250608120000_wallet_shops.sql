@@ -0,0 +1,9 @@
+CREATE TABLE wallet_shop_item
+(
+    wallet_id INTEGER NOT NULL,
+    item_name TEXT    NOT NULL COLLATE NOCASE,
+    price     INTEGER NOT NULL,
+    stock     INTEGER,
+    PRIMARY KEY (wallet_id, item_name),
+    FOREIGN KEY (wallet_id) REFERENCES wallet (id)
+);
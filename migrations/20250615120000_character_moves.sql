@@ -0,0 +1,15 @@
+CREATE TABLE character_move
+(
+    character_id INTEGER NOT NULL,
+    move_name    TEXT    NOT NULL COLLATE NOCASE,
+    PRIMARY KEY (character_id, move_name),
+    FOREIGN KEY (character_id) REFERENCES character (id)
+);
+
+CREATE TABLE character_unlocked_move
+(
+    character_id INTEGER NOT NULL,
+    move_name    TEXT    NOT NULL COLLATE NOCASE,
+    PRIMARY KEY (character_id, move_name),
+    FOREIGN KEY (character_id) REFERENCES character (id)
+);
@@ -0,0 +1,21 @@
+CREATE TABLE ledger_entry
+(
+    id             INTEGER NOT NULL PRIMARY KEY,
+    guild_id       INTEGER NOT NULL,
+    actor_id       INTEGER,
+    channel_id     INTEGER,
+    interaction_id INTEGER,
+    action_type    TEXT    NOT NULL,
+    character_id   INTEGER,
+    wallet_id      INTEGER,
+    field          TEXT,
+    delta          INTEGER,
+    message        TEXT    NOT NULL,
+    timestamp      INTEGER NOT NULL,
+    is_undone      BOOLEAN NOT NULL DEFAULT FALSE,
+    FOREIGN KEY (guild_id) REFERENCES guild (id),
+    FOREIGN KEY (character_id) REFERENCES character (id),
+    FOREIGN KEY (wallet_id) REFERENCES wallet (id)
+);
+
+CREATE INDEX ledger_entry_interaction_id ON ledger_entry (interaction_id);
@@ -0,0 +1,3 @@
+ALTER TABLE character ADD COLUMN hp_lost INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE character ADD COLUMN will_lost INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE character ADD COLUMN wounds INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,9 @@
+CREATE TABLE character_skill
+(
+    character_id INTEGER NOT NULL,
+    skill        TEXT    NOT NULL COLLATE NOCASE,
+    rank         INTEGER NOT NULL DEFAULT 0,
+    edit_rank    INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (character_id, skill),
+    FOREIGN KEY (character_id) REFERENCES character (id)
+);
@@ -0,0 +1,21 @@
+CREATE TABLE team
+(
+    id                 INTEGER NOT NULL PRIMARY KEY,
+    guild_id           INTEGER NOT NULL,
+    name               TEXT    NOT NULL COLLATE NOCASE,
+    emblem             TEXT,
+    wallet_id          INTEGER NOT NULL,
+    rank_points        INTEGER NOT NULL DEFAULT 0,
+    creation_timestamp INTEGER NOT NULL,
+    UNIQUE (guild_id, name),
+    FOREIGN KEY (guild_id) REFERENCES guild (id),
+    FOREIGN KEY (wallet_id) REFERENCES wallet (id)
+);
+
+CREATE TABLE team_member
+(
+    character_id INTEGER NOT NULL PRIMARY KEY,
+    team_id      INTEGER NOT NULL,
+    FOREIGN KEY (character_id) REFERENCES character (id),
+    FOREIGN KEY (team_id) REFERENCES team (id)
+);
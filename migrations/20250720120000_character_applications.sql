@@ -0,0 +1,33 @@
+ALTER TABLE guild ADD COLUMN character_application_channel_id INTEGER;
+
+CREATE TABLE character_application(
+    id INTEGER NOT NULL PRIMARY KEY,
+    guild_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    channel_id INTEGER NOT NULL,
+    name TEXT NOT NULL COLLATE NOCASE,
+    species_api_id INTEGER NOT NULL,
+    phenotype INTEGER NOT NULL,
+    is_shiny BOOLEAN NOT NULL,
+    backstory TEXT,
+    status INTEGER NOT NULL,
+    review_message_channel_id INTEGER,
+    review_message_id INTEGER,
+    character_id INTEGER,
+    creation_timestamp INTEGER NOT NULL,
+
+    FOREIGN KEY (guild_id) REFERENCES guild(id),
+    FOREIGN KEY (user_id) REFERENCES user(id),
+    FOREIGN KEY (character_id) REFERENCES character(id)
+);
+
+CREATE TABLE character_application_status_change(
+    id INTEGER NOT NULL PRIMARY KEY,
+    application_id INTEGER NOT NULL,
+    status INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    note TEXT,
+    timestamp INTEGER NOT NULL,
+
+    FOREIGN KEY (application_id) REFERENCES character_application(id) ON DELETE CASCADE
+);
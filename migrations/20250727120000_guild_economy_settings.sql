@@ -0,0 +1,5 @@
+ALTER TABLE guild ADD COLUMN backpack_upgrade_base_price INTEGER NOT NULL DEFAULT 500;
+ALTER TABLE guild ADD COLUMN backpack_upgrade_price_increase INTEGER NOT NULL DEFAULT 500;
+ALTER TABLE guild ADD COLUMN hidden_ability_unlock_price INTEGER NOT NULL DEFAULT 2000;
+ALTER TABLE guild ADD COLUMN default_backpack_slots INTEGER NOT NULL DEFAULT 6;
+ALTER TABLE guild ADD COLUMN maximum_backpack_slots INTEGER;
@@ -0,0 +1,12 @@
+ALTER TABLE guild ADD COLUMN experience_per_level INTEGER NOT NULL DEFAULT 100;
+ALTER TABLE guild ADD COLUMN base_combat_points INTEGER NOT NULL DEFAULT 3;
+ALTER TABLE guild ADD COLUMN combat_points_per_level INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE guild ADD COLUMN limit_break_base_cost INTEGER NOT NULL DEFAULT 2;
+ALTER TABLE guild ADD COLUMN limit_break_cost_increase INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE guild ADD COLUMN first_evolution_level INTEGER NOT NULL DEFAULT 3;
+ALTER TABLE guild ADD COLUMN second_evolution_level INTEGER NOT NULL DEFAULT 6;
+ALTER TABLE guild ADD COLUMN social_points_bronze INTEGER NOT NULL DEFAULT 4;
+ALTER TABLE guild ADD COLUMN social_points_silver INTEGER NOT NULL DEFAULT 6;
+ALTER TABLE guild ADD COLUMN social_points_gold INTEGER NOT NULL DEFAULT 8;
+ALTER TABLE guild ADD COLUMN social_points_platinum INTEGER NOT NULL DEFAULT 10;
+ALTER TABLE guild ADD COLUMN social_points_diamond INTEGER NOT NULL DEFAULT 12;
@@ -0,0 +1,18 @@
+CREATE TABLE quest_reward
+(
+    quest_id      INTEGER NOT NULL PRIMARY KEY,
+    experience    INTEGER NOT NULL DEFAULT 0,
+    money         INTEGER NOT NULL DEFAULT 0,
+    battle_points INTEGER NOT NULL DEFAULT 0,
+    gm_experience INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (quest_id) REFERENCES quest (channel_id)
+);
+
+CREATE TABLE quest_reward_item
+(
+    quest_id  INTEGER NOT NULL,
+    item_name TEXT    NOT NULL,
+    amount    INTEGER NOT NULL,
+    PRIMARY KEY (quest_id, item_name),
+    FOREIGN KEY (quest_id) REFERENCES quest (channel_id)
+);
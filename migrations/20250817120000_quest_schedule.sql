@@ -0,0 +1,3 @@
+ALTER TABLE quest ADD COLUMN scheduled_start_timestamp INTEGER;
+ALTER TABLE quest ADD COLUMN is_day_before_reminder_sent BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE quest ADD COLUMN is_hour_before_reminder_sent BOOLEAN NOT NULL DEFAULT FALSE;
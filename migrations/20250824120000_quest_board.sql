@@ -0,0 +1,2 @@
+ALTER TABLE guild ADD COLUMN quest_board_channel_id INTEGER;
+ALTER TABLE guild ADD COLUMN quest_board_message_id INTEGER;
@@ -0,0 +1,6 @@
+ALTER TABLE quest ADD COLUMN minimum_level INTEGER;
+ALTER TABLE quest ADD COLUMN maximum_level INTEGER;
+ALTER TABLE quest ADD COLUMN minimum_rank INTEGER;
+ALTER TABLE quest ADD COLUMN maximum_rank INTEGER;
+ALTER TABLE quest ADD COLUMN required_type TEXT;
+ALTER TABLE quest ADD COLUMN forbidden_type TEXT;
@@ -0,0 +1,8 @@
+CREATE TABLE quest_draw_loss(
+    guild_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    loss_count INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (guild_id, user_id),
+    FOREIGN KEY (guild_id) REFERENCES guild(id),
+    FOREIGN KEY (user_id) REFERENCES user(id)
+);
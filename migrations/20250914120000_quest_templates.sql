@@ -0,0 +1,27 @@
+CREATE TABLE quest_template
+(
+    guild_id                        INTEGER NOT NULL,
+    name                            TEXT    NOT NULL COLLATE NOCASE,
+    creator_id                      INTEGER NOT NULL,
+    title                           TEXT    NOT NULL,
+    description                     TEXT    NOT NULL,
+    maximum_participant_count       INTEGER NOT NULL,
+    participant_selection_mechanism INTEGER NOT NULL,
+    reward_experience               INTEGER NOT NULL DEFAULT 0,
+    reward_money                    INTEGER NOT NULL DEFAULT 0,
+    reward_battle_points            INTEGER NOT NULL DEFAULT 0,
+    reward_gm_experience            INTEGER NOT NULL DEFAULT 0,
+    minimum_level                   INTEGER,
+    maximum_level                   INTEGER,
+    minimum_rank                    INTEGER,
+    maximum_rank                    INTEGER,
+    required_type                   TEXT,
+    forbidden_type                  TEXT,
+    recurring_forum_channel_id      INTEGER,
+    recurrence_interval_days        INTEGER,
+    next_recurrence_timestamp       INTEGER,
+
+    PRIMARY KEY (guild_id, name),
+    FOREIGN KEY (guild_id) REFERENCES guild (id),
+    FOREIGN KEY (creator_id) REFERENCES user (id)
+);
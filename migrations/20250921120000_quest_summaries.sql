@@ -0,0 +1 @@
+ALTER TABLE quest ADD COLUMN summary TEXT;
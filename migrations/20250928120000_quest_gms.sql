@@ -0,0 +1,7 @@
+CREATE TABLE quest_gm(
+    quest_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    PRIMARY KEY (quest_id, user_id),
+    FOREIGN KEY (quest_id) REFERENCES quest(channel_id),
+    FOREIGN KEY (user_id) REFERENCES user(id)
+);
@@ -0,0 +1,15 @@
+CREATE TABLE quest_poll_slot(
+    quest_id INTEGER NOT NULL,
+    start_timestamp INTEGER NOT NULL,
+    PRIMARY KEY (quest_id, start_timestamp),
+    FOREIGN KEY (quest_id) REFERENCES quest(channel_id)
+);
+
+CREATE TABLE quest_poll_vote(
+    quest_id INTEGER NOT NULL,
+    start_timestamp INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    PRIMARY KEY (quest_id, start_timestamp, user_id),
+    FOREIGN KEY (quest_id, start_timestamp) REFERENCES quest_poll_slot(quest_id, start_timestamp),
+    FOREIGN KEY (user_id) REFERENCES user(id)
+);
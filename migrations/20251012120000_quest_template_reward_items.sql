@@ -0,0 +1,9 @@
+CREATE TABLE quest_template_reward_item
+(
+    guild_id      INTEGER NOT NULL,
+    template_name TEXT    NOT NULL COLLATE NOCASE,
+    item_name     TEXT    NOT NULL,
+    amount        INTEGER NOT NULL,
+    PRIMARY KEY (guild_id, template_name, item_name),
+    FOREIGN KEY (guild_id, template_name) REFERENCES quest_template (guild_id, name)
+);
@@ -55,6 +55,21 @@ pub async fn autocomplete_item<'a>(ctx: PoiseContext<'a>, partial: &'a str) -> V
     filter_and_sort(partial, game_data.item_names.iter(), 2)
 }
 
+pub async fn autocomplete_item_or_potion<'a>(
+    ctx: PoiseContext<'a>,
+    partial: &'a str,
+) -> Vec<String> {
+    let game_data = ctx.data().game.get_by_context(&ctx).await;
+    filter_and_sort(
+        partial,
+        game_data
+            .item_names
+            .iter()
+            .chain(game_data.potion_names.iter()),
+        2,
+    )
+}
+
 pub async fn autocomplete_weather<'a>(ctx: PoiseContext<'a>, partial: &'a str) -> Vec<String> {
     let game_data = ctx.data().game.get_by_context(&ctx).await;
     filter_and_sort(partial, game_data.weather_names.iter(), 0)
@@ -145,6 +160,38 @@ pub async fn autocomplete_wallet_name<'a>(ctx: PoiseContext<'a>, partial: &'a st
     }
 }
 
+pub async fn autocomplete_quest_template<'a>(
+    ctx: PoiseContext<'a>,
+    partial: &'a str,
+) -> Vec<String> {
+    let guild_id = ctx.guild_id().expect("Command should be guild_only!").get() as i64;
+    let entries = sqlx::query!(
+        "SELECT name FROM quest_template WHERE guild_id = ?",
+        guild_id
+    )
+    .fetch_all(&ctx.data().database)
+    .await;
+
+    if let Ok(entries) = entries {
+        filter_and_sort(partial, entries.iter().map(|x| &x.name), 0)
+    } else {
+        Vec::new()
+    }
+}
+
+pub async fn autocomplete_team_name<'a>(ctx: PoiseContext<'a>, partial: &'a str) -> Vec<String> {
+    let guild_id = ctx.guild_id().expect("Command should be guild_only!").get() as i64;
+    let entries = sqlx::query!("SELECT name FROM team WHERE team.guild_id = ?", guild_id)
+        .fetch_all(&ctx.data().database)
+        .await;
+
+    if let Ok(entries) = entries {
+        filter_and_sort(partial, entries.iter().map(|x| &x.name), 0)
+    } else {
+        Vec::new()
+    }
+}
+
 pub async fn autocomplete_owned_character_name<'a>(
     ctx: PoiseContext<'a>,
     partial: &'a str,
@@ -1,13 +1,49 @@
-use crate::commands::Error;
+use crate::commands::autocompletion::autocomplete_character_name;
+use crate::commands::{Error, find_character};
 use crate::shared::PoiseContext;
+use crate::shared::character_condition::get_character_condition;
+use crate::shared::errors::ValidationError;
 
 /// Use this to calculate your die count for reversal and other HP based moves
 #[poise::command(slash_command)]
 pub async fn calculate_hp_damage_modifier(
     ctx: PoiseContext<'_>,
-    #[description = "What's your max HP?"] max_hp: u8,
+    #[description = "What's your max HP?"] max_hp: Option<u8>,
+    #[description = "Or use the HP of a character instead."]
+    #[autocomplete = "autocomplete_character_name"]
+    character: Option<String>,
 ) -> Result<(), Error> {
-    ctx.say(build_string(max_hp)).await?;
+    match (max_hp, character) {
+        (Some(max_hp), None) => {
+            ctx.say(build_string(max_hp)).await?;
+        }
+        (None, Some(character)) => {
+            let Some(guild_id) = ctx.guild_id() else {
+                return Err(Box::new(ValidationError::new(
+                    "Characters can only be looked up within a server.",
+                )));
+            };
+            let character = find_character(ctx.data(), guild_id.get(), &character).await?;
+            let game_data = ctx.data().game.get_by_context(&ctx).await;
+            let mut connection = ctx.data().database.acquire().await?;
+            let condition =
+                get_character_condition(&mut connection, game_data, character.id).await?;
+            let max_hp = condition.max_hp.clamp(1, u8::MAX as i64) as u8;
+            ctx.say(format!(
+                "{}\n**{}** currently has **{}** HP.",
+                build_string(max_hp),
+                character.name,
+                condition.current_hp()
+            ))
+            .await?;
+        }
+        _ => {
+            return Err(Box::new(ValidationError::new(
+                "Please provide either your max HP or a character.",
+            )));
+        }
+    }
+
     Ok(())
 }
 
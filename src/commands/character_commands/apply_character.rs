@@ -0,0 +1,203 @@
+use crate::Error;
+use crate::commands::autocompletion::autocomplete_pokemon;
+use crate::commands::character_commands::validate_user_input;
+use crate::commands::{
+    ensure_guild_exists, ensure_user_exists, pokemon_from_autocomplete_string,
+    send_ephemeral_reply, send_error,
+};
+use crate::shared::PoiseContext;
+use crate::shared::character_application::{
+    build_review_message, change_application_status, create_review_buttons,
+    find_open_character_application, get_character_application, get_status_history,
+};
+use crate::shared::data::Data;
+use crate::shared::enums::{CharacterApplicationStatus, Gender};
+use crate::shared::errors::ValidationError;
+use chrono::Utc;
+use poise::Modal;
+use serenity::all::{ChannelId, CreateAllowedMentions, CreateMessage};
+
+type ApplicationContext<'a> = poise::ApplicationContext<'a, Data, Error>;
+
+#[derive(Debug, Modal)]
+#[name = "Character Application"]
+struct CharacterApplicationModal {
+    #[name = "Name"]
+    #[placeholder = "What's your character's name?"]
+    #[max_length = 30]
+    name: String,
+    #[name = "Backstory"]
+    #[placeholder = "Optional. Tell the GMs a little about your character!"]
+    #[paragraph]
+    #[max_length = 1500]
+    backstory: Option<String>,
+}
+
+/// Apply for a new character. A GM will review your application.
+#[poise::command(slash_command, guild_only)]
+pub async fn apply_character(
+    ctx: ApplicationContext<'_>,
+    #[autocomplete = "autocomplete_pokemon"]
+    #[description = "What kind of pokemon are you?"]
+    pokemon_species: String,
+    #[description = "Which phenotype?"] gender: Gender,
+    #[description = "Optional. Does it glow in the dark? Defaults to false."] is_shiny: Option<
+        bool,
+    >,
+) -> Result<(), Error> {
+    let application_context = ctx;
+    let ctx = PoiseContext::Application(ctx);
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    let user_id = ctx.author().id.get() as i64;
+
+    let review_channel_id = sqlx::query!(
+        "SELECT character_application_channel_id FROM guild WHERE id = ?",
+        guild_id
+    )
+    .fetch_optional(&ctx.data().database)
+    .await?
+    .and_then(|x| x.character_application_channel_id);
+    let Some(review_channel_id) = review_channel_id else {
+        return Err(Box::new(ValidationError::new(
+            "This server doesn't accept character applications yet. An admin needs to set up an application channel with /setup_guild first.",
+        )));
+    };
+
+    let pokemon = pokemon_from_autocomplete_string(&ctx, &pokemon_species).await?;
+    let Some(modal) =
+        poise::execute_modal::<_, _, CharacterApplicationModal>(application_context, None, None)
+            .await?
+    else {
+        return Ok(());
+    };
+
+    let name = modal.name.trim().to_string();
+    if let Err(e) = validate_user_input(name.as_str()) {
+        return send_error(&ctx, e).await;
+    }
+
+    let existing_character = sqlx::query!(
+        "SELECT id FROM character WHERE user_id = ? AND guild_id = ? AND name = ?",
+        user_id,
+        guild_id,
+        name
+    )
+    .fetch_optional(&ctx.data().database)
+    .await?;
+    if existing_character.is_some() {
+        return send_error(&ctx, "You already own a character with this name!").await;
+    }
+
+    ensure_guild_exists(&ctx, guild_id).await;
+    ensure_user_exists(&ctx, user_id, guild_id).await;
+
+    let channel_id = ctx.channel_id().get() as i64;
+    let species_api_id = pokemon.poke_api_id.0 as i64;
+    let phenotype = gender as i64;
+    let is_shiny = is_shiny.unwrap_or(false);
+    let existing_application =
+        find_open_character_application(&ctx.data().database, guild_id, user_id, &name).await?;
+
+    let mut transaction = ctx.data().database.begin().await?;
+    let (application_id, previous_status, note) = if let Some(application) = existing_application {
+        if application.status() == CharacterApplicationStatus::Pending {
+            return send_error(
+                &ctx,
+                "You already have a pending application for a character with this name. Please wait for a GM to review it!",
+            )
+            .await;
+        }
+
+        sqlx::query!(
+            "UPDATE character_application SET channel_id = ?, species_api_id = ?, phenotype = ?, is_shiny = ?, backstory = ? WHERE id = ?",
+            channel_id,
+            species_api_id,
+            phenotype,
+            is_shiny,
+            modal.backstory,
+            application.id
+        )
+        .execute(&mut *transaction)
+        .await?;
+        (application.id, application.status(), Some("Resubmitted"))
+    } else {
+        let timestamp = Utc::now().timestamp();
+        let status = CharacterApplicationStatus::Pending as i64;
+        let record = sqlx::query!(
+            "INSERT INTO character_application (guild_id, user_id, channel_id, name, species_api_id, phenotype, is_shiny, backstory, status, creation_timestamp) \
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING id",
+            guild_id,
+            user_id,
+            channel_id,
+            name,
+            species_api_id,
+            phenotype,
+            is_shiny,
+            modal.backstory,
+            status,
+            timestamp
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+        (record.id, CharacterApplicationStatus::Pending, None)
+    };
+    change_application_status(
+        &mut transaction,
+        application_id,
+        previous_status,
+        CharacterApplicationStatus::Pending,
+        user_id,
+        note,
+    )
+    .await?;
+
+    let application = get_character_application(&mut *transaction, application_id)
+        .await?
+        .expect("Application was just persisted!");
+    let history = get_status_history(&mut *transaction, application_id).await?;
+    // Only commit once the GMs can see it, or the application would be stuck in Pending.
+    let review_message = match ChannelId::new(review_channel_id as u64)
+        .send_message(
+            ctx,
+            CreateMessage::new()
+                .content(build_review_message(&application, Some(pokemon), &history))
+                .components(create_review_buttons(application_id))
+                .allowed_mentions(CreateAllowedMentions::default().empty_users()),
+        )
+        .await
+    {
+        Ok(message) => message,
+        Err(e) => {
+            return send_error(
+                &ctx,
+                &format!(
+                    "Unable to send your application to the GMs, so it hasn't been submitted. Please let them know!\n{}",
+                    e
+                ),
+            )
+            .await;
+        }
+    };
+
+    let review_message_channel_id = review_message.channel_id.get() as i64;
+    let review_message_id = review_message.id.get() as i64;
+    sqlx::query!(
+        "UPDATE character_application SET review_message_channel_id = ?, review_message_id = ? WHERE id = ?",
+        review_message_channel_id,
+        review_message_id,
+        application_id
+    )
+    .execute(&mut *transaction)
+    .await?;
+    transaction.commit().await?;
+
+    send_ephemeral_reply(
+        &ctx,
+        format!(
+            "Your application for **{}** has been submitted! A GM will take a look at it soon.",
+            name
+        ),
+    )
+    .await?;
+    Ok(())
+}
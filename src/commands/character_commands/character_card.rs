@@ -0,0 +1,30 @@
+use crate::Error;
+use crate::commands::autocompletion::autocomplete_character_name;
+use crate::commands::find_character;
+use crate::shared::PoiseContext;
+use crate::shared::character_card::render_character_card;
+use poise::CreateReply;
+use serenity::all::CreateAttachment;
+
+/// Renders a trainer card image for a character.
+#[poise::command(slash_command, guild_only)]
+pub async fn character_card(
+    ctx: PoiseContext<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    let game_data = ctx.data().game.get_by_context(&ctx).await;
+
+    let png = render_character_card(&ctx.data().database, game_data, character.id).await?;
+    ctx.send(CreateReply::default().attachment(CreateAttachment::bytes(
+        png,
+        format!("{}.png", character.name.to_lowercase().replace(' ', "_")),
+    )))
+    .await?;
+
+    Ok(())
+}
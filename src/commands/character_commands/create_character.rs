@@ -1,4 +1,4 @@
-use serenity::all::{CreateMessage, GetMessages};
+use serenity::all::{CreateMessage, GetMessages, Message};
 use serenity::model::user::User;
 
 use crate::commands::autocompletion::autocomplete_pokemon;
@@ -9,8 +9,11 @@ use crate::commands::{
 };
 use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
 use crate::shared::character::update_character_post_with_poise_context;
+use crate::shared::character_creation::{
+    DEFAULT_STARTING_EXPERIENCE, DEFAULT_STARTING_MONEY, NewCharacter, insert_new_character,
+};
 use crate::shared::enums::Gender;
-use crate::shared::utility::level_calculations;
+use crate::shared::guild_settings::get_progression_rules;
 use crate::shared::{PoiseContext, emoji};
 
 /// Create a new character within the database.
@@ -104,11 +107,46 @@ async fn execute(
     }
 
     let pokemon = pokemon_from_autocomplete_string(&ctx, &pokemon_species).await?;
-    let is_shiny = is_shiny.unwrap_or(false);
-    let exp = exp.unwrap_or(0);
-    let money = money.unwrap_or(500);
-    let phenotype = gender as i64;
+    let new_character = NewCharacter {
+        user_id: player.id.get() as i64,
+        name,
+        pokemon,
+        is_shiny: is_shiny.unwrap_or(false),
+        gender,
+        exp: exp.unwrap_or(DEFAULT_STARTING_EXPERIENCE),
+        money: money.unwrap_or(DEFAULT_STARTING_MONEY),
+    };
+
+    if let Some(character_id) = insert_character(&ctx, &new_character).await? {
+        send_ephemeral_reply(&ctx, "Character has been successfully created!").await?;
+        update_character_post_with_poise_context(&ctx, character_id).await;
+        log_action(
+            &ActionType::Initialization,
+            LogActionArguments::triggered_by_user(&ctx),
+            &format!(
+                "Initialized character {} ({}) with {} {} and {} exp.",
+                new_character.name,
+                pokemon.name,
+                new_character.money,
+                emoji::POKE_COIN,
+                new_character.exp
+            ),
+        )
+        .await?;
+        ctx.data()
+            .cache
+            .update_character_names(&ctx.data().database)
+            .await;
+    }
 
+    Ok(())
+}
+
+/// Sends the message which will later be turned into the character post.
+/// Returns None if the channel doesn't look right, in which case the user has already been notified.
+pub(super) async fn send_character_post_placeholder(
+    ctx: &PoiseContext<'_>,
+) -> Result<Option<Message>, Error> {
     let message = ctx
         .channel_id()
         .send_message(
@@ -121,80 +159,58 @@ async fn execute(
 
     if let Ok(messages) = ctx.channel_id().messages(ctx, GetMessages::new()).await {
         if messages.len() > 10 {
-            send_error(&ctx, "Seems like there's already been a looot of messages been sent in this channel! Cannot create a character here, that seems wrong.").await?;
+            send_error(ctx, "Seems like there's already been a looot of messages been sent in this channel! Cannot create a character here, that seems wrong.").await?;
             message.delete(ctx).await?;
-            return Ok(());
+            return Ok(None);
         }
     }
 
-    let user_id = player.id.get() as i64;
+    Ok(Some(message))
+}
+
+/// Creates the character post and database entry, as well as any missing emojis.
+/// Returns None if that didn't work out, in which case the user has already been notified.
+async fn insert_character(
+    ctx: &PoiseContext<'_>,
+    character: &NewCharacter<'_>,
+) -> Result<Option<i64>, Error> {
+    let Some(message) = send_character_post_placeholder(ctx).await? else {
+        return Ok(None);
+    };
+
     let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
 
-    ensure_guild_exists(&ctx, guild_id).await;
-    ensure_user_exists(&ctx, user_id, guild_id).await;
+    ensure_guild_exists(ctx, guild_id).await;
+    ensure_user_exists(ctx, character.user_id, guild_id).await;
 
     let stat_message_id = message.id.get() as i64;
     let stat_channel_id = message.channel_id.get() as i64;
-    let creation_date = chrono::Utc::now().date_naive();
-
-    let level = level_calculations::calculate_level_from_experience(exp);
-    let mon = level_calculations::get_usual_evolution_stage_for_level(
-        level,
-        pokemon,
+    let rules = get_progression_rules(&ctx.data().database, guild_id).await?;
+    let record = insert_new_character(
+        &ctx.data().database,
         &ctx.data().game.base_data,
-        None,
-    );
-
-    let record = sqlx::query!(
-        "INSERT INTO character (user_id, guild_id, name, stat_message_id, stat_channel_id, creation_date, experience, money, species_api_id, is_shiny, phenotype,\
-                                stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight
-) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING id",
-        user_id,
         guild_id,
-        name,
-        stat_message_id,
         stat_channel_id,
-        creation_date,
-        exp,
-        money,
-        pokemon.poke_api_id.0,
-        is_shiny,
-        phenotype,
-        mon.strength.min,
-        mon.dexterity.min,
-        mon.vitality.min,
-        mon.special.min,
-        mon.insight.min,
-    ).fetch_one(&ctx.data().database)
-        .await;
-
-    create_emojis::create_emojis_for_pokemon(&ctx, pokemon, &gender, is_shiny).await;
-
-    if let Ok(record) = record {
-        send_ephemeral_reply(&ctx, "Character has been successfully created!").await?;
-        update_character_post_with_poise_context(&ctx, record.id).await;
-        log_action(
-            &ActionType::Initialization,
-            LogActionArguments::triggered_by_user(&ctx),
-            &format!(
-                "Initialized character {} ({}) with {} {} and {} exp.",
-                name,
-                pokemon.name,
-                money,
-                emoji::POKE_COIN,
-                exp
-            ),
-        )
-        .await?;
-        ctx.data()
-            .cache
-            .update_character_names(&ctx.data().database)
-            .await;
-        return Ok(());
+        stat_message_id,
+        &rules,
+        character,
+    )
+    .await;
+
+    create_emojis::create_emojis_for_pokemon(
+        ctx,
+        character.pokemon,
+        &character.gender,
+        character.is_shiny,
+    )
+    .await;
+
+    if let Ok(character_id) = record {
+        return Ok(Some(character_id));
     }
 
-    send_error(&ctx, "Something went wrong! Does a character with this name already exist for this specific player?").await?;
+    send_error(ctx, "Something went wrong! Does a character with this name already exist for this specific player?").await?;
     message.delete(ctx).await?;
 
-    Ok(())
+    Ok(None)
 }
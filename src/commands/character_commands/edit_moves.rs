@@ -0,0 +1,115 @@
+use crate::Error;
+use crate::commands::autocompletion::{
+    autocomplete_character_name, autocomplete_move, autocomplete_owned_character_name,
+};
+use crate::commands::{ensure_user_owns_character, find_character, send_ephemeral_reply};
+use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
+use crate::shared::errors::ValidationError;
+use crate::shared::game_data::PokemonApiId;
+use crate::shared::{PoiseContext, character_moves};
+use poise::CreateReply;
+
+/// Select which moves your character is currently using.
+#[poise::command(slash_command, guild_only)]
+pub async fn edit_moves(
+    ctx: PoiseContext<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    character: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character(ctx.author(), &character)?;
+
+    let game_data = ctx.data().game.get_by_context(&ctx).await;
+    let (content, components) = character_moves::create_move_edit_message(
+        ctx.serenity_context(),
+        ctx.data(),
+        game_data,
+        character.id,
+        0,
+    )
+    .await?;
+
+    ctx.send(
+        CreateReply::default()
+            .content(content)
+            .components(components)
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Allow a character to learn a TM, tutor or egg move.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn unlock_move(
+    ctx: PoiseContext<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+    #[description = "Which move?"]
+    #[autocomplete = "autocomplete_move"]
+    name: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    let game_data = ctx.data().game.get_by_context(&ctx).await;
+
+    let Some(poke_move) = game_data.moves.get(&name.to_lowercase()) else {
+        return Err(Box::new(ValidationError::new(format!(
+            "Unable to find a move named **{}**, sorry!",
+            name
+        ))));
+    };
+
+    let record = sqlx::query!(
+        "SELECT species_api_id FROM character WHERE id = ?",
+        character.id
+    )
+    .fetch_one(&ctx.data().database)
+    .await?;
+    let pokemon = game_data
+        .pokemon_by_api_id
+        .get(&PokemonApiId(
+            record
+                .species_api_id
+                .try_into()
+                .expect("Should always be in valid range."),
+        ))
+        .expect("All mons inside the Database should have a valid API ID assigned.");
+
+    if !character_moves::can_be_unlocked(pokemon, &poke_move.name) {
+        return Err(Box::new(ValidationError::new(format!(
+            "{} can't learn {} through TMs, tutors or eggs.",
+            pokemon.name, poke_move.name
+        ))));
+    }
+
+    sqlx::query!(
+        "INSERT OR IGNORE INTO character_unlocked_move (character_id, move_name) VALUES (?, ?)",
+        character.id,
+        poke_move.name
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    send_ephemeral_reply(
+        &ctx,
+        format!(
+            "{} can now select {} in their move editor.",
+            character.name, poke_move.name
+        ),
+    )
+    .await?;
+    log_action(
+        &ActionType::CharacterEdit,
+        LogActionArguments::triggered_by_user(&ctx),
+        format!("Unlocked {} for {}", poke_move.name, character.name),
+    )
+    .await
+}
@@ -0,0 +1,233 @@
+use crate::Error;
+use crate::commands::autocompletion::{autocomplete_character_name, autocomplete_pokemon};
+use crate::commands::create_emojis::create_emojis_for_pokemon;
+use crate::commands::{
+    ensure_user_owns_character_or_is_gm, find_character, pokemon_from_autocomplete_string,
+};
+use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
+use crate::shared::character::update_character_post_with_poise_context;
+use crate::shared::enums::Gender;
+use crate::shared::errors::ValidationError;
+use crate::shared::game_data::pokemon::{Pokemon, PokemonStat};
+use crate::shared::game_data::{GameData, PokemonApiId};
+use crate::shared::guild_settings::get_progression_rules;
+use crate::shared::utility::level_calculations;
+use crate::shared::{PoiseContext, emoji};
+use serenity::all::{Mentionable, UserId};
+
+fn is_evolution_of(target: &Pokemon, current: &Pokemon, game_data: &GameData) -> bool {
+    let mut evolves_from = target.evolves_from;
+    while let Some(api_id) = evolves_from {
+        if api_id == current.poke_api_id {
+            return true;
+        }
+
+        evolves_from = game_data
+            .pokemon_by_api_id
+            .get(&api_id)
+            .and_then(|x| x.evolves_from);
+    }
+
+    false
+}
+
+/// Moves the points invested into a stat over to the new species and caps them at its maximum,
+/// keeping existing limit breaks intact. Returns the new value and the amount of points which didn't fit.
+fn clamp_stat(current: i64, old: &PokemonStat, new: &PokemonStat) -> (i64, i64) {
+    let invested = (current - old.min as i64).max(0);
+    let limit_breaks = (current - old.max as i64).max(0);
+    let value = new.min as i64 + invested;
+    let max = new.max as i64 + limit_breaks;
+    (value.min(max), (value - max).max(0))
+}
+
+/// Evolve your character!
+#[poise::command(slash_command, guild_only)]
+pub async fn evolve(
+    ctx: PoiseContext<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+    #[description = "Into what?"]
+    #[autocomplete = "autocomplete_pokemon"]
+    target: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character_or_is_gm(
+        ctx.author(),
+        ctx.author_member()
+            .await
+            .expect("author_member should be set within guild context."),
+        &character,
+    )?;
+
+    let target = pokemon_from_autocomplete_string(&ctx, &target).await?;
+    let game_data = ctx.data().game.get_by_context(&ctx).await;
+
+    let record = sqlx::query!(
+        "SELECT user_id, guild_id, experience, species_api_id, species_override_for_stats, phenotype, is_shiny, \
+        stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight \
+        FROM character WHERE id = ?",
+        character.id
+    )
+    .fetch_one(&ctx.data().database)
+    .await?;
+
+    let current = game_data
+        .pokemon_by_api_id
+        .get(&PokemonApiId(record.species_api_id as u16))
+        .expect("All mons inside the Database should have a valid API ID assigned.");
+
+    if !is_evolution_of(target, current, game_data) {
+        return Err(Box::new(ValidationError::new(format!(
+            "{} doesn't evolve into {}.",
+            current.name, target.name
+        ))));
+    }
+
+    let rules = get_progression_rules(&ctx.data().database, record.guild_id).await?;
+    let level = level_calculations::calculate_level_from_experience(&rules, record.experience);
+    let required_level = level_calculations::get_required_level_for_evolution_stage(
+        &rules,
+        level_calculations::get_evolution_stage(target, game_data),
+    );
+    if level < required_level {
+        return Err(Box::new(ValidationError::new(format!(
+            "{} needs to be at least level {} to evolve into {}. Right now, they are level {}.",
+            character.name, required_level, target.name, level
+        ))));
+    }
+
+    let species_override_for_stats = match record.species_override_for_stats {
+        Some(api_id) if api_id == target.poke_api_id.0 as i64 => None,
+        other => other,
+    };
+
+    let old_stats = level_calculations::get_usual_evolution_stage_for_level(
+        &rules,
+        level,
+        current,
+        game_data,
+        record.species_override_for_stats,
+    );
+    let new_stats = level_calculations::get_usual_evolution_stage_for_level(
+        &rules,
+        level,
+        target,
+        game_data,
+        species_override_for_stats,
+    );
+
+    let (strength, strength_refund) = clamp_stat(
+        record.stat_strength,
+        &old_stats.strength,
+        &new_stats.strength,
+    );
+    let (dexterity, dexterity_refund) = clamp_stat(
+        record.stat_dexterity,
+        &old_stats.dexterity,
+        &new_stats.dexterity,
+    );
+    let (vitality, vitality_refund) = clamp_stat(
+        record.stat_vitality,
+        &old_stats.vitality,
+        &new_stats.vitality,
+    );
+    let (special, special_refund) =
+        clamp_stat(record.stat_special, &old_stats.special, &new_stats.special);
+    let (insight, insight_refund) =
+        clamp_stat(record.stat_insight, &old_stats.insight, &new_stats.insight);
+    let refunded_points =
+        strength_refund + dexterity_refund + vitality_refund + special_refund + insight_refund;
+
+    let target_api_id = target.poke_api_id.0 as i64;
+    let result = sqlx::query!(
+        "UPDATE character SET species_api_id = ?, species_override_for_stats = ?, \
+        stat_strength = ?, stat_dexterity = ?, stat_vitality = ?, stat_special = ?, stat_insight = ?, \
+        stat_edit_strength = ?, stat_edit_dexterity = ?, stat_edit_vitality = ?, stat_edit_special = ?, stat_edit_insight = ? \
+        WHERE id = ? AND species_api_id = ?",
+        target_api_id,
+        species_override_for_stats,
+        strength,
+        dexterity,
+        vitality,
+        special,
+        insight,
+        strength,
+        dexterity,
+        vitality,
+        special,
+        insight,
+        character.id,
+        record.species_api_id,
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    if result.rows_affected() != 1 {
+        return Err(Box::new(ValidationError::new(
+            "Something went wrong while evolving. Did someone else edit this character at the same time?",
+        )));
+    }
+
+    let gender = Gender::from_phenotype(record.phenotype);
+    create_emojis_for_pokemon(&ctx, target, &gender, record.is_shiny).await;
+
+    let old_emoji = emoji::get_pokemon_emoji(
+        ctx.serenity_context(),
+        &ctx.data().database,
+        record.guild_id,
+        current,
+        &gender,
+        record.is_shiny,
+    )
+    .await
+    .unwrap_or_default();
+    let new_emoji = emoji::get_pokemon_emoji(
+        ctx.serenity_context(),
+        &ctx.data().database,
+        record.guild_id,
+        target,
+        &gender,
+        record.is_shiny,
+    )
+    .await
+    .unwrap_or_default();
+
+    let refund_notification = if refunded_points > 0 {
+        format!(
+            "\n{} combat points didn't fit into {}'s stat limits and can be spent again.",
+            refunded_points, target.name
+        )
+    } else {
+        String::new()
+    };
+
+    ctx.say(format!(
+        "### {} What? {} is evolving! {}\n{} {} ➡️ {} {}\n{}, **{}** evolved into **{}**!{}",
+        emoji::PARTY_POPPER,
+        character.name,
+        emoji::PARTYING_FACE,
+        old_emoji,
+        current.name,
+        new_emoji,
+        target.name,
+        UserId::new(record.user_id as u64).mention(),
+        character.name,
+        target.name,
+        refund_notification,
+    ))
+    .await?;
+
+    update_character_post_with_poise_context(&ctx, character.id).await;
+    log_action(
+        &ActionType::Evolution,
+        LogActionArguments::triggered_by_user(&ctx),
+        format!(
+            "{} evolved from {} into {}",
+            character.name, current.name, target.name
+        ),
+    )
+    .await
+}
@@ -0,0 +1,698 @@
+use crate::Error;
+use crate::commands::autocompletion::autocomplete_character_name;
+use crate::commands::character_commands::create_character::send_character_post_placeholder;
+use crate::commands::character_commands::validate_user_input;
+use crate::commands::{
+    create_emojis, ensure_guild_exists, ensure_user_exists, ensure_user_owns_character_or_is_gm,
+    find_character, send_ephemeral_reply, send_error,
+};
+use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
+use crate::shared::character::update_character_post_with_poise_context;
+use crate::shared::character_condition::CharacterCondition;
+use crate::shared::character_creation::{NewCharacter, insert_new_character};
+use crate::shared::character_stats::{GenericCharacterStats, SingleCharacterStatType};
+use crate::shared::enums::{Gender, MysteryDungeonRank, PokemonTypeWithoutShadow, Skill};
+use crate::shared::errors::{ParseError, ValidationError};
+use crate::shared::game_data::pokemon::Pokemon;
+use crate::shared::game_data::{GameData, PokemonApiId};
+use crate::shared::guild_settings::{ProgressionRules, get_progression_rules};
+use crate::shared::inventory::InventoryItem;
+use crate::shared::utility::level_calculations;
+use crate::shared::{PoiseContext, character_moves, character_skills, inventory};
+use poise::ChoiceParameter;
+use poise::CreateReply;
+use serde::{Deserialize, Serialize};
+use serenity::all::{Attachment, CreateAttachment, Message, User};
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+/// Bump this whenever the format changes in a way older versions can't handle.
+const EXPORT_FORMAT_VERSION: u32 = 2;
+const MAX_ITEM_AMOUNT: i64 = u8::MAX as i64;
+const ALL_STATS: [SingleCharacterStatType; 10] = [
+    SingleCharacterStatType::Strength,
+    SingleCharacterStatType::Dexterity,
+    SingleCharacterStatType::Vitality,
+    SingleCharacterStatType::Special,
+    SingleCharacterStatType::Insight,
+    SingleCharacterStatType::Tough,
+    SingleCharacterStatType::Cool,
+    SingleCharacterStatType::Beauty,
+    SingleCharacterStatType::Cute,
+    SingleCharacterStatType::Clever,
+];
+
+#[derive(Serialize, Deserialize)]
+struct CharacterExport {
+    version: u32,
+    name: String,
+    species: String,
+    species_api_id: u16,
+    species_override_for_stats: Option<u16>,
+    is_shiny: bool,
+    phenotype: i64,
+    experience: i64,
+    money: i64,
+    battle_points: i64,
+    backpack_upgrade_count: i64,
+    is_hidden_ability_unlocked: bool,
+    is_retired: bool,
+    #[serde(default)]
+    condition: ConditionExport,
+    counters: CounterExport,
+    combat_stats: CombatStatExport,
+    combat_stats_edit: CombatStatExport,
+    social_stats: SocialStatExport,
+    social_stats_edit: SocialStatExport,
+    tera_charges: Vec<TeraChargeExport>,
+    inventory: Vec<InventoryExport>,
+    moves: Vec<String>,
+    unlocked_moves: Vec<String>,
+    #[serde(default)]
+    skills: Vec<SkillExport>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CounterExport {
+    total_spar_count: i64,
+    weekly_spar_count: i64,
+    total_new_player_tour_count: i64,
+    total_new_player_combat_tutorial_count: i64,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct ConditionExport {
+    hp_lost: i64,
+    will_lost: i64,
+    wounds: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CombatStatExport {
+    strength: i64,
+    dexterity: i64,
+    vitality: i64,
+    special: i64,
+    insight: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SocialStatExport {
+    tough: i64,
+    cool: i64,
+    beauty: i64,
+    cute: i64,
+    clever: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TeraChargeExport {
+    tera_type: String,
+    unlocked: i64,
+    used: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct InventoryExport {
+    item_name: String,
+    amount: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SkillExport {
+    skill: String,
+    rank: i64,
+}
+
+/// Export a character into a JSON file, e.g. to move it to another server.
+#[poise::command(slash_command, guild_only)]
+pub async fn export_character(
+    ctx: PoiseContext<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character_or_is_gm(
+        ctx.author(),
+        ctx.author_member()
+            .await
+            .expect("author_member should be set within guild context."),
+        &character,
+    )?;
+
+    let database = &ctx.data().database;
+    let game_data = ctx.data().game.get_by_context(&ctx).await;
+    let record = sqlx::query!("SELECT * FROM character WHERE id = ?", character.id)
+        .fetch_one(database)
+        .await?;
+
+    let species = game_data
+        .pokemon_by_api_id
+        .get(&PokemonApiId(record.species_api_id as u16))
+        .map(|x| x.name.clone())
+        .unwrap_or_default();
+
+    let skills = character_skills::get_character_skills(database, character.id).await?;
+    let mut tera_charges = Vec::new();
+    for tera_type in PokemonTypeWithoutShadow::iter() {
+        let (unlocked, used) = sqlx::query_as::<_, (i64, i64)>(&format!(
+            "SELECT {}, {} FROM character WHERE id = ?",
+            tera_type.get_tera_unlocked_column(),
+            tera_type.get_tera_used_column()
+        ))
+        .bind(character.id)
+        .fetch_one(database)
+        .await?;
+
+        if unlocked > 0 || used > 0 {
+            tera_charges.push(TeraChargeExport {
+                tera_type: format!("{:?}", tera_type),
+                unlocked,
+                used,
+            });
+        }
+    }
+
+    let export = CharacterExport {
+        version: EXPORT_FORMAT_VERSION,
+        name: record.name,
+        species,
+        species_api_id: record.species_api_id as u16,
+        species_override_for_stats: record.species_override_for_stats.map(|x| x as u16),
+        is_shiny: record.is_shiny,
+        phenotype: record.phenotype,
+        experience: record.experience,
+        money: record.money,
+        battle_points: record.battle_points,
+        backpack_upgrade_count: record.backpack_upgrade_count,
+        is_hidden_ability_unlocked: record.is_hidden_ability_unlocked,
+        is_retired: record.is_retired,
+        condition: ConditionExport {
+            hp_lost: record.hp_lost,
+            will_lost: record.will_lost,
+            wounds: record.wounds,
+        },
+        counters: CounterExport {
+            total_spar_count: record.total_spar_count,
+            weekly_spar_count: record.weekly_spar_count,
+            total_new_player_tour_count: record.total_new_player_tour_count,
+            total_new_player_combat_tutorial_count: record.total_new_player_combat_tutorial_count,
+        },
+        combat_stats: CombatStatExport {
+            strength: record.stat_strength,
+            dexterity: record.stat_dexterity,
+            vitality: record.stat_vitality,
+            special: record.stat_special,
+            insight: record.stat_insight,
+        },
+        combat_stats_edit: CombatStatExport {
+            strength: record.stat_edit_strength,
+            dexterity: record.stat_edit_dexterity,
+            vitality: record.stat_edit_vitality,
+            special: record.stat_edit_special,
+            insight: record.stat_edit_insight,
+        },
+        social_stats: SocialStatExport {
+            tough: record.stat_tough,
+            cool: record.stat_cool,
+            beauty: record.stat_beauty,
+            cute: record.stat_cute,
+            clever: record.stat_clever,
+        },
+        social_stats_edit: SocialStatExport {
+            tough: record.stat_edit_tough,
+            cool: record.stat_edit_cool,
+            beauty: record.stat_edit_beauty,
+            cute: record.stat_edit_cute,
+            clever: record.stat_edit_clever,
+        },
+        tera_charges,
+        inventory: inventory::get_inventory(database, character.id)
+            .await?
+            .into_iter()
+            .map(|x| InventoryExport {
+                item_name: x.item_name,
+                amount: x.amount,
+            })
+            .collect(),
+        moves: character_moves::get_known_moves(database, character.id).await?,
+        unlocked_moves: character_moves::get_unlocked_moves(database, character.id).await?,
+        skills: Skill::iter()
+            .filter(|x| skills.get(*x) > 0)
+            .map(|x| SkillExport {
+                skill: x.to_string(),
+                rank: skills.get(x),
+            })
+            .collect(),
+    };
+
+    let json = serde_json::to_string_pretty(&export)?;
+    ctx.send(
+        CreateReply::default()
+            .content(format!(
+                "Here's **{}**! Use `/import_character` to bring them over to another server.",
+                character.name
+            ))
+            .attachment(CreateAttachment::bytes(
+                json.into_bytes(),
+                format!("{}.json", character.name),
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+fn validate_export(
+    export: &CharacterExport,
+    game_data: &GameData,
+    pokemon: &Pokemon,
+    rules: &ProgressionRules,
+) -> Result<(), ValidationError> {
+    if export.version != EXPORT_FORMAT_VERSION {
+        return Err(ValidationError::new(format!(
+            "This file uses format version {}, but only version {} is supported.",
+            export.version, EXPORT_FORMAT_VERSION
+        )));
+    }
+
+    if let Some(species_override) = export.species_override_for_stats
+        && !game_data
+            .pokemon_by_api_id
+            .contains_key(&PokemonApiId(species_override))
+    {
+        return Err(ValidationError::new(format!(
+            "Unable to find the species used as stat override (API ID {}) on this server.",
+            species_override
+        )));
+    }
+
+    for tera_charge in &export.tera_charges {
+        if PokemonTypeWithoutShadow::from_str(&tera_charge.tera_type).is_err() {
+            return Err(ValidationError::new(format!(
+                "Unknown terastallization type: {}",
+                tera_charge.tera_type
+            )));
+        }
+        if tera_charge.used < 0 || tera_charge.used > tera_charge.unlocked {
+            return Err(ValidationError::new(format!(
+                "Invalid amount of {} terastallization charges.",
+                tera_charge.tera_type
+            )));
+        }
+    }
+
+    for entry in &export.inventory {
+        if InventoryItem::find(game_data, &entry.item_name).is_none() {
+            return Err(ValidationError::new(format!(
+                "Unable to find an item named **{}** on this server.",
+                entry.item_name
+            )));
+        }
+        if entry.amount < 1 || entry.amount > MAX_ITEM_AMOUNT {
+            return Err(ValidationError::new(format!(
+                "Invalid amount of **{}**: {}",
+                entry.item_name, entry.amount
+            )));
+        }
+    }
+
+    for move_name in export.moves.iter().chain(export.unlocked_moves.iter()) {
+        if !game_data.moves.contains_key(&move_name.to_lowercase()) {
+            return Err(ValidationError::new(format!(
+                "Unable to find a move named **{}** on this server.",
+                move_name
+            )));
+        }
+    }
+
+    if export.experience < 0
+        || export.money < 0
+        || export.battle_points < 0
+        || export.backpack_upgrade_count < 0
+    {
+        return Err(ValidationError::new(
+            "Experience, money, battle points and backpack upgrades can't be negative.",
+        ));
+    }
+
+    let level = level_calculations::calculate_level_from_experience(rules, export.experience);
+    let rank = MysteryDungeonRank::from_level(level);
+    let mut invested_skill_points = 0;
+    for entry in &export.skills {
+        if Skill::from_str(&entry.skill).is_err()
+            || entry.rank < 0
+            || entry.rank > rank.max_skill_rank() as i64
+        {
+            return Err(ValidationError::new(format!(
+                "Invalid skill: {}",
+                entry.skill
+            )));
+        }
+        invested_skill_points += entry.rank;
+    }
+    if invested_skill_points > rank.skill_points() as i64 {
+        return Err(ValidationError::new(
+            "The character has invested more skill points than their rank allows.",
+        ));
+    }
+
+    let stat_species = level_calculations::get_usual_evolution_stage_for_level(
+        rules,
+        level,
+        pokemon,
+        game_data,
+        export.species_override_for_stats.map(|x| x as i64),
+    );
+    let available_combat_points =
+        level_calculations::calculate_available_combat_points(rules, level);
+    let available_social_points = rules.social_stat_points(&rank);
+    for (combat, social) in [
+        (&export.combat_stats, &export.social_stats),
+        (&export.combat_stats_edit, &export.social_stats_edit),
+    ] {
+        let combat_stats = GenericCharacterStats::from_combat(
+            stat_species,
+            combat.strength,
+            combat.dexterity,
+            combat.vitality,
+            combat.special,
+            combat.insight,
+        );
+        let social_stats = GenericCharacterStats::from_social(
+            social.tough,
+            social.cool,
+            social.beauty,
+            social.cute,
+            social.clever,
+        );
+
+        for stat in ALL_STATS {
+            let value = if stat.is_combat_stat() {
+                combat_stats.get(stat)
+            } else {
+                social_stats.get(stat)
+            };
+            if value.current < value.species_min {
+                return Err(ValidationError::new(format!(
+                    "{} can't be lower than {} for this species.",
+                    stat.name(),
+                    value.species_min
+                )));
+            }
+            if !stat.is_combat_stat() && value.current > value.species_max {
+                return Err(ValidationError::new(format!(
+                    "{} can't be higher than {}.",
+                    stat.name(),
+                    value.species_max
+                )));
+            }
+        }
+
+        // Stats above the species max are limit breaks, which need to be paid for with stat points.
+        if combat_stats.calculate_invested_stat_points(rules) > available_combat_points {
+            return Err(ValidationError::new(
+                "The character has invested more combat stat points than their level allows.",
+            ));
+        }
+        if social_stats.calculate_invested_stat_points(rules) > available_social_points {
+            return Err(ValidationError::new(
+                "The character has invested more social stat points than their rank allows.",
+            ));
+        }
+    }
+
+    let condition = CharacterCondition::new(
+        &GenericCharacterStats::from_combat(
+            stat_species,
+            export.combat_stats.strength,
+            export.combat_stats.dexterity,
+            export.combat_stats.vitality,
+            export.combat_stats.special,
+            export.combat_stats.insight,
+        ),
+        export.condition.hp_lost,
+        export.condition.will_lost,
+        export.condition.wounds,
+    );
+    if condition.hp_lost < 0
+        || condition.hp_lost > condition.max_hp
+        || condition.will_lost < 0
+        || condition.will_lost > condition.max_will
+        || condition.wounds < 0
+    {
+        return Err(ValidationError::new(
+            "Lost HP and Will need to be between 0 and their max, and wounds can't be negative.",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Import a character from a JSON file created with /export_character.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn import_character(
+    ctx: PoiseContext<'_>,
+    #[description = "Who owns the character?"] player: User,
+    #[description = "The exported JSON file."] file: Attachment,
+    #[description = "Optional. Defaults to the name inside the file."] name: Option<String>,
+) -> Result<(), Error> {
+    let content = file.download().await?;
+    let export: CharacterExport = match serde_json::from_slice(&content) {
+        Ok(export) => export,
+        Err(e) => {
+            return Err(Box::new(ParseError::new(format!(
+                "Unable to parse that file: {}",
+                e
+            ))));
+        }
+    };
+
+    let name = name.unwrap_or(export.name.clone());
+    if let Err(e) = validate_user_input(name.as_str()) {
+        return send_error(&ctx, e).await;
+    }
+
+    let game_data = ctx.data().game.get_by_context(&ctx).await;
+    let Some(pokemon) = game_data
+        .pokemon
+        .get(&export.species.to_lowercase())
+        .or(game_data
+            .pokemon_by_api_id
+            .get(&PokemonApiId(export.species_api_id)))
+    else {
+        return Err(Box::new(ValidationError::new(format!(
+            "Unable to find a pokemon named **{}** on this server.",
+            export.species
+        ))));
+    };
+
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    let rules = get_progression_rules(&ctx.data().database, guild_id).await?;
+    validate_export(&export, game_data, pokemon, &rules)?;
+
+    let new_character = NewCharacter {
+        user_id: player.id.get() as i64,
+        name,
+        pokemon,
+        is_shiny: export.is_shiny,
+        gender: Gender::from_phenotype(export.phenotype),
+        exp: export.experience,
+        money: export.money,
+    };
+
+    let Some(message) = send_character_post_placeholder(&ctx).await? else {
+        return Ok(());
+    };
+    ensure_guild_exists(&ctx, guild_id).await;
+    ensure_user_exists(&ctx, new_character.user_id, guild_id).await;
+
+    let character_id =
+        match write_import(&ctx, game_data, &message, &rules, &new_character, &export).await {
+            Ok(character_id) => character_id,
+            Err(e) => {
+                let _ = message.delete(&ctx).await;
+                return Err(e);
+            }
+        };
+
+    create_emojis::create_emojis_for_pokemon(
+        &ctx,
+        pokemon,
+        &new_character.gender,
+        new_character.is_shiny,
+    )
+    .await;
+
+    send_ephemeral_reply(&ctx, "Character has been successfully imported!").await?;
+    update_character_post_with_poise_context(&ctx, character_id).await;
+    log_action(
+        &ActionType::Initialization,
+        LogActionArguments::triggered_by_user(&ctx),
+        format!(
+            "Imported character {} ({}) with {} exp.",
+            new_character.name, pokemon.name, new_character.exp
+        ),
+    )
+    .await?;
+    ctx.data()
+        .cache
+        .update_character_names(&ctx.data().database)
+        .await;
+
+    Ok(())
+}
+
+/// Writes the character and everything that belongs to it within a single transaction,
+/// so a failing import won't leave a half-imported character behind.
+async fn write_import(
+    ctx: &PoiseContext<'_>,
+    game_data: &GameData,
+    message: &Message,
+    rules: &ProgressionRules,
+    new_character: &NewCharacter<'_>,
+    export: &CharacterExport,
+) -> Result<i64, Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    let mut transaction = ctx.data().database.begin().await?;
+    let Ok(character_id) = insert_new_character(
+        &mut *transaction,
+        &ctx.data().game.base_data,
+        guild_id,
+        message.channel_id.get() as i64,
+        message.id.get() as i64,
+        rules,
+        new_character,
+    )
+    .await
+    else {
+        return Err(Box::new(ValidationError::new(
+            "Something went wrong! Does a character with this name already exist for this specific player?",
+        )));
+    };
+
+    let species_override_for_stats = export.species_override_for_stats.map(|x| x as i64);
+    sqlx::query!(
+        "UPDATE character SET species_override_for_stats = ?, battle_points = ?, backpack_upgrade_count = ?, \
+        is_hidden_ability_unlocked = ?, is_retired = ?, hp_lost = ?, will_lost = ?, wounds = ?, \
+        total_spar_count = ?, weekly_spar_count = ?, total_new_player_tour_count = ?, total_new_player_combat_tutorial_count = ?, \
+        stat_strength = ?, stat_dexterity = ?, stat_vitality = ?, stat_special = ?, stat_insight = ?, \
+        stat_edit_strength = ?, stat_edit_dexterity = ?, stat_edit_vitality = ?, stat_edit_special = ?, stat_edit_insight = ?, \
+        stat_tough = ?, stat_cool = ?, stat_beauty = ?, stat_cute = ?, stat_clever = ?, \
+        stat_edit_tough = ?, stat_edit_cool = ?, stat_edit_beauty = ?, stat_edit_cute = ?, stat_edit_clever = ? \
+        WHERE id = ?",
+        species_override_for_stats,
+        export.battle_points,
+        export.backpack_upgrade_count,
+        export.is_hidden_ability_unlocked,
+        export.is_retired,
+        export.condition.hp_lost,
+        export.condition.will_lost,
+        export.condition.wounds,
+        export.counters.total_spar_count,
+        export.counters.weekly_spar_count,
+        export.counters.total_new_player_tour_count,
+        export.counters.total_new_player_combat_tutorial_count,
+        export.combat_stats.strength,
+        export.combat_stats.dexterity,
+        export.combat_stats.vitality,
+        export.combat_stats.special,
+        export.combat_stats.insight,
+        export.combat_stats_edit.strength,
+        export.combat_stats_edit.dexterity,
+        export.combat_stats_edit.vitality,
+        export.combat_stats_edit.special,
+        export.combat_stats_edit.insight,
+        export.social_stats.tough,
+        export.social_stats.cool,
+        export.social_stats.beauty,
+        export.social_stats.cute,
+        export.social_stats.clever,
+        export.social_stats_edit.tough,
+        export.social_stats_edit.cool,
+        export.social_stats_edit.beauty,
+        export.social_stats_edit.cute,
+        export.social_stats_edit.clever,
+        character_id
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    for tera_charge in &export.tera_charges {
+        let tera_type = PokemonTypeWithoutShadow::from_str(&tera_charge.tera_type)
+            .expect("Tera types have been validated before.");
+        sqlx::query(&format!(
+            "UPDATE character SET {} = ?, {} = ? WHERE id = ?",
+            tera_type.get_tera_unlocked_column(),
+            tera_type.get_tera_used_column()
+        ))
+        .bind(tera_charge.unlocked)
+        .bind(tera_charge.used)
+        .bind(character_id)
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    for entry in &export.inventory {
+        sqlx::query!(
+            "INSERT INTO character_inventory (character_id, item_name, amount) VALUES (?, ?, ?)",
+            character_id,
+            entry.item_name,
+            entry.amount
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    for move_name in &export.moves {
+        sqlx::query!(
+            "INSERT OR IGNORE INTO character_move (character_id, move_name) VALUES (?, ?)",
+            character_id,
+            move_name
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    for move_name in &export.unlocked_moves {
+        sqlx::query!(
+            "INSERT OR IGNORE INTO character_unlocked_move (character_id, move_name) VALUES (?, ?)",
+            character_id,
+            move_name
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    for entry in &export.skills {
+        sqlx::query!(
+            "INSERT OR IGNORE INTO character_skill (character_id, skill, rank, edit_rank) VALUES (?, ?, ?, ?)",
+            character_id,
+            entry.skill,
+            entry.rank,
+            entry.rank
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    let inventory = inventory::get_inventory(&mut *transaction, character_id).await?;
+    let backpack_slots = inventory::get_backpack_slots(&mut *transaction, character_id).await?;
+    let used_slots = inventory::count_used_slots(game_data, &inventory);
+    if used_slots > backpack_slots {
+        return Err(Box::new(ValidationError::new(format!(
+            "The backpack would need {} slots, but only {} are available on this server.",
+            used_slots, backpack_slots
+        ))));
+    }
+
+    transaction.commit().await?;
+    Ok(character_id)
+}
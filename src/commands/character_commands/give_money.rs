@@ -4,10 +4,11 @@ use crate::commands::character_commands::change_character_stat_after_validation;
 use crate::commands::{
     Error, ensure_character_has_money, ensure_user_owns_character, find_character,
 };
-use crate::shared::action_log::ActionType;
+use crate::shared::action_log::{ActionType, create_undo_button};
 use crate::shared::cache::CharacterCacheItem;
 use crate::shared::errors::ValidationError;
 use crate::shared::{PoiseContext, emoji};
+use poise::CreateReply;
 
 async fn transfer_money_between_characters<'a>(
     ctx: &PoiseContext<'a>,
@@ -46,13 +47,17 @@ async fn transfer_money_between_characters<'a>(
         )
         .await
         {
-            ctx.say(format!(
-                "***{}** gave {} {} to **{}***!",
-                giver.name,
-                amount,
-                emoji::POKE_COIN,
-                receiver.name
-            ))
+            ctx.send(
+                CreateReply::default()
+                    .content(format!(
+                        "***{}** gave {} {} to **{}***!",
+                        giver.name,
+                        amount,
+                        emoji::POKE_COIN,
+                        receiver.name
+                    ))
+                    .components(vec![create_undo_button(ctx.id())]),
+            )
             .await?;
         } else {
             // TODO: The undo might fail.
@@ -76,7 +81,6 @@ pub async fn give_money(
     #[autocomplete = "autocomplete_character_name"]
     receiver: String,
 ) -> Result<(), Error> {
-    // TODO: Button to undo the transaction which lasts for a minute or so.
     let guild_id = ctx.guild_id().expect("Command is guild_only").get();
     let giver = find_character(ctx.data(), guild_id, &giver).await?;
     let receiver = find_character(ctx.data(), guild_id, &receiver).await?;
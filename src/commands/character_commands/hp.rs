@@ -0,0 +1,201 @@
+use crate::Error;
+use crate::commands::autocompletion::autocomplete_character_name;
+use crate::commands::{ensure_user_owns_character_or_is_gm, find_character};
+use crate::shared::PoiseContext;
+use crate::shared::action_log::{ActionType, LogActionArguments, create_undo_button, log_action};
+use crate::shared::cache::CharacterCacheItem;
+use crate::shared::character::update_character_post_with_poise_context;
+use crate::shared::character_condition::{
+    ConditionChange, change_character_condition, get_character_condition,
+};
+use crate::shared::errors::ValidationError;
+use poise::CreateReply;
+
+/// Track a character's current HP, Will and Wounds.
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("damage", "heal", "rest"),
+    subcommand_required
+)]
+pub async fn hp(_: PoiseContext<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+async fn find_character_for_condition_change(
+    ctx: &PoiseContext<'_>,
+    character: &str,
+) -> Result<CharacterCacheItem, Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, character).await?;
+    ensure_user_owns_character_or_is_gm(
+        ctx.author(),
+        ctx.author_member()
+            .await
+            .expect("author_member should be set within guild context."),
+        &character,
+    )?;
+
+    Ok(character)
+}
+
+async fn apply_condition_change(
+    ctx: &PoiseContext<'_>,
+    character: &CharacterCacheItem,
+    hp_lost: i64,
+    will_lost: i64,
+    wounds: i64,
+) -> Result<(), Error> {
+    let game_data = ctx.data().game.get_by_context(ctx).await;
+    let mut connection = ctx.data().database.acquire().await?;
+    let change = change_character_condition(
+        &mut connection,
+        game_data,
+        character.id,
+        hp_lost,
+        will_lost,
+        wounds,
+    )
+    .await?;
+
+    let description = describe_change(&change);
+    ctx.send(
+        CreateReply::default()
+            .content(format!(
+                "**{}**: {}\n{}",
+                character.name,
+                description,
+                change.condition.build_string()
+            ))
+            .components(vec![create_undo_button(ctx.id())]),
+    )
+    .await?;
+    update_character_post_with_poise_context(ctx, character.id).await;
+
+    let mut log_arguments = LogActionArguments::triggered_by_user(ctx);
+    for (field, delta) in [
+        ("hp_lost", change.hp_lost),
+        ("will_lost", change.will_lost),
+        ("wounds", change.wounds),
+    ] {
+        if delta != 0 {
+            log_arguments = log_arguments.with_character_change(character.id, field, delta);
+        }
+    }
+
+    log_action(
+        &ActionType::ConditionChange,
+        log_arguments,
+        format!("{}: {}", character.name, description),
+    )
+    .await
+}
+
+fn describe_change(change: &ConditionChange) -> String {
+    let mut parts = Vec::new();
+    if change.hp_lost > 0 {
+        parts.push(format!("lost {} HP", change.hp_lost));
+    } else if change.hp_lost < 0 {
+        parts.push(format!("recovered {} HP", -change.hp_lost));
+    }
+    if change.will_lost > 0 {
+        parts.push(format!("spent {} Will", change.will_lost));
+    } else if change.will_lost < 0 {
+        parts.push(format!("recovered {} Will", -change.will_lost));
+    }
+    if change.wounds > 0 {
+        parts.push(format!("received {} Wound(s)", change.wounds));
+    } else if change.wounds < 0 {
+        parts.push(format!("healed {} Wound(s)", -change.wounds));
+    }
+
+    if parts.is_empty() {
+        String::from("Nothing changed.")
+    } else {
+        parts.join(", ")
+    }
+}
+
+fn ensure_any_value_is_set(
+    hp: Option<u8>,
+    will: Option<u8>,
+    wounds: Option<u8>,
+) -> Result<(), Error> {
+    if hp.is_none() && will.is_none() && wounds.is_none() {
+        return Err(Box::new(ValidationError::new(
+            "Please specify at least one of hp, will or wounds.",
+        )));
+    }
+
+    Ok(())
+}
+
+/// Reduce a character's HP or Will, or give them Wounds.
+#[poise::command(slash_command)]
+pub async fn damage(
+    ctx: PoiseContext<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+    #[description = "How much HP is lost?"] hp: Option<u8>,
+    #[description = "How much Will is spent?"] will: Option<u8>,
+    #[description = "How many wounds are received?"] wounds: Option<u8>,
+) -> Result<(), Error> {
+    ensure_any_value_is_set(hp, will, wounds)?;
+    let character = find_character_for_condition_change(&ctx, &character).await?;
+    apply_condition_change(
+        &ctx,
+        &character,
+        hp.unwrap_or_default() as i64,
+        will.unwrap_or_default() as i64,
+        wounds.unwrap_or_default() as i64,
+    )
+    .await
+}
+
+/// Restore a character's HP or Will, or heal their Wounds.
+#[poise::command(slash_command)]
+pub async fn heal(
+    ctx: PoiseContext<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+    #[description = "How much HP is restored?"] hp: Option<u8>,
+    #[description = "How much Will is restored?"] will: Option<u8>,
+    #[description = "How many wounds are healed?"] wounds: Option<u8>,
+) -> Result<(), Error> {
+    ensure_any_value_is_set(hp, will, wounds)?;
+    let character = find_character_for_condition_change(&ctx, &character).await?;
+    apply_condition_change(
+        &ctx,
+        &character,
+        -(hp.unwrap_or_default() as i64),
+        -(will.unwrap_or_default() as i64),
+        -(wounds.unwrap_or_default() as i64),
+    )
+    .await
+}
+
+/// Fully restore a character's HP and Will. Wounds need to be healed separately.
+#[poise::command(slash_command)]
+pub async fn rest(
+    ctx: PoiseContext<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+) -> Result<(), Error> {
+    let character = find_character_for_condition_change(&ctx, &character).await?;
+    let game_data = ctx.data().game.get_by_context(&ctx).await;
+    let condition = {
+        let mut connection = ctx.data().database.acquire().await?;
+        get_character_condition(&mut connection, game_data, character.id).await?
+    };
+    apply_condition_change(
+        &ctx,
+        &character,
+        -condition.hp_lost,
+        -condition.will_lost,
+        0,
+    )
+    .await
+}
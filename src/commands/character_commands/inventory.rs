@@ -0,0 +1,340 @@
+use crate::Error;
+use crate::commands::autocompletion::{
+    autocomplete_character_name, autocomplete_item_or_potion, autocomplete_owned_character_name,
+};
+use crate::commands::{
+    ensure_user_owns_character, ensure_user_owns_character_or_is_gm, find_character,
+    is_user_admin_or_gm, send_ephemeral_reply,
+};
+use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
+use crate::shared::character::update_character_post_with_poise_context;
+use crate::shared::character_condition::{change_character_condition, get_character_condition};
+use crate::shared::errors::ValidationError;
+use crate::shared::inventory::{HealthRestoration, InventoryItem};
+use crate::shared::{PoiseContext, emoji, inventory};
+
+/// Manage a character's backpack.
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("show", "add", "remove", "use_item", "give"),
+    subcommand_required
+)]
+pub async fn inventory(_: PoiseContext<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+async fn find_inventory_item<'a>(
+    ctx: &PoiseContext<'a>,
+    item_name: &str,
+) -> Result<InventoryItem, Error> {
+    let game_data = ctx.data().game.get_by_context(ctx).await;
+    match InventoryItem::find(game_data, item_name) {
+        Some(item) => Ok(item),
+        None => Err(Box::new(ValidationError::new(format!(
+            "Unable to find an item or potion named **{}**, sorry!",
+            item_name
+        )))),
+    }
+}
+
+/// Removing stuff should still work in case an item got removed from the game data.
+async fn find_inventory_item_name<'a>(ctx: &PoiseContext<'a>, item_name: &str) -> String {
+    let game_data = ctx.data().game.get_by_context(ctx).await;
+    match InventoryItem::find(game_data, item_name) {
+        Some(item) => item.name,
+        None => item_name.to_string(),
+    }
+}
+
+/// Display what's inside a character's backpack.
+#[poise::command(slash_command)]
+pub async fn show(
+    ctx: PoiseContext<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    let game_data = ctx.data().game.get_by_context(&ctx).await;
+
+    let inventory = inventory::get_inventory(&ctx.data().database, character.id).await?;
+    let slots = inventory::get_backpack_slots(&ctx.data().database, character.id).await?;
+    let used_slots = inventory::count_used_slots(game_data, &inventory);
+
+    send_ephemeral_reply(
+        &ctx,
+        format!(
+            "### {} {}'s Backpack `({} / {})`\n{}",
+            emoji::BACKPACK,
+            character.name,
+            used_slots,
+            slots,
+            inventory::build_inventory_string(&inventory)
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Put items into a character's backpack. GM only.
+#[poise::command(slash_command)]
+pub async fn add(
+    ctx: PoiseContext<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+    #[description = "Which item?"]
+    #[autocomplete = "autocomplete_item_or_potion"]
+    item: String,
+    #[description = "How many? Defaults to 1."]
+    #[min = 1_u8]
+    amount: Option<u8>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    // Players get their items through rewards, shops and trades instead.
+    if !is_user_admin_or_gm(
+        ctx.author_member()
+            .await
+            .expect("author_member should be set within guild context."),
+    ) {
+        return Err(Box::new(ValidationError::new(
+            "Only GMs can add items to a backpack.",
+        )));
+    }
+
+    let item = find_inventory_item(&ctx, &item).await?;
+    let amount = amount.unwrap_or(1) as i64;
+    let game_data = ctx.data().game.get_by_context(&ctx).await;
+
+    let mut connection = ctx.data().database.acquire().await?;
+    inventory::add_item(
+        &mut connection,
+        game_data,
+        character.id,
+        &character.name,
+        &item,
+        amount,
+    )
+    .await?;
+
+    ctx.say(format!(
+        "Added {}x **{}** to {}'s backpack.",
+        amount, item.name, character.name
+    ))
+    .await?;
+    update_character_post_with_poise_context(&ctx, character.id).await;
+    log_action(
+        &ActionType::InventoryChange,
+        LogActionArguments::triggered_by_user(&ctx),
+        format!("Added {}x {} to {}", amount, item.name, character.name),
+    )
+    .await
+}
+
+/// Take items out of a character's backpack.
+#[poise::command(slash_command)]
+pub async fn remove(
+    ctx: PoiseContext<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+    #[description = "Which item?"]
+    #[autocomplete = "autocomplete_item_or_potion"]
+    item: String,
+    #[description = "How many? Defaults to 1."]
+    #[min = 1_u8]
+    amount: Option<u8>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character_or_is_gm(
+        ctx.author(),
+        ctx.author_member()
+            .await
+            .expect("author_member should be set within guild context."),
+        &character,
+    )?;
+
+    let item_name = find_inventory_item_name(&ctx, &item).await;
+    let amount = amount.unwrap_or(1) as i64;
+
+    let mut connection = ctx.data().database.acquire().await?;
+    inventory::remove_item(
+        &mut connection,
+        character.id,
+        &character.name,
+        &item_name,
+        amount,
+    )
+    .await?;
+
+    ctx.say(format!(
+        "Removed {}x **{}** from {}'s backpack.",
+        amount, item_name, character.name
+    ))
+    .await?;
+    update_character_post_with_poise_context(&ctx, character.id).await;
+    log_action(
+        &ActionType::InventoryChange,
+        LogActionArguments::triggered_by_user(&ctx),
+        format!("Removed {}x {} from {}", amount, item_name, character.name),
+    )
+    .await
+}
+
+/// Use a single use item from a character's backpack.
+#[poise::command(slash_command, rename = "use")]
+pub async fn use_item(
+    ctx: PoiseContext<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    character: String,
+    #[description = "Which item?"]
+    #[autocomplete = "autocomplete_item_or_potion"]
+    item: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character(ctx.author(), &character)?;
+
+    let item = find_inventory_item(&ctx, &item).await?;
+    if !item.single_use {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{}** isn't consumed on use. Just describe what {} does with it in your post!",
+            item.name, character.name
+        ))));
+    }
+
+    let game_data = ctx.data().game.get_by_context(&ctx).await;
+    let mut transaction = ctx.data().database.begin().await?;
+    inventory::remove_item(
+        &mut transaction,
+        character.id,
+        &character.name,
+        &item.name,
+        1,
+    )
+    .await?;
+
+    let mut message = format!("**{}** used **{}**!", character.name, item.name);
+    let mut log_arguments = LogActionArguments::triggered_by_user(&ctx);
+    let hp_lost_delta = match item.health_restoration() {
+        HealthRestoration::Amount(amount) => {
+            message.push_str(&format!("\n*Restores {} HP.*", amount));
+            Some(-(amount as i64))
+        }
+        HealthRestoration::Full => {
+            message.push_str("\n*Restores all HP.*");
+            let condition =
+                get_character_condition(&mut transaction, game_data, character.id).await?;
+            Some(-condition.hp_lost)
+        }
+        HealthRestoration::None => {
+            if item.effect.is_some() {
+                message.push_str(&format!(
+                    "\n*{} doesn't restore a fixed amount of HP, so HP wasn't changed. Use `/hp heal` in case it needs to be adjusted.*",
+                    item.name
+                ));
+            }
+            None
+        }
+    };
+    if let Some(hp_lost_delta) = hp_lost_delta {
+        let change = change_character_condition(
+            &mut transaction,
+            game_data,
+            character.id,
+            hp_lost_delta,
+            0,
+            0,
+        )
+        .await?;
+        message.push_str(&format!("\n{}", change.condition.build_string()));
+        if change.hp_lost != 0 {
+            log_arguments =
+                log_arguments.with_character_change(character.id, "hp_lost", change.hp_lost);
+        }
+    }
+    transaction.commit().await?;
+
+    if let Some(effect) = &item.effect {
+        message.push_str(&format!("\n{}", effect));
+    }
+
+    ctx.say(message).await?;
+    update_character_post_with_poise_context(&ctx, character.id).await;
+    log_action(
+        &ActionType::ItemUse,
+        log_arguments,
+        format!("{} used 1x {}", character.name, item.name),
+    )
+    .await
+}
+
+/// Hand items over to another character.
+#[poise::command(slash_command)]
+pub async fn give(
+    ctx: PoiseContext<'_>,
+    #[description = "Who gives the item?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    giver: String,
+    #[description = "Which item?"]
+    #[autocomplete = "autocomplete_item_or_potion"]
+    item: String,
+    #[description = "To whom?"]
+    #[autocomplete = "autocomplete_character_name"]
+    receiver: String,
+    #[description = "How many? Defaults to 1."]
+    #[min = 1_u8]
+    amount: Option<u8>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let giver = find_character(ctx.data(), guild_id, &giver).await?;
+    let receiver = find_character(ctx.data(), guild_id, &receiver).await?;
+    ensure_user_owns_character(ctx.author(), &giver)?;
+    if giver.id == receiver.id {
+        return Err(Box::new(ValidationError::new(
+            "*You successfully moved the item from one pocket to another. Ha. Ha.*",
+        )));
+    }
+
+    let item = find_inventory_item(&ctx, &item).await?;
+    let amount = amount.unwrap_or(1) as i64;
+    let game_data = ctx.data().game.get_by_context(&ctx).await;
+
+    let mut transaction = ctx.data().database.begin().await?;
+    inventory::remove_item(&mut transaction, giver.id, &giver.name, &item.name, amount).await?;
+    inventory::add_item(
+        &mut transaction,
+        game_data,
+        receiver.id,
+        &receiver.name,
+        &item,
+        amount,
+    )
+    .await?;
+    transaction.commit().await?;
+
+    ctx.say(format!(
+        "***{}** gave {}x {} to **{}***!",
+        giver.name, amount, item.name, receiver.name
+    ))
+    .await?;
+    update_character_post_with_poise_context(&ctx, giver.id).await;
+    update_character_post_with_poise_context(&ctx, receiver.id).await;
+    log_action(
+        &ActionType::TradeOutgoing,
+        LogActionArguments::triggered_by_user(&ctx),
+        format!("Removed {}x {} from {}", amount, item.name, giver.name),
+    )
+    .await?;
+    log_action(
+        &ActionType::TradeIncoming,
+        LogActionArguments::triggered_by_user(&ctx),
+        format!("Added {}x {} to {}", amount, item.name, receiver.name),
+    )
+    .await
+}
@@ -2,18 +2,29 @@ use crate::Error;
 use crate::commands::{parse_character_names, send_error};
 use crate::shared::action_log::{ActionType, LogActionArguments};
 use crate::shared::cache::CharacterCacheItem;
-use crate::shared::character::update_character_post_with_poise_context;
+use crate::shared::character::{
+    get_remaining_stat_points, update_character_post_with_poise_context,
+};
 use crate::shared::data::Data;
 use crate::shared::enums::{MysteryDungeonRank, PokemonTypeWithoutShadow};
+use crate::shared::guild_settings::get_progression_rules;
 use crate::shared::utility::{input_validation, level_calculations};
 use crate::shared::{PoiseContext, action_log, emoji};
-use poise::Command;
+use poise::{Command, CreateReply};
+use serenity::all::{Mentionable, UserId};
 
+mod apply_character;
+mod character_card;
 mod character_sheet;
 mod create_character;
 mod create_character_post;
 mod edit_character;
+mod edit_moves;
+mod evolve;
+mod export_character;
 mod give_money;
+mod hp;
+mod inventory;
 mod reset_character_stats;
 mod retire_character;
 mod reward_battle_points;
@@ -23,6 +34,7 @@ mod reward_giving_tour;
 mod reward_money;
 mod reward_spar;
 mod reward_terastallization;
+mod transfer_character;
 mod unlock_hidden_ability;
 mod unretire_character;
 mod upgrade_backpack;
@@ -30,9 +42,18 @@ mod use_terastallization;
 
 pub fn get_all_commands() -> Vec<Command<Data, Error>> {
     vec![
+        apply_character::apply_character(),
+        character_card::character_card(),
         character_sheet::character_sheet(),
         edit_character::edit_character(),
+        edit_moves::edit_moves(),
+        edit_moves::unlock_move(),
+        evolve::evolve(),
+        export_character::export_character(),
+        export_character::import_character(),
         give_money::give_money(),
+        hp::hp(),
+        inventory::inventory(),
         create_character::create_character(),
         create_character::initialize_character(),
         create_character_post::create_character_post(),
@@ -46,6 +67,7 @@ pub fn get_all_commands() -> Vec<Command<Data, Error>> {
         reward_giving_tour::reward_giving_tour(),
         reset_character_stats::reset_character_stats(),
         retire_character::retire_character(),
+        transfer_character::transfer_character(),
         unretire_character::unretire_character(),
         use_terastallization::use_terastallization(),
         reward_terastallization::reward_terastallization(),
@@ -187,19 +209,11 @@ pub async fn change_character_stat_after_validation<'a>(
                 to_or_from = "to";
 
                 if database_column == "experience" {
-                    let old_level = level_calculations::calculate_level_from_experience(record.value);
-                    let new_level = level_calculations::calculate_level_from_experience(record.value + amount);
+                    let rules = get_progression_rules(&ctx.data().database, character.guild_id as i64).await?;
+                    let old_level = level_calculations::calculate_level_from_experience(&rules, record.value);
+                    let new_level = level_calculations::calculate_level_from_experience(&rules, record.value + amount);
                     if new_level > old_level {
-                        let old_rank = MysteryDungeonRank::from_level(old_level as u8);
-                        let new_rank = MysteryDungeonRank::from_level(new_level as u8);
-
-                        let rank_notification = if new_rank > old_rank {
-                            format!(" They are now {}!", new_rank)
-                        } else {
-                            String::new()
-                        };
-
-                        let _ = ctx.say(format!("### {} Level Up! {}\n**{}** just reached level {}!{}", emoji::PARTY_POPPER, emoji::PARTYING_FACE, record.name, new_level, rank_notification)).await;
+                        announce_level_up(ctx, character, &record.name, old_level, new_level).await;
                     }
                 }
             } else {
@@ -209,7 +223,7 @@ pub async fn change_character_stat_after_validation<'a>(
 
             if action_type != &ActionType::DoNotLog {
                 action_log::log_action(action_type,
-                                       LogActionArguments::triggered_by_user(&ctx),
+                                       LogActionArguments::triggered_by_user(&ctx).with_character_change(record.id, database_column, amount),
                                        format!("{} {} {} {} {}", added_or_removed, amount.abs(), action, to_or_from, record.name).as_str()).await
             } else {
                 Ok(())
@@ -221,6 +235,52 @@ pub async fn change_character_stat_after_validation<'a>(
     }
 }
 
+/// Congratulates the player, mentioning rank ups and offering buttons to spend the new stat points.
+pub async fn announce_level_up(
+    ctx: &PoiseContext<'_>,
+    character: &CharacterCacheItem,
+    character_name: &str,
+    old_level: i64,
+    new_level: i64,
+) {
+    let old_rank = MysteryDungeonRank::from_level(old_level);
+    let new_rank = MysteryDungeonRank::from_level(new_level);
+    let rank_notification = if new_rank > old_rank {
+        format!(" They are now {}!", new_rank)
+    } else {
+        String::new()
+    };
+
+    let game_data = ctx.data().game.get_by_context(ctx).await;
+    let remaining_points =
+        get_remaining_stat_points(&ctx.data().database, game_data, character.id).await;
+
+    let mut message = format!(
+        "### {} Level Up! {}\n{}, **{}** just reached level {}!{}",
+        emoji::PARTY_POPPER,
+        emoji::PARTYING_FACE,
+        UserId::new(character.user_id).mention(),
+        character_name,
+        new_level,
+        rank_notification
+    );
+
+    let mut reply = CreateReply::default();
+    if let Ok(remaining_points) = remaining_points
+        && let Some(buttons) = remaining_points.create_stat_edit_buttons(character.id)
+    {
+        message.push_str(&format!(
+            "\nThey now have **{}** combat, **{}** social and **{}** skill points left to spend.",
+            remaining_points.combat.max(0),
+            remaining_points.social.max(0),
+            remaining_points.skills.max(0)
+        ));
+        reply = reply.components(vec![buttons]);
+    }
+
+    let _ = ctx.send(reply.content(message)).await;
+}
+
 pub fn validate_user_input<'a>(text: &str) -> Result<(), &'a str> {
     input_validation::validate_user_input(text, 30)
 }
@@ -5,6 +5,7 @@ use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
 use crate::shared::cache::CharacterCacheItem;
 use crate::shared::character::update_character_post_with_poise_context;
 use crate::shared::game_data::PokemonApiId;
+use crate::shared::guild_settings::get_progression_rules;
 use crate::shared::utility::level_calculations;
 
 /// Resets a characters stats to its default values.
@@ -58,9 +59,11 @@ pub async fn reset_db_stats(
         .get(&species_id)
         .expect("DB IDs should always be mappable.");
 
-    let level = level_calculations::calculate_level_from_experience(record.experience);
+    let rules = get_progression_rules(&ctx.data().database, character.guild_id as i64).await?;
+    let level = level_calculations::calculate_level_from_experience(&rules, record.experience);
 
     let pokemon_evolution_form_for_stats = level_calculations::get_usual_evolution_stage_for_level(
+        &rules,
         level,
         used_poke_species,
         &ctx.data().game.base_data,
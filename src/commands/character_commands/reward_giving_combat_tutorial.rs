@@ -2,8 +2,9 @@ use crate::commands::autocompletion::autocomplete_character_name;
 use crate::commands::character_commands::change_character_stat_after_validation;
 use crate::commands::{Error, parse_user_input_to_character, send_error};
 use crate::shared::PoiseContext;
-use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
+use crate::shared::action_log::{ActionType, LogActionArguments, create_undo_button, log_action};
 use crate::shared::cache::CharacterCacheItem;
+use poise::CreateReply;
 
 /// Reward players for giving a combat tutorial to new players.
 #[allow(clippy::too_many_arguments)]
@@ -18,13 +19,12 @@ pub async fn reward_giving_combat_tutorial(
     #[autocomplete = "autocomplete_character_name"]
     character: String,
 ) -> Result<(), Error> {
-    // TODO: Button to undo the transaction which lasts for a minute or so.
     match handle_giving_combat_tutorial(&ctx, character).await {
         Ok(result) => {
-            ctx.say(format!(
+            ctx.send(CreateReply::default().content(format!(
                 "Tracked that {} gave a combat tutorial. They gained {} experience points as a reward!",
                 &result.organizer.name, result.experience_value,
-            ))
+            )).components(vec![create_undo_button(ctx.id())]))
             .await?;
         }
         Err(err) => {
@@ -109,7 +109,11 @@ async fn track_combat_tutorial_for_character<'a>(
 
     let _ = log_action(
         &ActionType::NewPlayerCombatTutorial,
-        LogActionArguments::triggered_by_user(&ctx),
+        LogActionArguments::triggered_by_user(&ctx).with_character_change(
+            character.id,
+            "total_new_player_combat_tutorial_count",
+            1,
+        ),
         &format!("{} gave a combat tutorial!", character.name),
     )
     .await;
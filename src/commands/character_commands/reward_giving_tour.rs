@@ -2,8 +2,9 @@ use crate::commands::autocompletion::autocomplete_character_name;
 use crate::commands::character_commands::change_character_stat_after_validation;
 use crate::commands::{Error, parse_user_input_to_character, send_error};
 use crate::shared::PoiseContext;
-use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
+use crate::shared::action_log::{ActionType, LogActionArguments, create_undo_button, log_action};
 use crate::shared::cache::CharacterCacheItem;
+use poise::CreateReply;
 
 /// Reward players for giving a tour to new characters.
 #[allow(clippy::too_many_arguments)]
@@ -18,13 +19,12 @@ pub async fn reward_giving_tour(
     #[autocomplete = "autocomplete_character_name"]
     character: String,
 ) -> Result<(), Error> {
-    // TODO: Button to undo the transaction which lasts for a minute or so.
     match handle_giving_tour(&ctx, character).await {
         Ok(result) => {
-            ctx.say(format!(
+            ctx.send(CreateReply::default().content(format!(
                 "Tracked that {} gave a tour. They gained {} experience points as a reward!",
                 &result.organizer.name, result.experience_value,
-            ))
+            )).components(vec![create_undo_button(ctx.id())]))
             .await?;
         }
         Err(err) => {
@@ -104,7 +104,11 @@ async fn track_tour_for_character<'a>(
 
     let _ = log_action(
         &ActionType::NewPlayerTour,
-        LogActionArguments::triggered_by_user(ctx),
+        LogActionArguments::triggered_by_user(ctx).with_character_change(
+            character.id,
+            "total_new_player_tour_count",
+            1,
+        ),
         &format!("{} gave a tour to a new character!", character.name),
     )
     .await;
@@ -1,8 +1,9 @@
 use crate::commands::autocompletion::autocomplete_character_name;
 use crate::commands::character_commands::{build_character_list, change_character_stat};
 use crate::commands::{Error, parse_variadic_args, send_error};
-use crate::shared::action_log::ActionType;
+use crate::shared::action_log::{ActionType, create_undo_button};
 use crate::shared::{PoiseContext, emoji};
+use poise::CreateReply;
 
 /// Reward players with cash.
 #[allow(clippy::too_many_arguments)]
@@ -26,7 +27,6 @@ pub async fn reward_money(
     #[autocomplete = "autocomplete_character_name"] character8: Option<String>,
     #[autocomplete = "autocomplete_character_name"] character9: Option<String>,
 ) -> Result<(), Error> {
-    // TODO: Button to undo the transaction which lasts for a minute or so.
     let args = parse_variadic_args(
         character1, character2, character3, character4, character5, character6, character7,
         character8, character9,
@@ -34,12 +34,16 @@ pub async fn reward_money(
 
     match change_character_stat(&ctx, "money", &args, amount as i64, ActionType::Reward).await {
         Ok(characters) => {
-            ctx.say(format!(
-                "{} received {} {}!",
-                build_character_list(&characters),
-                amount,
-                emoji::POKE_COIN
-            ))
+            ctx.send(
+                CreateReply::default()
+                    .content(format!(
+                        "{} received {} {}!",
+                        build_character_list(&characters),
+                        amount,
+                        emoji::POKE_COIN
+                    ))
+                    .components(vec![create_undo_button(ctx.id())]),
+            )
             .await?;
         }
         Err(err) => {
@@ -4,9 +4,10 @@ use crate::commands::character_commands::{
 };
 use crate::commands::{Error, parse_character_names, parse_variadic_args, send_error};
 use crate::shared::PoiseContext;
-use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
+use crate::shared::action_log::{ActionType, LogActionArguments, create_undo_button, log_action};
 use crate::shared::cache::CharacterCacheItem;
 use crate::shared::character::update_character_post_with_poise_context;
+use poise::CreateReply;
 
 /// Reward players for sparring outside of quests.
 #[allow(clippy::too_many_arguments)]
@@ -31,7 +32,6 @@ pub async fn reward_spar(
     #[autocomplete = "autocomplete_character_name"] character8: Option<String>,
     #[autocomplete = "autocomplete_character_name"] character9: Option<String>,
 ) -> Result<(), Error> {
-    // TODO: Button to undo the transaction which lasts for a minute or so.
     let args = parse_variadic_args(
         character1,
         Some(character2),
@@ -47,26 +47,38 @@ pub async fn reward_spar(
     match handle_sparring(&ctx, &args).await {
         Ok(result) => {
             if result.participants_who_gained_exp.is_empty() {
-                ctx.say(format!(
-                    "Tracked a sparring session for {}.\n*Everyone's already reached the weekly limit for sparring exp rewards.*",
-                    build_character_list(&result.participants),
-                ))
-                    .await?;
+                ctx.send(
+                    CreateReply::default()
+                        .content(format!(
+                            "Tracked a sparring session for {}.\n*Everyone's already reached the weekly limit for sparring exp rewards.*",
+                            build_character_list(&result.participants),
+                        ))
+                        .components(vec![create_undo_button(ctx.id())]),
+                )
+                .await?;
             } else if result.participants_who_gained_exp.len() != result.participants.len() {
-                ctx.say(format!(
-                    "Tracked a sparring session for {}.\n{} received {} experience points. *(Everyone who did not receive any already reached the weekly limit)*",
-                    build_character_list(&result.participants),
-                    build_character_list(&result.participants_who_gained_exp),
-                    result.experience_value,
-                ))
-                    .await?;
+                ctx.send(
+                    CreateReply::default()
+                        .content(format!(
+                            "Tracked a sparring session for {}.\n{} received {} experience points. *(Everyone who did not receive any already reached the weekly limit)*",
+                            build_character_list(&result.participants),
+                            build_character_list(&result.participants_who_gained_exp),
+                            result.experience_value,
+                        ))
+                        .components(vec![create_undo_button(ctx.id())]),
+                )
+                .await?;
             } else {
-                ctx.say(format!(
-                    "Tracked a sparring session for {}.\n{} received {} experience points!",
-                    build_character_list(&result.participants),
-                    build_character_list(&result.participants_who_gained_exp),
-                    result.experience_value,
-                ))
+                ctx.send(
+                    CreateReply::default()
+                        .content(format!(
+                            "Tracked a sparring session for {}.\n{} received {} experience points!",
+                            build_character_list(&result.participants),
+                            build_character_list(&result.participants_who_gained_exp),
+                            result.experience_value,
+                        ))
+                        .components(vec![create_undo_button(ctx.id())]),
+                )
                 .await?;
             }
         }
@@ -156,7 +168,9 @@ async fn track_spar_for_character<'a>(
 
     let _ = log_action(
         &ActionType::Spar,
-        LogActionArguments::triggered_by_user(ctx),
+        LogActionArguments::triggered_by_user(ctx)
+            .with_character_change(character.id, "total_spar_count", 1)
+            .with_character_change(character.id, "weekly_spar_count", 1),
         &format!("Tracked a sparring session for {}!", character.name),
     )
     .await;
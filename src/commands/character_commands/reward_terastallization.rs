@@ -2,10 +2,11 @@ use crate::commands::autocompletion::{autocomplete_character_name, autocomplete_
 use crate::commands::character_commands::{build_character_list, change_character_stat};
 use crate::commands::{Error, parse_variadic_args};
 use crate::shared::PoiseContext;
-use crate::shared::action_log::ActionType;
+use crate::shared::action_log::{ActionType, create_undo_button};
 use crate::shared::cache::CharacterCacheItem;
 use crate::shared::enums::PokemonTypeWithoutShadow;
 use crate::shared::errors::CommandInvocationError;
+use poise::CreateReply;
 
 /// Reward players with a Terastallization charge.
 #[allow(clippy::too_many_arguments)]
@@ -31,7 +32,6 @@ pub async fn reward_terastallization(
     #[autocomplete = "autocomplete_character_name"] character8: Option<String>,
     #[autocomplete = "autocomplete_character_name"] character9: Option<String>,
 ) -> Result<(), Error> {
-    // TODO: Button to undo the transaction which lasts for a minute or so.
     let args = parse_variadic_args(
         character1, character2, character3, character4, character5, character6, character7,
         character8, character9,
@@ -39,11 +39,15 @@ pub async fn reward_terastallization(
 
     match handle_unlock(&ctx, tera_type, &args).await {
         Ok(characters) => {
-            ctx.say(format!(
-                "Unlocked a {} Terastallization Charge for {}!",
-                tera_type,
-                build_character_list(&characters)
-            ))
+            ctx.send(
+                CreateReply::default()
+                    .content(format!(
+                        "Unlocked a {} Terastallization Charge for {}!",
+                        tera_type,
+                        build_character_list(&characters)
+                    ))
+                    .components(vec![create_undo_button(ctx.id())]),
+            )
             .await?;
             Ok(())
         }
@@ -0,0 +1,96 @@
+use crate::commands::autocompletion::autocomplete_character_name;
+use crate::commands::{
+    Error, ensure_user_exists, ensure_user_owns_character_or_is_gm, find_character,
+};
+use crate::shared::PoiseContext;
+use crate::shared::errors::ValidationError;
+use poise::CreateReply;
+use serenity::all::{ButtonStyle, CreateActionRow, CreateButton, Mentionable, User, UserId};
+
+/// Hand a character over to another player. They'll have to accept the transfer.
+#[poise::command(slash_command, guild_only)]
+pub async fn transfer_character(
+    ctx: PoiseContext<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+    #[description = "Who should receive the character?"] new_owner: User,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only");
+    let character = find_character(ctx.data(), guild_id.get(), &character).await?;
+    ensure_user_owns_character_or_is_gm(
+        ctx.author(),
+        ctx.author_member()
+            .await
+            .expect("author_member should be set within guild context."),
+        &character,
+    )?;
+
+    if new_owner.bot {
+        return Err(Box::new(ValidationError::new("Bots can't own characters!")));
+    }
+    if new_owner.id.get() == character.user_id {
+        return Err(Box::new(ValidationError::new(format!(
+            "{} already belongs to {}.",
+            character.name,
+            new_owner.mention()
+        ))));
+    }
+    // Characters of players who left the server get retired, so handing them to someone outside it would be pointless.
+    if guild_id.member(ctx, new_owner.id).await.is_err() {
+        return Err(Box::new(ValidationError::new(
+            "Characters can only be transferred to members of this server.",
+        )));
+    }
+
+    let new_owner_id = new_owner.id.get() as i64;
+    let guild_id = guild_id.get() as i64;
+    let name_collision = sqlx::query!(
+        "SELECT id FROM character WHERE user_id = ? AND guild_id = ? AND name = ?",
+        new_owner_id,
+        guild_id,
+        character.name
+    )
+    .fetch_optional(&ctx.data().database)
+    .await?;
+    if name_collision.is_some() {
+        return Err(Box::new(ValidationError::new(format!(
+            "{} already owns a character named {}.",
+            new_owner.mention(),
+            character.name
+        ))));
+    }
+
+    ensure_user_exists(&ctx, new_owner_id, guild_id).await;
+
+    let button_id = format!(
+        "character-transfer_{}_{}_{}",
+        character.id, character.user_id, new_owner_id
+    );
+    let retirement_note = if character.is_retired {
+        "\n-# This character is currently retired and will stay that way until someone un-retires them."
+    } else {
+        ""
+    };
+    ctx.send(
+        CreateReply::default()
+            .content(format!(
+                "{}, {} would like to hand **{}** over to you. Do you accept?{}",
+                new_owner.mention(),
+                UserId::new(character.user_id).mention(),
+                character.name,
+                retirement_note
+            ))
+            .components(vec![CreateActionRow::Buttons(vec![
+                CreateButton::new(format!("{}_accept", button_id))
+                    .label("Accept")
+                    .style(ButtonStyle::Success),
+                CreateButton::new(format!("{}_decline", button_id))
+                    .label("Decline")
+                    .style(ButtonStyle::Danger),
+            ])]),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -8,12 +8,12 @@ use crate::commands::{Error, find_character};
 use crate::shared::action_log::{ActionType, LogActionArguments};
 use crate::shared::character::update_character_post_with_poise_context;
 use crate::shared::errors::ValidationError;
+use crate::shared::guild_settings::get_guild_economy_settings;
 use crate::shared::utility::button_building;
 use crate::shared::{PoiseContext, action_log, emoji};
 
 const CONFIRM: &str = "unlock_hidden_ability_proceed";
 const ABORT: &str = "unlock_hidden_ability_abort";
-const PRICE: i64 = 2000;
 
 /// Unlock your hidden ability!
 #[allow(clippy::too_many_arguments)]
@@ -39,11 +39,14 @@ pub async fn unlock_hidden_ability(
         )));
     }
 
-    if character_record.money < PRICE {
+    let price = get_guild_economy_settings(&ctx.data().database, guild_id as i64)
+        .await?
+        .hidden_ability_unlock_price;
+    if character_record.money < price {
         return Err(Box::new(ValidationError::new(format!(
             "**Unable to unlock {}'s hidden ability.**\n*That would require {} {}. Right now, {} only owns {} {}.*",
             character.name,
-            PRICE,
+            price,
             emoji::POKE_COIN,
             character.name,
             character_record.money,
@@ -54,7 +57,7 @@ pub async fn unlock_hidden_ability(
     let original_message = format!(
         "**Unlocking {}'s hidden ability will require {} {}.**",
         character.name,
-        PRICE,
+        price,
         emoji::POKE_COIN,
     );
 
@@ -88,7 +91,7 @@ pub async fn unlock_hidden_ability(
 
     if let Some(interaction) = interaction {
         if interaction.data.custom_id == CONFIRM {
-            let updated_money = character_record.money - PRICE;
+            let updated_money = character_record.money - price;
 
             let query_result = sqlx::query!(
                         "UPDATE character SET money = ?, is_hidden_ability_unlocked = true WHERE id = ? AND money = ?",
@@ -105,7 +108,7 @@ pub async fn unlock_hidden_ability(
                     LogActionArguments::triggered_by_user(&ctx),
                     format!(
                         "Removed {} {} from {}",
-                        PRICE,
+                        price,
                         emoji::POKE_COIN,
                         character.name,
                     )
@@ -7,13 +7,13 @@ use crate::commands::autocompletion::autocomplete_owned_character_name;
 use crate::commands::{Error, find_character, send_error};
 use crate::shared::action_log::{ActionType, LogActionArguments};
 use crate::shared::character::update_character_post_with_poise_context;
+use crate::shared::errors::ValidationError;
+use crate::shared::guild_settings::get_guild_economy_settings;
 use crate::shared::utility::button_building;
-use crate::shared::{PoiseContext, action_log, constants, emoji};
+use crate::shared::{PoiseContext, action_log, emoji};
 
 const CONFIRM: &str = "upgrade_backpack_proceed";
 const ABORT: &str = "upgrade_backpack_abort";
-const BASE_PRICE: i64 = 500;
-const MONEY_PER_LEVEL: i64 = 500;
 
 /// See what it takes to upgrade your backpack!
 #[allow(clippy::too_many_arguments)]
@@ -33,9 +33,17 @@ pub async fn upgrade_backpack(
     .fetch_one(&ctx.data().database)
     .await?;
 
-    let required_money = BASE_PRICE + MONEY_PER_LEVEL * character_record.backpack_upgrade_count;
-    let target_slots =
-        constants::DEFAULT_BACKPACK_SLOTS + character_record.backpack_upgrade_count + 1;
+    let settings = get_guild_economy_settings(&ctx.data().database, guild_id as i64).await?;
+    let required_money = settings.backpack_upgrade_price(character_record.backpack_upgrade_count);
+    let target_slots = settings.backpack_slots(character_record.backpack_upgrade_count + 1);
+    if let Some(maximum_backpack_slots) = settings.maximum_backpack_slots
+        && target_slots > maximum_backpack_slots
+    {
+        return Err(Box::new(ValidationError::new(format!(
+            "{}'s backpack already has the maximum of {} slots on this server!",
+            character.name, maximum_backpack_slots
+        ))));
+    }
     if character_record.money < required_money {
         return send_error(
             &ctx,
@@ -0,0 +1,69 @@
+use crate::Error;
+use crate::commands::autocompletion::autocomplete_character_name;
+use crate::commands::find_character;
+use crate::shared::PoiseContext;
+use crate::shared::character_skills::get_character_skills;
+use crate::shared::character_stats::SingleCharacterStatType;
+use crate::shared::dice_rolls::{DEFAULT_CRIT_DIE_COUNT_OPTION, ParsedRollQuery};
+use crate::shared::enums::Skill;
+
+/// Roll a skill check for a character, using their attribute and skill rank.
+#[poise::command(slash_command, guild_only)]
+pub async fn check(
+    ctx: PoiseContext<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+    #[description = "Which attribute?"] attribute: SingleCharacterStatType,
+    #[description = "Which skill?"] skill: Skill,
+    #[description = "How many successes are needed? Defaults to 1."]
+    #[min = 1_u8]
+    #[max = 10_u8]
+    required_successes: Option<u8>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    let record = sqlx::query!(
+        "SELECT stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight, \
+        stat_tough, stat_cool, stat_beauty, stat_cute, stat_clever \
+        FROM character WHERE id = ?",
+        character.id
+    )
+    .fetch_one(&ctx.data().database)
+    .await?;
+
+    let attribute_value = match attribute {
+        SingleCharacterStatType::Strength => record.stat_strength,
+        SingleCharacterStatType::Dexterity => record.stat_dexterity,
+        SingleCharacterStatType::Vitality => record.stat_vitality,
+        SingleCharacterStatType::Special => record.stat_special,
+        SingleCharacterStatType::Insight => record.stat_insight,
+        SingleCharacterStatType::Tough => record.stat_tough,
+        SingleCharacterStatType::Cool => record.stat_cool,
+        SingleCharacterStatType::Beauty => record.stat_beauty,
+        SingleCharacterStatType::Cute => record.stat_cute,
+        SingleCharacterStatType::Clever => record.stat_clever,
+    };
+    let skill_rank = get_character_skills(&ctx.data().database, character.id)
+        .await?
+        .get(skill);
+
+    let dice = (attribute_value + skill_rank).clamp(0, u8::MAX as i64) as u8;
+    let required_successes = required_successes.unwrap_or(1);
+    let result =
+        ParsedRollQuery::new(Some(dice), None, None, DEFAULT_CRIT_DIE_COUNT_OPTION).execute();
+
+    let verdict = if result.success_count >= required_successes {
+        "**Passed!**"
+    } else {
+        "**Failed...**"
+    };
+
+    ctx.say(format!(
+        "### {} rolls {:?} + {}\n{} ({} needed)\n{}",
+        character.name, attribute, skill, result.message, required_successes, verdict
+    ))
+    .await?;
+
+    Ok(())
+}
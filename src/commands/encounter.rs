@@ -4,11 +4,12 @@ use rand::{Rng, rng};
 use crate::commands::autocompletion::autocomplete_pokemon;
 use crate::commands::{Error, pokemon_from_autocomplete_string};
 use crate::shared::PoiseContext;
-use crate::shared::enums::{CombatOrSocialStat, Gender, MysteryDungeonRank, SocialStat, Stat};
+use crate::shared::enums::{Gender, MysteryDungeonRank, SocialStat, Stat};
 use crate::shared::game_data::GameData;
-use crate::shared::game_data::r#move::Move;
 use crate::shared::game_data::pokemon::Pokemon;
 use crate::shared::game_data::pokemon_types::PokemonTypes;
+use crate::shared::guild_settings::{ProgressionRules, get_progression_rules};
+use crate::shared::move_dice_pools::DicePoolStats;
 use crate::shared::utility::{level_calculations, message_splitting};
 
 /// Encounter some wild pokemon!
@@ -29,7 +30,13 @@ pub async fn encounter(
 ) -> Result<(), Error> {
     let pokemon = pokemon_from_autocomplete_string(&ctx, &pokemon).await?;
     let game_data = ctx.data().game.get_by_context(&ctx).await;
-    for encounter in build_encounter(pokemon, level, amount) {
+    let rules = match ctx.guild_id() {
+        Some(guild_id) => {
+            get_progression_rules(&ctx.data().database, guild_id.get() as i64).await?
+        }
+        None => ProgressionRules::default(),
+    };
+    for encounter in build_encounter(&rules, pokemon, level, amount) {
         for part in
             message_splitting::split_long_messages(encounter.build_string(pokemon, &game_data))
         {
@@ -40,10 +47,15 @@ pub async fn encounter(
     Ok(())
 }
 
-fn build_encounter(pokemon: &Pokemon, level: u8, amount: Option<u8>) -> Vec<EncounterMon> {
+fn build_encounter(
+    rules: &ProgressionRules,
+    pokemon: &Pokemon,
+    level: u8,
+    amount: Option<u8>,
+) -> Vec<EncounterMon> {
     let mut result = Vec::new();
     for _ in 0..amount.unwrap_or(1) {
-        result.push(EncounterMon::from_pokemon(pokemon, level));
+        result.push(EncounterMon::from_pokemon(rules, pokemon, level));
     }
 
     result
@@ -73,13 +85,13 @@ struct EncounterMon {
 }
 
 impl EncounterMon {
-    pub fn from_pokemon(pokemon: &Pokemon, level: u8) -> Self {
+    pub fn from_pokemon(rules: &ProgressionRules, pokemon: &Pokemon, level: u8) -> Self {
         let mut result = EncounterMon {
             name: pokemon.name.clone(),
             gender: EncounterMon::get_random_gender(pokemon),
             types: pokemon.types.clone(),
             level,
-            rank: MysteryDungeonRank::from_level(level),
+            rank: MysteryDungeonRank::from_level(i64::from(level)),
             ability: EncounterMon::get_random_ability(pokemon),
             hp: 0,
             will: 0,
@@ -106,21 +118,24 @@ impl EncounterMon {
         ];
         let mut non_maxed_stat_points = all_stats.clone();
         let mut remaining_stat_points =
-            level_calculations::calculate_available_combat_points(level as i64);
+            level_calculations::calculate_available_combat_points(rules, level as i64);
         let mut limit_break_count = 0;
         while remaining_stat_points > 0 {
             if let Some(mut stat) = non_maxed_stat_points.iter().choose(&mut rng) {
                 result.increase_stat(stat);
 
-                if result.get_stat(stat) == pokemon.get_stat(stat).max {
+                if result.dice_pool_stats().get_stat(stat) == pokemon.get_stat(stat).max as i64 {
                     let el_drop_o = *stat;
                     stat = &el_drop_o;
                     non_maxed_stat_points.retain(|x| x != stat);
                 }
                 remaining_stat_points -= 1;
-            } else if remaining_stat_points > 2 + limit_break_count {
+            } else if remaining_stat_points
+                > level_calculations::calculate_next_limit_break_cost(rules, limit_break_count)
+            {
                 result.increase_stat(all_stats.iter().choose(&mut rng).unwrap());
-                remaining_stat_points -= 2 + limit_break_count;
+                remaining_stat_points -=
+                    level_calculations::calculate_next_limit_break_cost(rules, limit_break_count);
                 limit_break_count += 1;
             } else {
                 break;
@@ -134,7 +149,7 @@ impl EncounterMon {
             SocialStat::Clever,
             SocialStat::Cute,
         ];
-        let mut remaining_social_points = result.rank.social_stat_points();
+        let mut remaining_social_points = rules.social_stat_points(&result.rank);
         while remaining_social_points > 0 {
             if let Some(mut stat) = non_maxed_social_stats.iter().choose(&mut rng) {
                 result.increase_social_stat(stat);
@@ -213,24 +228,20 @@ impl EncounterMon {
         };
     }
 
-    fn get_stat(&self, stat: &Stat) -> u8 {
-        match stat {
-            Stat::Strength => self.strength,
-            Stat::Dexterity => self.dexterity,
-            Stat::Vitality => self.vitality,
-            Stat::Special => self.special,
-            Stat::Insight => self.insight,
-            Stat::Copy => 0,
-            Stat::StrengthOrSpecial => {
-                if self.strength > self.special {
-                    self.strength
-                } else {
-                    self.special
-                }
-            }
-            Stat::Rank => self.rank.die_count(),
-            Stat::StrengthPlusRank => self.strength + self.rank.die_count(),
-            Stat::StrengthMinusRank => self.strength - self.rank.die_count(),
+    fn dice_pool_stats(&self) -> DicePoolStats {
+        DicePoolStats {
+            rank: self.rank,
+            hp: self.hp as i64,
+            strength: self.strength as i64,
+            dexterity: self.dexterity as i64,
+            vitality: self.vitality as i64,
+            special: self.special as i64,
+            insight: self.insight as i64,
+            tough: self.tough as i64,
+            cool: self.cool as i64,
+            beauty: self.beauty as i64,
+            clever: self.clever as i64,
+            cute: self.cute as i64,
         }
     }
 
@@ -316,8 +327,9 @@ INS: {:>2} / {:>2}      Cute:   {} / 5
                 if m.damage1.unwrap_or(Stat::Strength) == Stat::Copy {
                     result.push_str("ACC: **Copy** | DMG: **Copy** \n");
                 } else {
-                    let accuracy = self.calculate_accuracy(m);
-                    let damage = self.calculate_damage(m);
+                    let dice_pool_stats = self.dice_pool_stats();
+                    let accuracy = dice_pool_stats.calculate_accuracy(m);
+                    let damage = dice_pool_stats.calculate_damage(m);
                     if damage > 0 {
                         if m.typing.has_stab(&Some(self.types.type1))
                             || m.typing.has_stab(&self.types.type2)
@@ -353,68 +365,4 @@ INS: {:>2} / {:>2}      Cute:   {} / 5
 
         result
     }
-
-    fn calculate_accuracy(&self, m: &Move) -> u8 {
-        let mut result = 0;
-        if let Some(acc) = m.accuracy1 {
-            result += self.get_die_count_for_stat(acc);
-        }
-
-        if m.accuracy2.is_some() {
-            result += self.rank.die_count();
-        }
-
-        result
-    }
-
-    fn calculate_damage(&self, m: &Move) -> u8 {
-        let mut result = m.power;
-        if let Some(stat) = m.damage1 {
-            result += self.get_stat(&stat);
-        }
-
-        if m.happiness_damage.is_some() {
-            result += self.rank.die_count();
-        }
-
-        result
-    }
-
-    fn get_die_count_for_stat(&self, acc: CombatOrSocialStat) -> u8 {
-        match acc {
-            CombatOrSocialStat::Strength => self.strength,
-            CombatOrSocialStat::Dexterity => self.dexterity,
-            CombatOrSocialStat::Vitality => self.vitality,
-            CombatOrSocialStat::Special => self.special,
-            CombatOrSocialStat::Insight => self.insight,
-            CombatOrSocialStat::VitalityOrInsight => self.insight.max(self.vitality),
-            CombatOrSocialStat::HP => self.hp,
-            CombatOrSocialStat::Tough => self.tough,
-            CombatOrSocialStat::Cool => self.cool,
-            CombatOrSocialStat::Beauty => self.beauty,
-            CombatOrSocialStat::Clever => self.clever,
-            CombatOrSocialStat::Cute => self.cute,
-            CombatOrSocialStat::Brawl => self.rank.die_count(),
-            CombatOrSocialStat::Channel => self.rank.die_count(),
-            CombatOrSocialStat::Clash => self.rank.die_count(),
-            CombatOrSocialStat::Evasion => self.rank.die_count(),
-            CombatOrSocialStat::Alert => self.rank.die_count(),
-            CombatOrSocialStat::Athletic => self.rank.die_count(),
-            CombatOrSocialStat::Nature => self.rank.die_count(),
-            CombatOrSocialStat::Stealth => self.rank.die_count(),
-            CombatOrSocialStat::Allure => self.rank.die_count(),
-            CombatOrSocialStat::Etiquette => self.rank.die_count(),
-            CombatOrSocialStat::Intimidate => self.rank.die_count(),
-            CombatOrSocialStat::Perform => self.rank.die_count(),
-            CombatOrSocialStat::Will => self.rank.die_count(),
-            CombatOrSocialStat::Copied => 0,
-            CombatOrSocialStat::ToughOrCute => self.tough.max(self.cute),
-            CombatOrSocialStat::MissingBeauty => 5 - self.beauty,
-            CombatOrSocialStat::BrawlOrChannel => self.rank.die_count(),
-            CombatOrSocialStat::Varies => self.rank.die_count(),
-            CombatOrSocialStat::Medicine => self.rank.die_count(),
-            CombatOrSocialStat::Empathy => self.rank.die_count(),
-            CombatOrSocialStat::Rank => self.rank.die_count(),
-        }
-    }
 }
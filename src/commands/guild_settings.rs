@@ -0,0 +1,253 @@
+use crate::commands::{Error, ensure_guild_exists, send_ephemeral_reply};
+use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
+use crate::shared::character::update_character_post_with_poise_context;
+use crate::shared::enums::MysteryDungeonRank;
+use crate::shared::errors::ValidationError;
+use crate::shared::guild_settings::{
+    GuildEconomySettings, ProgressionRules, get_guild_economy_settings, get_progression_rules,
+};
+use crate::shared::{PoiseContext, emoji};
+
+/// Change prices, limits and progression rules. Leave everything empty to see the current values.
+#[allow(clippy::too_many_arguments)]
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn guild_settings(
+    ctx: PoiseContext<'_>,
+    #[description = "Price for the first backpack upgrade."]
+    #[min = 0_i64]
+    backpack_upgrade_base_price: Option<i64>,
+    #[description = "How much more expensive every further backpack upgrade gets."]
+    #[min = 0_i64]
+    backpack_upgrade_price_increase: Option<i64>,
+    #[description = "Price for unlocking a hidden ability."]
+    #[min = 0_i64]
+    hidden_ability_unlock_price: Option<i64>,
+    #[description = "How many backpack slots characters have without any upgrades."]
+    #[min = 0_i64]
+    default_backpack_slots: Option<i64>,
+    #[description = "Upgrades can't go past this many slots. Set to 0 to remove the limit."]
+    #[min = 0_i64]
+    maximum_backpack_slots: Option<i64>,
+    #[description = "How much experience is needed per level."]
+    #[min = 1_i64]
+    experience_per_level: Option<i64>,
+    #[description = "Combat stat points every character gets regardless of their level."]
+    #[min = 0_i64]
+    base_combat_points: Option<i64>,
+    #[description = "Combat stat points gained per level."]
+    #[min = 0_i64]
+    combat_points_per_level: Option<i64>,
+    #[description = "Stat points required for the first limit break."]
+    #[min = 1_i64]
+    limit_break_base_cost: Option<i64>,
+    #[description = "How much more expensive every further limit break gets."]
+    #[min = 0_i64]
+    limit_break_cost_increase: Option<i64>,
+    #[description = "Level at which pokemon usually reach their first evolution stage."]
+    #[min = 1_i64]
+    first_evolution_level: Option<i64>,
+    #[description = "Level at which pokemon usually reach their second evolution stage."]
+    #[min = 1_i64]
+    second_evolution_level: Option<i64>,
+    #[description = "Social stat points at Bronze Rank."]
+    #[min = 0_i64]
+    social_points_bronze: Option<i64>,
+    #[description = "Social stat points at Silver Rank."]
+    #[min = 0_i64]
+    social_points_silver: Option<i64>,
+    #[description = "Social stat points at Gold Rank."]
+    #[min = 0_i64]
+    social_points_gold: Option<i64>,
+    #[description = "Social stat points at Platinum Rank."]
+    #[min = 0_i64]
+    social_points_platinum: Option<i64>,
+    #[description = "Social stat points at Diamond Rank."]
+    #[min = 0_i64]
+    social_points_diamond: Option<i64>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    ensure_guild_exists(&ctx, guild_id).await;
+
+    let current = get_guild_economy_settings(&ctx.data().database, guild_id).await?;
+    let settings = GuildEconomySettings {
+        backpack_upgrade_base_price: backpack_upgrade_base_price
+            .unwrap_or(current.backpack_upgrade_base_price),
+        backpack_upgrade_price_increase: backpack_upgrade_price_increase
+            .unwrap_or(current.backpack_upgrade_price_increase),
+        hidden_ability_unlock_price: hidden_ability_unlock_price
+            .unwrap_or(current.hidden_ability_unlock_price),
+        default_backpack_slots: default_backpack_slots.unwrap_or(current.default_backpack_slots),
+        maximum_backpack_slots: match maximum_backpack_slots {
+            Some(0) => None,
+            Some(x) => Some(x),
+            None => current.maximum_backpack_slots,
+        },
+    };
+
+    let current = get_progression_rules(&ctx.data().database, guild_id).await?;
+    let rules = ProgressionRules {
+        experience_per_level: experience_per_level.unwrap_or(current.experience_per_level),
+        base_combat_points: base_combat_points.unwrap_or(current.base_combat_points),
+        combat_points_per_level: combat_points_per_level.unwrap_or(current.combat_points_per_level),
+        limit_break_base_cost: limit_break_base_cost.unwrap_or(current.limit_break_base_cost),
+        limit_break_cost_increase: limit_break_cost_increase
+            .unwrap_or(current.limit_break_cost_increase),
+        first_evolution_level: first_evolution_level.unwrap_or(current.first_evolution_level),
+        second_evolution_level: second_evolution_level.unwrap_or(current.second_evolution_level),
+        social_points_bronze: social_points_bronze.unwrap_or(current.social_points_bronze),
+        social_points_silver: social_points_silver.unwrap_or(current.social_points_silver),
+        social_points_gold: social_points_gold.unwrap_or(current.social_points_gold),
+        social_points_platinum: social_points_platinum.unwrap_or(current.social_points_platinum),
+        social_points_diamond: social_points_diamond.unwrap_or(current.social_points_diamond),
+    };
+    if rules.second_evolution_level < rules.first_evolution_level {
+        return Err(Box::new(ValidationError::new(
+            "The second evolution stage can't be reached before the first one.",
+        )));
+    }
+
+    let has_economy_changes = backpack_upgrade_base_price.is_some()
+        || backpack_upgrade_price_increase.is_some()
+        || hidden_ability_unlock_price.is_some()
+        || default_backpack_slots.is_some()
+        || maximum_backpack_slots.is_some();
+    let has_progression_changes = experience_per_level.is_some()
+        || base_combat_points.is_some()
+        || combat_points_per_level.is_some()
+        || limit_break_base_cost.is_some()
+        || limit_break_cost_increase.is_some()
+        || first_evolution_level.is_some()
+        || second_evolution_level.is_some()
+        || social_points_bronze.is_some()
+        || social_points_silver.is_some()
+        || social_points_gold.is_some()
+        || social_points_platinum.is_some()
+        || social_points_diamond.is_some();
+    if has_economy_changes {
+        sqlx::query!(
+            "UPDATE guild SET backpack_upgrade_base_price = ?, backpack_upgrade_price_increase = ?, hidden_ability_unlock_price = ?, \
+            default_backpack_slots = ?, maximum_backpack_slots = ? WHERE id = ?",
+            settings.backpack_upgrade_base_price,
+            settings.backpack_upgrade_price_increase,
+            settings.hidden_ability_unlock_price,
+            settings.default_backpack_slots,
+            settings.maximum_backpack_slots,
+            guild_id
+        )
+        .execute(&ctx.data().database)
+        .await?;
+    }
+    if has_progression_changes {
+        sqlx::query!(
+            "UPDATE guild SET experience_per_level = ?, base_combat_points = ?, combat_points_per_level = ?, \
+            limit_break_base_cost = ?, limit_break_cost_increase = ?, first_evolution_level = ?, second_evolution_level = ?, \
+            social_points_bronze = ?, social_points_silver = ?, social_points_gold = ?, social_points_platinum = ?, \
+            social_points_diamond = ? WHERE id = ?",
+            rules.experience_per_level,
+            rules.base_combat_points,
+            rules.combat_points_per_level,
+            rules.limit_break_base_cost,
+            rules.limit_break_cost_increase,
+            rules.first_evolution_level,
+            rules.second_evolution_level,
+            rules.social_points_bronze,
+            rules.social_points_silver,
+            rules.social_points_gold,
+            rules.social_points_platinum,
+            rules.social_points_diamond,
+            guild_id
+        )
+        .execute(&ctx.data().database)
+        .await?;
+    }
+
+    let message = format!(
+        "{}\n{}",
+        build_settings_string(&settings),
+        build_progression_rules_string(&rules)
+    );
+    if has_economy_changes || has_progression_changes {
+        send_ephemeral_reply(&ctx, format!("Settings have been updated!\n{}", message)).await?;
+        log_action(
+            &ActionType::GuildSettingsUpdate,
+            LogActionArguments::triggered_by_user(&ctx),
+            format!("Guild settings have been updated:\n{}", message),
+        )
+        .await?;
+    } else {
+        send_ephemeral_reply(&ctx, message).await?;
+    }
+
+    // Levels, ranks and stat points shown in character posts depend on these rules.
+    if has_progression_changes {
+        let character_ids = sqlx::query!(
+            "SELECT id FROM character WHERE guild_id = ? AND is_retired = false",
+            guild_id
+        )
+        .fetch_all(&ctx.data().database)
+        .await?;
+        for record in character_ids {
+            update_character_post_with_poise_context(&ctx, record.id).await;
+        }
+    }
+
+    Ok(())
+}
+
+fn build_settings_string(settings: &GuildEconomySettings) -> String {
+    let maximum_backpack_slots = match settings.maximum_backpack_slots {
+        Some(x) => x.to_string(),
+        None => String::from("No limit"),
+    };
+
+    format!(
+        "{} Backpack Upgrade Price: {} {} (+{} per upgrade)\n\
+        {} Default Backpack Slots: {}\n\
+        {} Maximum Backpack Slots: {}\n\
+        💊 Hidden Ability Unlock Price: {} {}",
+        emoji::BACKPACK,
+        settings.backpack_upgrade_base_price,
+        emoji::POKE_COIN,
+        settings.backpack_upgrade_price_increase,
+        emoji::BACKPACK,
+        settings.default_backpack_slots,
+        emoji::BACKPACK,
+        maximum_backpack_slots,
+        settings.hidden_ability_unlock_price,
+        emoji::POKE_COIN
+    )
+}
+
+fn build_progression_rules_string(rules: &ProgressionRules) -> String {
+    let social_points = [
+        MysteryDungeonRank::Bronze,
+        MysteryDungeonRank::Silver,
+        MysteryDungeonRank::Gold,
+        MysteryDungeonRank::Platinum,
+        MysteryDungeonRank::Diamond,
+    ]
+    .iter()
+    .map(|rank| format!("{} {}", rank.emoji_string(), rules.social_stat_points(rank)))
+    .collect::<Vec<String>>()
+    .join(" | ");
+
+    format!(
+        "📈 Experience per Level: {}\n\
+        ⚔️ Combat Stat Points: {} (+{} per level)\n\
+        💥 Limit Break Cost: {} (+{} per limit break)\n\
+        ✨ Evolution Levels: {} / {}\n\
+        🎭 Social Stat Points: {}",
+        rules.experience_per_level,
+        rules.base_combat_points,
+        rules.combat_points_per_level,
+        rules.limit_break_base_cost,
+        rules.limit_break_cost_increase,
+        rules.first_evolution_level,
+        rules.second_evolution_level,
+        social_points
+    )
+}
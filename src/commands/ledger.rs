@@ -0,0 +1,97 @@
+use crate::Error;
+use crate::commands::autocompletion::{autocomplete_character_name, autocomplete_wallet_name};
+use crate::commands::{find_character, find_wallet, send_ephemeral_reply};
+use crate::shared::PoiseContext;
+use crate::shared::action_log::ActionType;
+use crate::shared::errors::ValidationError;
+use crate::shared::utility::message_splitting::split_long_messages;
+use poise::CreateReply;
+use std::str::FromStr;
+
+const LEDGER_ENTRY_LIMIT: i64 = 25;
+
+struct LedgerQueryResult {
+    action_type: String,
+    message: String,
+    timestamp: i64,
+    is_undone: bool,
+}
+
+/// Look up the most recent changes to a character or wallet.
+#[poise::command(slash_command, guild_only)]
+pub async fn ledger(
+    ctx: PoiseContext<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: Option<String>,
+    #[description = "Which wallet?"]
+    #[autocomplete = "autocomplete_wallet_name"]
+    wallet: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let (name, entries) = match (character, wallet) {
+        (Some(character), None) => {
+            let character = find_character(ctx.data(), guild_id, &character).await?;
+            let entries = sqlx::query_as!(
+                LedgerQueryResult,
+                "SELECT action_type, message, timestamp, is_undone FROM ledger_entry \
+                WHERE character_id = ? ORDER BY id DESC LIMIT ?",
+                character.id,
+                LEDGER_ENTRY_LIMIT
+            )
+            .fetch_all(&ctx.data().database)
+            .await?;
+            (character.name, entries)
+        }
+        (None, Some(wallet)) => {
+            let wallet = find_wallet(ctx.data(), guild_id, &wallet).await?;
+            let entries = sqlx::query_as!(
+                LedgerQueryResult,
+                "SELECT action_type, message, timestamp, is_undone FROM ledger_entry \
+                WHERE wallet_id = ? ORDER BY id DESC LIMIT ?",
+                wallet.id,
+                LEDGER_ENTRY_LIMIT
+            )
+            .fetch_all(&ctx.data().database)
+            .await?;
+            (wallet.name, entries)
+        }
+        _ => {
+            return Err(Box::new(ValidationError::new(
+                "Please select either a character or a wallet.",
+            )));
+        }
+    };
+
+    if entries.is_empty() {
+        send_ephemeral_reply(
+            &ctx,
+            format!("There are no ledger entries for **{}** yet.", name),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let mut message = format!("### 📒 Ledger for {}\n", name);
+    for entry in entries {
+        let action_type = ActionType::from_str(&entry.action_type)
+            .map(|x| x.to_string())
+            .unwrap_or(entry.action_type);
+        let line = format!(
+            "<t:{}:f> {} {}",
+            entry.timestamp, action_type, entry.message
+        );
+        if entry.is_undone {
+            message.push_str(&format!("- ~~{}~~ *(Undone)*\n", line));
+        } else {
+            message.push_str(&format!("- {}\n", line));
+        }
+    }
+
+    for part in split_long_messages(message) {
+        ctx.send(CreateReply::default().content(part).ephemeral(true))
+            .await?;
+    }
+
+    Ok(())
+}
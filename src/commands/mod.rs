@@ -40,7 +40,10 @@ mod weather;
 mod area_attack_roll;
 mod attack_roll;
 mod character_commands;
+mod check;
 mod edit_rules;
+mod guild_settings;
+mod ledger;
 mod pin_or_unpin;
 mod player_info;
 mod prune_emojis;
@@ -51,17 +54,20 @@ mod setting_time_offset;
 mod setup_guild;
 mod store_gm_experience;
 mod successive_action_roll;
+mod team;
 mod use_gm_experience;
-mod wallet_commands;
+pub(crate) mod wallet_commands;
 
 pub fn get_all_commands() -> Vec<Command<Data, Error>> {
     let mut result = vec![
         setup_guild::setup_guild(),
+        guild_settings::guild_settings(),
         roll::roll(),
         roll::r(),
         r#move::poke_move(),
         ability::ability(),
         attack_roll::attack_roll(),
+        check::check(),
         area_attack_roll::area_attack_roll(),
         item::item(),
         stats::stats(),
@@ -93,6 +99,8 @@ pub fn get_all_commands() -> Vec<Command<Data, Error>> {
         pin_or_unpin::pin_or_unpin(),
         store_gm_experience::store_gm_experience(),
         use_gm_experience::use_gm_experience(),
+        ledger::ledger(),
+        team::team(),
     ];
 
     for x in character_commands::get_all_commands() {
@@ -399,6 +407,18 @@ pub fn ensure_user_owns_character(
     }
 }
 
+pub fn ensure_user_owns_character_or_is_gm(
+    user: &User,
+    user_member: Cow<'_, Member>,
+    character: &CharacterCacheItem,
+) -> Result<(), ValidationError> {
+    if is_user_admin_or_gm(user_member) {
+        return Ok(());
+    }
+
+    ensure_user_owns_character(user, character)
+}
+
 // TODO: Technically this should be persisted in the database and configurable on a per-server basis, but... as long as only one server uses the bot, who cares...? :D
 const ADMIN_ROLE_ID: u64 = 1113123557292134480;
 const GM_ROLE_ID: u64 = 1114261188323319878;
@@ -5,6 +5,7 @@ use crate::commands::Error;
 use crate::shared::data::Data;
 use crate::shared::errors::DatabaseError;
 use crate::shared::game_data::PokemonApiId;
+use crate::shared::guild_settings::{get_progression_rules, ProgressionRules};
 use crate::shared::utility::channel_id_ext::ChannelIdExt;
 use crate::shared::utility::message_splitting::split_long_messages;
 use crate::shared::utility::{error_handling, level_calculations};
@@ -33,9 +34,11 @@ pub async fn player_info(
 
     match characters {
         Ok(characters) => {
+            let rules = get_progression_rules(&ctx.data().database, guild_id).await?;
             let reply = build_reply(
                 ctx.serenity_context(),
                 ctx.data(),
+                &rules,
                 &user_in_guild,
                 characters,
                 hosted_quest_count,
@@ -63,9 +66,11 @@ async fn query_hosted_quest_count(
     guild_id: i64,
 ) -> Option<i64> {
     match sqlx::query!(
-        "SELECT COUNT(*) as count FROM quest WHERE creator_id = ? AND guild_id = ? AND completion_timestamp IS NOT NULL",
+        "SELECT COUNT(*) as count FROM quest WHERE guild_id = ? AND completion_timestamp IS NOT NULL \
+        AND (creator_id = ? OR EXISTS(SELECT 1 FROM quest_gm WHERE quest_gm.quest_id = quest.channel_id AND quest_gm.user_id = ?))",
+        guild_id,
         user_id,
-        guild_id
+        user_id
     )
         .fetch_one(&ctx.data().database)
         .await {
@@ -91,6 +96,7 @@ async fn query_gm_experience(ctx: &PoiseContext<'_>, user_id: i64, guild_id: i64
 async fn build_reply(
     ctx: &serenity::all::Context,
     data: &Data,
+    rules: &ProgressionRules,
     user_in_guild: &Member,
     characters: Vec<QueryObject>,
     hosted_quest_count: Option<i64>,
@@ -104,9 +110,10 @@ async fn build_reply(
         total_exp += character.experience;
 
         let character_level =
-            level_calculations::calculate_level_from_experience(character.experience);
+            level_calculations::calculate_level_from_experience(rules, character.experience);
         total_levels += character_level;
-        let current_exp = level_calculations::calculate_current_experience(character.experience);
+        let current_exp =
+            level_calculations::calculate_current_experience(rules, character.experience);
 
         let channel_id = ChannelId::new(character.stat_channel_id as u64);
         let api_id = PokemonApiId(character.species_api_id as u16);
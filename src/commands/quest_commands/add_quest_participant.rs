@@ -60,7 +60,12 @@ ON CONFLICT (quest_id, character_id) DO UPDATE SET accepted = true",
     }
 
     ctx.say(result).await?;
-    quest_message_utils::update_quest_message(ctx.serenity_context(), ctx.data(), channel_id)
+    quest_message_utils::update_quest_message(
+        ctx.serenity_context(),
+        &ctx.data().database,
+        &ctx.data().game.base_data,
+        channel_id,
+    )
         .await?;
     Ok(())
 }
@@ -1,12 +1,30 @@
 use chrono::Utc;
+use poise::Modal;
 
 use crate::commands::autocompletion::autocomplete_character_name;
-use crate::commands::character_commands::build_character_list;
-use crate::commands::{Error, parse_character_names, parse_variadic_args, send_error};
-use crate::shared::PoiseContext;
+use crate::commands::character_commands::{announce_level_up, build_character_list};
+use crate::commands::{
+    Error, ensure_user_exists, parse_character_names, parse_variadic_args, send_error,
+};
+use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
 use crate::shared::character::update_character_post_with_poise_context;
+use crate::shared::guild_settings::get_progression_rules;
+use crate::shared::quest_gm::get_quest_gm_ids;
+use crate::shared::quest_poll::delete_quest_poll;
+use crate::shared::quest_reward::{distribute_quest_reward, get_quest_reward};
+use crate::shared::utility::level_calculations;
+use crate::shared::{PoiseContext, quest_board, team};
 
-/// Use this to increase the quest completion counter.
+#[derive(Debug, Modal)]
+#[name = "Quest Summary"]
+struct QuestSummaryModal {
+    #[name = "What happened during this quest?"]
+    #[paragraph]
+    #[max_length = 4000]
+    summary: String,
+}
+
+/// Complete the quest in this channel and hand out its rewards.
 #[allow(clippy::too_many_arguments)]
 #[poise::command(
     slash_command,
@@ -18,6 +36,7 @@ pub async fn complete_quest(
     #[description = "Which character?"]
     #[autocomplete = "autocomplete_character_name"]
     character1: String,
+    #[description = "Write a summary for /quest_archive?"] write_summary: Option<bool>,
     #[autocomplete = "autocomplete_character_name"] character2: Option<String>,
     #[autocomplete = "autocomplete_character_name"] character3: Option<String>,
     #[autocomplete = "autocomplete_character_name"] character4: Option<String>,
@@ -35,47 +54,199 @@ pub async fn complete_quest(
     let channel_id = ctx.channel_id().get() as i64;
     let guild_id = ctx.guild_id().expect("Command is guild_only!");
 
-    let existing_quest = sqlx::query!("SELECT * FROM quest WHERE channel_id = ?", channel_id)
-        .fetch_optional(&ctx.data().database)
-        .await?;
-
-    if existing_quest.is_none() {
+    let Some(quest) = sqlx::query!(
+        "SELECT creator_id, completion_timestamp, summary FROM quest WHERE channel_id = ?",
+        channel_id
+    )
+    .fetch_optional(&ctx.data().database)
+    .await?
+    else {
         return send_error(
             &ctx,
             "Doesn't look like there was a quest created within this channel!",
         )
         .await;
-    }
+    };
+
+    // Modals need to be the first response to an interaction, so this has to happen before anything else.
+    let summary = match ctx {
+        poise::Context::Application(app_ctx) if write_summary.unwrap_or(false) => {
+            let defaults = quest
+                .summary
+                .clone()
+                .map(|summary| QuestSummaryModal { summary });
+            poise::execute_modal(app_ctx, defaults, None)
+                .await?
+                .map(|x| x.summary)
+        }
+        _ => None,
+    };
 
     let characters = parse_character_names(&ctx, guild_id.get(), &args).await?;
+    let guild_id = guild_id.get() as i64;
+    let game_data = ctx.data().game.get_by_context(&ctx).await;
+    let rules = get_progression_rules(&ctx.data().database, guild_id).await?;
     let timestamp = Utc::now().timestamp();
+    ensure_user_exists(&ctx, quest.creator_id, guild_id).await;
+    let gm_ids = get_quest_gm_ids(&ctx.data().database, channel_id).await?;
+
+    let mut transaction = ctx.data().database.begin().await?;
     sqlx::query!(
         "UPDATE quest SET completion_timestamp = ? WHERE channel_id = ?",
         timestamp,
         channel_id
     )
-    .execute(&ctx.data().database)
+    .execute(&mut *transaction)
     .await?;
 
-    for x in &characters {
+    if let Some(summary) = &summary {
         sqlx::query!(
+            "UPDATE quest SET summary = ? WHERE channel_id = ?",
+            summary,
+            channel_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    let mut newly_completed_characters = Vec::new();
+    for x in &characters {
+        let result = sqlx::query!(
             "INSERT INTO quest_completion (quest_id, character_id) VALUES (?, ?) ON CONFLICT DO NOTHING",
             channel_id,
             x.id,
         )
-        .execute(&ctx.data().database)
+        .execute(&mut *transaction)
         .await?;
+        if result.rows_affected() > 0 {
+            newly_completed_characters.push(x.clone());
+        }
     }
 
-    ctx.say(format!(
-        "{} completed a quest!",
-        build_character_list(&characters)
-    ))
+    let mut level_ups = Vec::new();
+    let reward = get_quest_reward(&mut transaction, channel_id).await?;
+    if !reward.is_empty() {
+        for x in &newly_completed_characters {
+            let experience = sqlx::query!("SELECT experience FROM character WHERE id = ?", x.id)
+                .fetch_one(&mut *transaction)
+                .await?
+                .experience;
+            let old_level = level_calculations::calculate_level_from_experience(&rules, experience);
+            let new_level = level_calculations::calculate_level_from_experience(
+                &rules,
+                experience + reward.experience,
+            );
+            if new_level > old_level {
+                level_ups.push((x, old_level, new_level));
+            }
+        }
+
+        if let Err(e) = distribute_quest_reward(
+            &mut transaction,
+            game_data,
+            &reward,
+            &newly_completed_characters,
+        )
+        .await
+        {
+            return send_error(
+                &ctx,
+                &format!(
+                    "Unable to hand out the quest rewards, nothing has been changed.\n{}",
+                    e
+                ),
+            )
+            .await;
+        }
+
+        // GM Experience is only handed out the first time a quest gets completed, but every co-GM gets the full amount.
+        if quest.completion_timestamp.is_none() && reward.gm_experience > 0 {
+            for gm_id in &gm_ids {
+                sqlx::query!(
+                    "UPDATE user_in_guild SET gm_experience = gm_experience + ? WHERE user_id = ? AND guild_id = ?",
+                    reward.gm_experience,
+                    gm_id,
+                    guild_id
+                )
+                .execute(&mut *transaction)
+                .await?;
+            }
+        }
+    }
+    let newly_completed_character_ids: Vec<i64> =
+        newly_completed_characters.iter().map(|x| x.id).collect();
+    let ranked_up_teams = team::award_rank_points_for_quest_completion(
+        &mut transaction,
+        &newly_completed_character_ids,
+    )
     .await?;
 
+    // Votes on a completed quest's poll would be meaningless.
+    delete_quest_poll(&mut transaction, channel_id).await?;
+    transaction.commit().await?;
+
+    let mut message = format!("{} completed a quest!", build_character_list(&characters));
+    for team in ranked_up_teams {
+        let rank = team.rank();
+        message.push_str(&format!(
+            "\n{} reached {} {} rank!",
+            team.display_name(),
+            rank.emoji_string(),
+            rank.name_without_emoji()
+        ));
+    }
+
+    if summary.is_some() {
+        message.push_str("\n*The summary was added to the `/quest_archive`.*");
+    }
+
+    ctx.say(message).await?;
+    for (character, old_level, new_level) in level_ups {
+        announce_level_up(&ctx, character, &character.name, old_level, new_level).await;
+    }
+
+    if !reward.is_empty() && !newly_completed_characters.is_empty() {
+        let mut summary = format!(
+            "{} received the rewards for completing <#{}>:\n{}",
+            build_character_list(&newly_completed_characters),
+            channel_id,
+            reward.build_string()
+        );
+        if quest.completion_timestamp.is_some() && reward.gm_experience > 0 {
+            summary.push_str("*GM Experience had already been handed out earlier.*\n");
+        }
+        let gm_experience_was_handed_out =
+            quest.completion_timestamp.is_none() && reward.gm_experience > 0;
+        if !reward.items.is_empty() || gm_experience_was_handed_out {
+            summary.push_str(
+                "*Undo only reverts experience, money and battle points. Items and GM Experience need to be removed manually.*",
+            );
+        }
+        let mut log_arguments = LogActionArguments::triggered_by_user(&ctx);
+        for x in &newly_completed_characters {
+            for (field, delta) in [
+                ("experience", reward.experience),
+                ("money", reward.money),
+                ("battle_points", reward.battle_points),
+            ] {
+                if delta != 0 {
+                    log_arguments = log_arguments.with_character_change(x.id, field, delta);
+                }
+            }
+        }
+        log_action(&ActionType::Reward, log_arguments, summary).await?;
+    }
+
     for x in characters {
         update_character_post_with_poise_context(&ctx, x.id).await;
     }
 
+    quest_board::update_quest_board_or_log_error(
+        ctx.serenity_context(),
+        &ctx.data().database,
+        guild_id,
+    )
+    .await;
+
     Ok(())
 }
@@ -1,12 +1,18 @@
+use crate::commands::autocompletion::autocomplete_quest_template;
 use crate::commands::{send_error, Error};
-use crate::shared::data::Data;
-use crate::shared::enums::QuestParticipantSelectionMechanism;
+use crate::shared::enums::{
+    MysteryDungeonRank, PokemonTypeWithoutShadow, QuestParticipantSelectionMechanism,
+};
+use crate::shared::quest_creation::{apply_quest_settings, create_quest_impl};
+use crate::shared::quest_restrictions::QuestRestrictions;
+use crate::shared::quest_reward::QuestReward;
+use crate::shared::quest_template::{get_quest_template, get_quest_template_reward_items};
 use crate::shared::utility::quest_message_utils;
-use crate::shared::PoiseContext;
-use chrono::Utc;
+use crate::shared::{quest_board, PoiseContext};
 use poise::CreateReply;
 use serenity::all::{GetMessages, MessageType};
 
+#[allow(clippy::too_many_arguments)]
 #[poise::command(
     slash_command,
     guild_only,
@@ -14,9 +20,105 @@ use serenity::all::{GetMessages, MessageType};
 )]
 pub async fn create_quest(
     ctx: PoiseContext<'_>,
-    #[min = 1_i64] max_participants: i64,
-    selection_mechanism: QuestParticipantSelectionMechanism,
+    #[description = "Apply a template saved with /quest_template. Other options override its values."]
+    #[autocomplete = "autocomplete_quest_template"]
+    template: Option<String>,
+    #[description = "How many characters may join? Required unless a template is used."]
+    #[min = 1_i64]
+    max_participants: Option<i64>,
+    #[description = "How are participants selected? Required unless a template is used."]
+    selection_mechanism: Option<QuestParticipantSelectionMechanism>,
+    #[description = "Experience for every participant. Items can be added with /quest_rewards."]
+    #[min = 0_i64]
+    reward_experience: Option<i64>,
+    #[description = "Money for every participant."]
+    #[min = 0_i64]
+    reward_money: Option<i64>,
+    #[description = "Battle points for every participant."]
+    #[min = 0_i64]
+    reward_battle_points: Option<i64>,
+    #[description = "GM experience for you and your co-GMs once the quest is completed."]
+    #[min = 0_i64]
+    #[max = 100_i64]
+    reward_gm_experience: Option<i64>,
+    #[description = "Lowest level which may sign up."]
+    #[min = 1_i64]
+    minimum_level: Option<i64>,
+    #[description = "Highest level which may sign up."]
+    #[min = 1_i64]
+    maximum_level: Option<i64>,
+    #[description = "Lowest rank which may sign up."]
+    minimum_rank: Option<MysteryDungeonRank>,
+    #[description = "Highest rank which may sign up."]
+    maximum_rank: Option<MysteryDungeonRank>,
+    #[description = "Only species with this type may sign up."]
+    required_type: Option<PokemonTypeWithoutShadow>,
+    #[description = "Species with this type can't sign up."]
+    forbidden_type: Option<PokemonTypeWithoutShadow>,
 ) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    let template = match template {
+        Some(name) => match get_quest_template(&ctx.data().database, guild_id, &name).await? {
+            Some(template) => Some(template),
+            None => {
+                return send_error(&ctx, &format!("Unable to find a template named {}!", name))
+                    .await;
+            }
+        },
+        None => None,
+    };
+
+    let Some(max_participants) =
+        max_participants.or(template.as_ref().map(|x| x.maximum_participant_count))
+    else {
+        return send_error(&ctx, "Either max_participants or a template needs to be set!").await;
+    };
+    let Some(selection_mechanism) =
+        selection_mechanism.or(template.as_ref().map(|x| x.selection_mechanism()))
+    else {
+        return send_error(&ctx, "Either selection_mechanism or a template needs to be set!").await;
+    };
+
+    let template_reward = match &template {
+        Some(template) => {
+            let items = get_quest_template_reward_items(
+                &ctx.data().database,
+                template.guild_id,
+                &template.name,
+            )
+            .await?;
+            template.reward(items)
+        }
+        None => QuestReward::default(),
+    };
+    let reward = QuestReward {
+        experience: reward_experience.unwrap_or(template_reward.experience),
+        money: reward_money.unwrap_or(template_reward.money),
+        battle_points: reward_battle_points.unwrap_or(template_reward.battle_points),
+        gm_experience: reward_gm_experience.unwrap_or(template_reward.gm_experience),
+        items: template_reward.items,
+    };
+
+    let template_restrictions = template
+        .as_ref()
+        .map(|x| x.restrictions())
+        .unwrap_or_default();
+    let restrictions = QuestRestrictions {
+        minimum_level: minimum_level.or(template_restrictions.minimum_level),
+        maximum_level: maximum_level.or(template_restrictions.maximum_level),
+        minimum_rank: minimum_rank.or(template_restrictions.minimum_rank),
+        maximum_rank: maximum_rank.or(template_restrictions.maximum_rank),
+        required_type: required_type
+            .map(|x| x.as_pokemon_type())
+            .or(template_restrictions.required_type),
+        forbidden_type: forbidden_type
+            .map(|x| x.as_pokemon_type())
+            .or(template_restrictions.forbidden_type),
+    };
+    if let Err(e) = restrictions.validate() {
+        return send_error(&ctx, e).await;
+    }
+
     let reply = ctx
         .send(CreateReply::default().content("Creating Quest..."))
         .await?;
@@ -42,8 +144,8 @@ pub async fn create_quest(
         .expect("There should be at least one message in this forum?");
 
     let result = create_quest_impl(
-        ctx.data(),
-        ctx.guild_id().expect("Command is guild_only").get() as i64,
+        &ctx.data().database,
+        guild_id,
         channel_id,
         ctx.author().id.get() as i64,
         reply_message.id.get() as i64,
@@ -55,10 +157,14 @@ pub async fn create_quest(
 
     match result {
         Ok(_) => {
+            let mut connection = ctx.data().database.acquire().await?;
+            apply_quest_settings(&mut connection, channel_id, &reward, &restrictions).await?;
+
             let (text, too_many_signups) =
                 quest_message_utils::generate_quest_post_message_content(
                     ctx.serenity_context(),
-                    ctx.data(),
+                    &ctx.data().database,
+                    &ctx.data().game.base_data,
                     channel_id,
                     max_participants,
                     selection_mechanism,
@@ -76,6 +182,8 @@ pub async fn create_quest(
                 )
                 .await?;
 
+            quest_board::update_quest_board_or_log_error(ctx.serenity_context(), &ctx.data().database, guild_id).await;
+
             let _ = reply_message.pin(&ctx).await;
             let _ = oldest_message_inside_channel.pin(&ctx).await;
 
@@ -108,134 +216,3 @@ pub async fn create_quest(
         }
     }
 }
-
-async fn create_quest_impl(
-    data: &Data,
-    guild_id: i64,
-    channel_id: i64,
-    creator_id: i64,
-    bot_message_id: i64,
-    max_participants: i64,
-    selection_mechanism: QuestParticipantSelectionMechanism,
-    quest_description_message_id: i64,
-) -> Result<(), String> {
-    let timestamp = Utc::now().timestamp();
-
-    let result = sqlx::query!("INSERT INTO quest (guild_id, channel_id, creator_id, bot_message_id, creation_timestamp, maximum_participant_count, participant_selection_mechanism, quest_description_message_id) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
-        guild_id, channel_id, creator_id, bot_message_id, timestamp, max_participants, selection_mechanism, quest_description_message_id
-    )
-        .execute(&data.database).await;
-
-    match result {
-        Ok(result) => {
-            if result.rows_affected() > 0 {
-                Ok(())
-            } else {
-                Err(String::from("Unable to persist quest entry!"))
-            }
-        }
-        Err(e) => Err(format!("**Something went wrong!**\n{}", e)),
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::commands::quest_commands::create_quest::create_quest_impl;
-    use crate::shared::enums::QuestParticipantSelectionMechanism;
-    use crate::{database_mocks, Error};
-    use chrono::Utc;
-    use more_asserts::{assert_ge, assert_le};
-    use sqlx::{Pool, Sqlite};
-
-    #[sqlx::test]
-    async fn create_quest(db: Pool<Sqlite>) -> Result<(), Error> {
-        let data = database_mocks::create_mock::data(db).await;
-        let channel_id = 100;
-        let creator_id = 200;
-        let guild_id = 300;
-        let bot_message_id = 400;
-        let max_participants = 5;
-        let mechanism = QuestParticipantSelectionMechanism::FirstComeFirstServe;
-        let quest_description_message_id = 700;
-
-        database_mocks::create_mock::guild(&data.database, guild_id).await;
-        database_mocks::create_mock::user(&data.database, creator_id).await;
-        let timestamp_before = Utc::now().timestamp();
-        create_quest_impl(
-            &data,
-            guild_id,
-            channel_id,
-            creator_id,
-            bot_message_id,
-            max_participants,
-            mechanism,
-            quest_description_message_id,
-        )
-        .await?;
-        let timestamp_after = Utc::now().timestamp();
-
-        let quests = sqlx::query!(
-            "SELECT guild_id, creator_id, channel_id, creation_timestamp, completion_timestamp, maximum_participant_count, participant_selection_mechanism FROM quest"
-        )
-        .fetch_all(&data.database)
-        .await?;
-
-        let quest = quests.first().unwrap();
-        assert_eq!(creator_id, quest.creator_id);
-        assert_eq!(guild_id, quest.guild_id);
-        assert_eq!(channel_id, quest.channel_id);
-        assert_le!(timestamp_before, quest.creation_timestamp);
-        assert_ge!(timestamp_after, quest.creation_timestamp);
-        assert_eq!(None, quest.completion_timestamp);
-        assert_eq!(max_participants, quest.maximum_participant_count);
-        assert_eq!(
-            mechanism,
-            QuestParticipantSelectionMechanism::from_repr(quest.participant_selection_mechanism)
-                .unwrap()
-        );
-
-        Ok(())
-    }
-
-    #[sqlx::test]
-    async fn create_quest_called_twice(db: Pool<Sqlite>) -> Result<(), Error> {
-        let data = database_mocks::create_mock::data(db).await;
-        let channel_id = 100;
-        let creator_id = 200;
-        let guild_id = 300;
-        let bot_message_id = 400;
-        let max_participants = 5;
-        let selection_mechanism = QuestParticipantSelectionMechanism::FirstComeFirstServe;
-        let quest_description_message_id = 700;
-
-        database_mocks::create_mock::guild(&data.database, guild_id).await;
-        database_mocks::create_mock::user(&data.database, creator_id).await;
-
-        create_quest_impl(
-            &data,
-            guild_id,
-            channel_id,
-            creator_id,
-            bot_message_id,
-            max_participants,
-            selection_mechanism,
-            quest_description_message_id,
-        )
-        .await?;
-        let result = create_quest_impl(
-            &data,
-            guild_id,
-            channel_id,
-            creator_id,
-            bot_message_id,
-            max_participants,
-            selection_mechanism,
-            quest_description_message_id,
-        )
-        .await;
-
-        assert!(result.is_err());
-
-        Ok(())
-    }
-}
@@ -5,13 +5,27 @@ use poise::Command;
 mod add_quest_participant;
 mod complete_quest;
 mod create_quest;
+mod quest_archive;
+mod quest_gm;
+mod quest_poll;
+mod quest_rewards;
+mod quest_template;
+mod quests;
 mod remove_quest_participant;
+mod schedule_quest;
 
 pub fn get_all_commands() -> Vec<Command<Data, Error>> {
     vec![
         add_quest_participant::add_quest_participant(),
         complete_quest::complete_quest(),
         create_quest::create_quest(),
+        quest_archive::quest_archive(),
+        quest_gm::quest_gm(),
+        quest_poll::quest_poll(),
+        quest_rewards::quest_rewards(),
+        quest_template::quest_template(),
+        quests::quests(),
         remove_quest_participant::remove_quest_participant(),
+        schedule_quest::schedule_quest(),
     ]
 }
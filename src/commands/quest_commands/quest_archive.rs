@@ -0,0 +1,76 @@
+use crate::commands::autocompletion::autocomplete_character_name;
+use crate::commands::{Error, find_character, send_ephemeral_reply};
+use crate::shared::PoiseContext;
+use crate::shared::errors::ValidationError;
+use crate::shared::quest_archive::{QuestArchiveFilter, search_quest_archive};
+use crate::shared::utility::message_splitting::split_long_messages;
+use chrono::NaiveDate;
+use serenity::all::User;
+
+const MAX_RESULTS: i64 = 10;
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+fn parse_date(name: &str, value: &str) -> Result<i64, Error> {
+    match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        Ok(date) => Ok(date
+            .and_hms_opt(0, 0, 0)
+            .expect("Midnight should always exist.")
+            .and_utc()
+            .timestamp()),
+        Err(_) => Err(Box::new(ValidationError::new(format!(
+            "Unable to parse {name}, please use YYYY-MM-DD."
+        )))),
+    }
+}
+
+/// Search through completed quests and their summaries.
+#[poise::command(slash_command, guild_only)]
+pub async fn quest_archive(
+    ctx: PoiseContext<'_>,
+    #[description = "Only quests this character took part in."]
+    #[autocomplete = "autocomplete_character_name"]
+    character: Option<String>,
+    #[description = "Only quests hosted or co-GMed by this user."] creator: Option<User>,
+    #[description = "Completed on or after this date, as YYYY-MM-DD."] after: Option<String>,
+    #[description = "Completed on or before this date, as YYYY-MM-DD."] before: Option<String>,
+    #[description = "Only quests whose summary contains this text."] text: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character_id = match character {
+        Some(name) => Some(find_character(ctx.data(), guild_id, &name).await?.id),
+        None => None,
+    };
+    let completed_after = match after {
+        Some(date) => Some(parse_date("after", &date)?),
+        None => None,
+    };
+    let completed_before = match before {
+        Some(date) => Some(parse_date("before", &date)? + SECONDS_PER_DAY - 1),
+        None => None,
+    };
+
+    let filter = QuestArchiveFilter {
+        character_id,
+        gm_id: creator.map(|x| x.id.get() as i64),
+        completed_after,
+        completed_before,
+        text,
+    };
+    let entries =
+        search_quest_archive(&ctx.data().database, guild_id as i64, &filter, MAX_RESULTS).await?;
+    if entries.is_empty() {
+        send_ephemeral_reply(&ctx, "Couldn't find any completed quests matching that!").await?;
+        return Ok(());
+    }
+
+    let mut text =
+        format!("## 📚 Quest Archive\n*Showing up to {MAX_RESULTS} of the most recent matches.*\n");
+    for entry in entries {
+        text.push_str(&entry.build_string());
+    }
+
+    for part in split_long_messages(text) {
+        send_ephemeral_reply(&ctx, part).await?;
+    }
+    Ok(())
+}
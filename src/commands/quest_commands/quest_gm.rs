@@ -0,0 +1,95 @@
+use crate::commands::{Error, ensure_user_exists, send_error};
+use crate::shared::PoiseContext;
+use crate::shared::quest_gm::{build_gm_mention_list, get_quest_gm_ids};
+use serenity::all::User;
+
+/// Manage the co-GMs of the quest in this channel.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR",
+    subcommands("add", "remove"),
+    subcommand_required
+)]
+pub async fn quest_gm(_: PoiseContext<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Add a co-GM who may do everything the quest owner can.
+#[poise::command(slash_command)]
+pub async fn add(
+    ctx: PoiseContext<'_>,
+    #[description = "Who should help running this quest?"] user: User,
+) -> Result<(), Error> {
+    let channel_id = ctx.channel_id().get() as i64;
+    let guild_id = ctx.guild_id().expect("Command is guild_only!").get() as i64;
+    let Some(quest) = sqlx::query!(
+        "SELECT creator_id FROM quest WHERE channel_id = ?",
+        channel_id
+    )
+    .fetch_optional(&ctx.data().database)
+    .await?
+    else {
+        return send_error(
+            &ctx,
+            "Doesn't look like there was a quest created within this channel!",
+        )
+        .await;
+    };
+
+    let user_id = user.id.get() as i64;
+    if quest.creator_id == user_id {
+        return send_error(&ctx, "That user already owns this quest!").await;
+    }
+
+    ensure_user_exists(&ctx, user_id, guild_id).await;
+    let result = sqlx::query!(
+        "INSERT INTO quest_gm (quest_id, user_id) VALUES (?, ?) ON CONFLICT DO NOTHING",
+        channel_id,
+        user_id
+    )
+    .execute(&ctx.data().database)
+    .await?;
+    if result.rows_affected() == 0 {
+        return send_error(&ctx, "That user already is a co-GM for this quest!").await;
+    }
+
+    reply_with_gm_list(&ctx, channel_id, &format!("Added {} as a co-GM!", user)).await
+}
+
+/// Remove a co-GM from this quest.
+#[poise::command(slash_command)]
+pub async fn remove(
+    ctx: PoiseContext<'_>,
+    #[description = "Which co-GM?"] user: User,
+) -> Result<(), Error> {
+    let channel_id = ctx.channel_id().get() as i64;
+    let user_id = user.id.get() as i64;
+    let result = sqlx::query!(
+        "DELETE FROM quest_gm WHERE quest_id = ? AND user_id = ?",
+        channel_id,
+        user_id
+    )
+    .execute(&ctx.data().database)
+    .await?;
+    if result.rows_affected() == 0 {
+        return send_error(&ctx, "That user isn't a co-GM for a quest in this channel!").await;
+    }
+
+    reply_with_gm_list(&ctx, channel_id, &format!("Removed {} as a co-GM!", user)).await
+}
+
+async fn reply_with_gm_list(
+    ctx: &PoiseContext<'_>,
+    channel_id: i64,
+    message: &str,
+) -> Result<(), Error> {
+    let gm_ids = get_quest_gm_ids(&ctx.data().database, channel_id).await?;
+    ctx.say(format!(
+        "{}\nGMs for this quest: {}",
+        message,
+        build_gm_mention_list(&gm_ids)
+    ))
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use crate::commands::{Error, send_ephemeral_reply};
+use crate::shared::PoiseContext;
+use crate::shared::errors::ValidationError;
+use crate::shared::quest_poll::{build_quest_poll_message, replace_quest_poll};
+use crate::shared::utility::time_offset;
+use chrono::{Datelike, NaiveDateTime, Timelike, Utc};
+use poise::CreateReply;
+use serenity::all::CreateAllowedMentions;
+
+fn parse_slot(value: &str, hour_offset: i64, minute_offset: i64) -> Result<i64, Error> {
+    let Ok(local) = NaiveDateTime::parse_from_str(value.trim(), "%Y-%m-%d %H:%M") else {
+        return Err(Box::new(ValidationError::new(format!(
+            "Unable to parse `{value}`, please use YYYY-MM-DD HH:MM."
+        ))));
+    };
+
+    let Some(timestamp) = time_offset::local_time_to_unix_timestamp(
+        Some(local.minute() as u8),
+        Some(local.hour() as u8),
+        Some(local.day() as u8),
+        Some(local.month() as u8),
+        Some(local.year() as u16),
+        hour_offset,
+        minute_offset,
+    ) else {
+        return Err(Box::new(ValidationError::new(format!(
+            "`{value}` doesn't exist!"
+        ))));
+    };
+
+    Ok(timestamp)
+}
+
+/// Let the participants vote on when the quest in this channel should start.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn quest_poll(
+    ctx: PoiseContext<'_>,
+    #[description = "Your local time, as YYYY-MM-DD HH:MM"] slot1: String,
+    #[description = "Your local time, as YYYY-MM-DD HH:MM"] slot2: Option<String>,
+    #[description = "Your local time, as YYYY-MM-DD HH:MM"] slot3: Option<String>,
+    #[description = "Your local time, as YYYY-MM-DD HH:MM"] slot4: Option<String>,
+    #[description = "Your local time, as YYYY-MM-DD HH:MM"] slot5: Option<String>,
+) -> Result<(), Error> {
+    let channel_id = ctx.channel_id().get() as i64;
+    let quest = sqlx::query!(
+        "SELECT completion_timestamp FROM quest WHERE channel_id = ?",
+        channel_id
+    )
+    .fetch_optional(&ctx.data().database)
+    .await?;
+    match quest {
+        None => {
+            return Err(Box::new(ValidationError::new(
+                "Doesn't look like there was a quest created within this channel!",
+            )));
+        }
+        Some(quest) if quest.completion_timestamp.is_some() => {
+            return Err(Box::new(ValidationError::new(
+                "This quest has already been completed!",
+            )));
+        }
+        Some(_) => {}
+    }
+
+    let user_id = ctx.author().id.get() as i64;
+    let offset = time_offset::get_user_time_offset(&ctx.data().database, user_id).await;
+    let (hour_offset, minute_offset) = offset.unwrap_or_default();
+
+    let mut start_timestamps = Vec::new();
+    for slot in [Some(slot1), slot2, slot3, slot4, slot5]
+        .into_iter()
+        .flatten()
+    {
+        start_timestamps.push(parse_slot(&slot, hour_offset, minute_offset)?);
+    }
+    start_timestamps.sort();
+    start_timestamps.dedup();
+
+    replace_quest_poll(
+        &ctx.data().database,
+        channel_id,
+        &start_timestamps,
+        Utc::now().timestamp(),
+    )
+    .await?;
+    let (text, components) = build_quest_poll_message(&ctx.data().database, channel_id).await?;
+    ctx.send(
+        CreateReply::default()
+            .content(text)
+            .components(components)
+            .allowed_mentions(CreateAllowedMentions::new()),
+    )
+    .await?;
+
+    if offset.is_none() {
+        send_ephemeral_reply(&ctx, "*Looks like you don't have your timezone set up, so the times were interpreted as UTC. Use `/setting_time_offset` to change that.*").await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use crate::commands::autocompletion::autocomplete_item_or_potion;
+use crate::commands::{Error, send_ephemeral_reply};
+use crate::shared::PoiseContext;
+use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
+use crate::shared::errors::ValidationError;
+use crate::shared::inventory::InventoryItem;
+use crate::shared::quest_reward::{
+    QuestReward, add_quest_reward_item, get_quest_reward, set_quest_reward_values,
+};
+
+/// Manage the rewards which get handed out once this quest is completed.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR",
+    subcommands("show", "set", "add_item", "remove_item"),
+    subcommand_required
+)]
+pub async fn quest_rewards(_: PoiseContext<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+async fn ensure_quest_exists(ctx: &PoiseContext<'_>) -> Result<i64, Error> {
+    let channel_id = ctx.channel_id().get() as i64;
+    let quest = sqlx::query!(
+        "SELECT completion_timestamp FROM quest WHERE channel_id = ?",
+        channel_id
+    )
+    .fetch_optional(&ctx.data().database)
+    .await?;
+
+    match quest {
+        None => Err(Box::new(ValidationError::new(
+            "Doesn't look like there was a quest created within this channel!",
+        ))),
+        Some(quest) if quest.completion_timestamp.is_some() => Err(Box::new(ValidationError::new(
+            "This quest has already been completed!",
+        ))),
+        Some(_) => Ok(channel_id),
+    }
+}
+
+async fn reply_and_log(ctx: &PoiseContext<'_>, quest_id: i64) -> Result<(), Error> {
+    let mut connection = ctx.data().database.acquire().await?;
+    let reward = get_quest_reward(&mut connection, quest_id).await?;
+    let text = format!("**Quest Rewards:**\n{}", reward.build_string());
+    send_ephemeral_reply(ctx, &text).await?;
+    log_action(
+        &ActionType::Reward,
+        LogActionArguments::triggered_by_user(ctx),
+        format!(
+            "Updated the rewards for <#{}>:\n{}",
+            quest_id,
+            reward.build_string()
+        ),
+    )
+    .await
+}
+
+/// Display the rewards for this quest.
+#[poise::command(slash_command)]
+pub async fn show(ctx: PoiseContext<'_>) -> Result<(), Error> {
+    let quest_id = ensure_quest_exists(&ctx).await?;
+    let mut connection = ctx.data().database.acquire().await?;
+    let reward = get_quest_reward(&mut connection, quest_id).await?;
+    send_ephemeral_reply(
+        &ctx,
+        format!("**Quest Rewards:**\n{}", reward.build_string()),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Set the experience, money, battle points and GM experience for this quest.
+#[poise::command(slash_command)]
+pub async fn set(
+    ctx: PoiseContext<'_>,
+    #[description = "Experience for every participant."]
+    #[min = 0_i64]
+    experience: Option<i64>,
+    #[description = "Money for every participant."]
+    #[min = 0_i64]
+    money: Option<i64>,
+    #[description = "Battle points for every participant."]
+    #[min = 0_i64]
+    battle_points: Option<i64>,
+    #[description = "GM experience for the quest's host and every co-GM."]
+    #[min = 0_i64]
+    #[max = 100_i64]
+    gm_experience: Option<i64>,
+) -> Result<(), Error> {
+    let quest_id = ensure_quest_exists(&ctx).await?;
+    let mut connection = ctx.data().database.acquire().await?;
+    let current = get_quest_reward(&mut connection, quest_id).await?;
+    let reward = QuestReward {
+        experience: experience.unwrap_or(current.experience),
+        money: money.unwrap_or(current.money),
+        battle_points: battle_points.unwrap_or(current.battle_points),
+        gm_experience: gm_experience.unwrap_or(current.gm_experience),
+        items: Vec::new(),
+    };
+    set_quest_reward_values(&mut *connection, quest_id, &reward).await?;
+
+    reply_and_log(&ctx, quest_id).await
+}
+
+/// Add an item which every participant will receive.
+#[poise::command(slash_command)]
+pub async fn add_item(
+    ctx: PoiseContext<'_>,
+    #[description = "Which item?"]
+    #[autocomplete = "autocomplete_item_or_potion"]
+    item: String,
+    #[description = "How many? Defaults to 1."]
+    #[min = 1_u8]
+    amount: Option<u8>,
+) -> Result<(), Error> {
+    let quest_id = ensure_quest_exists(&ctx).await?;
+    let game_data = ctx.data().game.get_by_context(&ctx).await;
+    let Some(item) = InventoryItem::find(game_data, &item) else {
+        return Err(Box::new(ValidationError::new(format!(
+            "Unable to find an item or potion named **{}**, sorry!",
+            item
+        ))));
+    };
+
+    let amount = amount.unwrap_or(1) as i64;
+    add_quest_reward_item(&ctx.data().database, quest_id, &item.name, amount).await?;
+
+    reply_and_log(&ctx, quest_id).await
+}
+
+/// Remove an item from this quest's rewards.
+#[poise::command(slash_command)]
+pub async fn remove_item(
+    ctx: PoiseContext<'_>,
+    #[description = "Which item?"]
+    #[autocomplete = "autocomplete_item_or_potion"]
+    item: String,
+) -> Result<(), Error> {
+    let quest_id = ensure_quest_exists(&ctx).await?;
+    let result = sqlx::query!(
+        "DELETE FROM quest_reward_item WHERE quest_id = ? AND item_name = ? COLLATE NOCASE",
+        quest_id,
+        item
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{}** isn't part of this quest's rewards.",
+            item
+        ))));
+    }
+
+    reply_and_log(&ctx, quest_id).await
+}
@@ -0,0 +1,426 @@
+use crate::commands::autocompletion::{autocomplete_item_or_potion, autocomplete_quest_template};
+use crate::commands::{Error, ensure_user_exists, send_ephemeral_reply};
+use crate::shared::PoiseContext;
+use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
+use crate::shared::enums::{
+    MysteryDungeonRank, PokemonTypeWithoutShadow, QuestParticipantSelectionMechanism,
+};
+use crate::shared::errors::ValidationError;
+use crate::shared::inventory::InventoryItem;
+use crate::shared::quest_restrictions::QuestRestrictions;
+use crate::shared::quest_template::{
+    QuestTemplate, get_quest_template, get_quest_template_reward_items, get_quest_templates,
+};
+use crate::shared::utility::message_splitting::split_long_messages;
+use crate::shared::utility::time_offset;
+use chrono::Utc;
+use serenity::all::{Channel, ChannelType};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Manage reusable quest settings.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR",
+    subcommands(
+        "create_or_update",
+        "show",
+        "list",
+        "add_item",
+        "remove_item",
+        "delete"
+    ),
+    subcommand_required
+)]
+pub async fn quest_template(_: PoiseContext<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+async fn find_template(ctx: &PoiseContext<'_>, name: &str) -> Result<QuestTemplate, Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    match get_quest_template(&ctx.data().database, guild_id, name).await? {
+        Some(template) => Ok(template),
+        None => Err(Box::new(ValidationError::new(format!(
+            "Unable to find a template named {name}!"
+        )))),
+    }
+}
+
+async fn build_template_string(
+    ctx: &PoiseContext<'_>,
+    template: &QuestTemplate,
+) -> Result<String, Error> {
+    let items =
+        get_quest_template_reward_items(&ctx.data().database, template.guild_id, &template.name)
+            .await?;
+    Ok(template.build_string(&items))
+}
+
+/// Create a template or update the one with the same name. Use \n for linebreaks.
+#[allow(clippy::too_many_arguments)]
+#[poise::command(slash_command)]
+pub async fn create_or_update(
+    ctx: PoiseContext<'_>,
+    #[description = "How should we call it?"] name: String,
+    #[description = "Forum post title for recurring quests."] title: String,
+    #[description = "Forum post content for recurring quests."] description: String,
+    #[description = "How many characters may join?"]
+    #[min = 1_i64]
+    max_participants: i64,
+    #[description = "How are participants selected?"]
+    selection_mechanism: QuestParticipantSelectionMechanism,
+    #[description = "Experience for every participant."]
+    #[min = 0_i64]
+    reward_experience: Option<i64>,
+    #[description = "Money for every participant."]
+    #[min = 0_i64]
+    reward_money: Option<i64>,
+    #[description = "Battle points for every participant."]
+    #[min = 0_i64]
+    reward_battle_points: Option<i64>,
+    #[description = "GM experience for every GM once the quest is completed."]
+    #[min = 0_i64]
+    #[max = 100_i64]
+    reward_gm_experience: Option<i64>,
+    #[description = "Lowest level which may sign up."]
+    #[min = 1_i64]
+    minimum_level: Option<i64>,
+    #[description = "Highest level which may sign up."]
+    #[min = 1_i64]
+    maximum_level: Option<i64>,
+    #[description = "Lowest rank which may sign up."] minimum_rank: Option<MysteryDungeonRank>,
+    #[description = "Highest rank which may sign up."] maximum_rank: Option<MysteryDungeonRank>,
+    #[description = "Required species type."] required_type: Option<PokemonTypeWithoutShadow>,
+    #[description = "Forbidden species type."] forbidden_type: Option<PokemonTypeWithoutShadow>,
+    #[description = "Post this quest automatically in this forum. Requires repeat_every_days."]
+    recurring_forum: Option<Channel>,
+    #[description = "How many days lie between two posts?"]
+    #[min = 1_i64]
+    #[max = 365_i64]
+    repeat_every_days: Option<i64>,
+    #[description = "At which hour of your local time should it be posted? Defaults to now, or the current schedule."]
+    #[max = 23_u8]
+    post_at_hour: Option<u8>,
+) -> Result<(), Error> {
+    if name.len() > 100 || title.len() > 100 {
+        return Err(Box::new(ValidationError::new(
+            "Name and title can't be longer than 100 characters!",
+        )));
+    }
+
+    let restrictions = QuestRestrictions {
+        minimum_level,
+        maximum_level,
+        minimum_rank,
+        maximum_rank,
+        required_type: required_type.map(|x| x.as_pokemon_type()),
+        forbidden_type: forbidden_type.map(|x| x.as_pokemon_type()),
+    };
+    restrictions
+        .validate()
+        .map_err(|e| Box::new(ValidationError::new(e)))?;
+
+    let recurring_forum_channel_id = match (&recurring_forum, repeat_every_days) {
+        (Some(Channel::Guild(channel)), Some(_)) if channel.kind == ChannelType::Forum => {
+            Some(channel.id.get() as i64)
+        }
+        (Some(_), Some(_)) => {
+            return Err(Box::new(ValidationError::new(
+                "Recurring quests need to be posted in a forum channel!",
+            )));
+        }
+        (None, None) => None,
+        _ => {
+            return Err(Box::new(ValidationError::new(
+                "recurring_forum and repeat_every_days need to be set together!",
+            )));
+        }
+    };
+
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    let existing_template = get_quest_template(&ctx.data().database, guild_id, &name).await?;
+    let keep_existing_schedule = post_at_hour.is_none()
+        && existing_template.as_ref().is_some_and(|x| {
+            x.recurring_forum_channel_id == recurring_forum_channel_id
+                && x.recurrence_interval_days == repeat_every_days
+        });
+
+    let next_recurrence_timestamp = match repeat_every_days {
+        // Otherwise every edit of a recurring template would post it again right away.
+        Some(_) if keep_existing_schedule => existing_template
+            .as_ref()
+            .and_then(|x| x.next_recurrence_timestamp),
+        Some(days) if recurring_forum_channel_id.is_some() => {
+            let now = Utc::now().timestamp();
+            let mut timestamp = match post_at_hour {
+                Some(hour) => {
+                    let user_id = ctx.author().id.get() as i64;
+                    let (hour_offset, minute_offset) =
+                        time_offset::get_user_time_offset(&ctx.data().database, user_id)
+                            .await
+                            .unwrap_or_default();
+                    time_offset::local_time_to_unix_timestamp(
+                        Some(0),
+                        Some(hour),
+                        None,
+                        None,
+                        None,
+                        hour_offset,
+                        minute_offset,
+                    )
+                    .unwrap_or(now)
+                }
+                None => now,
+            };
+            while timestamp < now {
+                timestamp += days * SECONDS_PER_DAY;
+            }
+            Some(timestamp)
+        }
+        _ => None,
+    };
+
+    let creator_id = ctx.author().id.get() as i64;
+    ensure_user_exists(&ctx, creator_id, guild_id).await;
+
+    let description = description.replace("\\n", "\n");
+    if description.len() > 2000 {
+        return Err(Box::new(ValidationError::new(
+            "The description can't be longer than 2000 characters, or it couldn't be posted!",
+        )));
+    }
+    let (minimum_rank, maximum_rank, required_type, forbidden_type) =
+        restrictions.rank_and_type_columns();
+    let reward_experience = reward_experience.unwrap_or(0);
+    let reward_money = reward_money.unwrap_or(0);
+    let reward_battle_points = reward_battle_points.unwrap_or(0);
+    let reward_gm_experience = reward_gm_experience.unwrap_or(0);
+    sqlx::query!(
+        "INSERT INTO quest_template (guild_id, name, creator_id, title, description, maximum_participant_count, participant_selection_mechanism, \
+        reward_experience, reward_money, reward_battle_points, reward_gm_experience, \
+        minimum_level, maximum_level, minimum_rank, maximum_rank, required_type, forbidden_type, \
+        recurring_forum_channel_id, recurrence_interval_days, next_recurrence_timestamp) \
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) \
+        ON CONFLICT (guild_id, name) DO UPDATE SET creator_id = excluded.creator_id, title = excluded.title, description = excluded.description, \
+        maximum_participant_count = excluded.maximum_participant_count, participant_selection_mechanism = excluded.participant_selection_mechanism, \
+        reward_experience = excluded.reward_experience, reward_money = excluded.reward_money, \
+        reward_battle_points = excluded.reward_battle_points, reward_gm_experience = excluded.reward_gm_experience, \
+        minimum_level = excluded.minimum_level, maximum_level = excluded.maximum_level, \
+        minimum_rank = excluded.minimum_rank, maximum_rank = excluded.maximum_rank, \
+        required_type = excluded.required_type, forbidden_type = excluded.forbidden_type, \
+        recurring_forum_channel_id = excluded.recurring_forum_channel_id, recurrence_interval_days = excluded.recurrence_interval_days, \
+        next_recurrence_timestamp = excluded.next_recurrence_timestamp",
+        guild_id,
+        name,
+        creator_id,
+        title,
+        description,
+        max_participants,
+        selection_mechanism,
+        reward_experience,
+        reward_money,
+        reward_battle_points,
+        reward_gm_experience,
+        restrictions.minimum_level,
+        restrictions.maximum_level,
+        minimum_rank,
+        maximum_rank,
+        required_type,
+        forbidden_type,
+        recurring_forum_channel_id,
+        repeat_every_days,
+        next_recurrence_timestamp
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    let template = find_template(&ctx, &name).await?;
+    send_ephemeral_reply(&ctx, build_template_string(&ctx, &template).await?).await?;
+    log_action(
+        &ActionType::QuestTemplateUpdate,
+        LogActionArguments::triggered_by_user(&ctx),
+        format!("Created (or updated) a quest template named {name}"),
+    )
+    .await
+}
+
+/// Display the settings of a template.
+#[poise::command(slash_command)]
+pub async fn show(
+    ctx: PoiseContext<'_>,
+    #[description = "Which template?"]
+    #[autocomplete = "autocomplete_quest_template"]
+    name: String,
+) -> Result<(), Error> {
+    let template = find_template(&ctx, &name).await?;
+    for part in split_long_messages(build_template_string(&ctx, &template).await?) {
+        send_ephemeral_reply(&ctx, part).await?;
+    }
+    Ok(())
+}
+
+/// List all quest templates on this server.
+#[poise::command(slash_command)]
+pub async fn list(ctx: PoiseContext<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    let templates = get_quest_templates(&ctx.data().database, guild_id).await?;
+    if templates.is_empty() {
+        send_ephemeral_reply(
+            &ctx,
+            "There are no quest templates yet. Create one with `/quest_template create_or_update`!",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let mut text = String::from("## 📋 Quest Templates\n");
+    for template in templates {
+        let recurrence = match (
+            template.recurrence_interval_days,
+            template.next_recurrence_timestamp,
+        ) {
+            (Some(days), Some(timestamp)) => {
+                format!(" | 🔁 every {} day(s), next <t:{}:R>", days, timestamp)
+            }
+            _ => String::new(),
+        };
+        text.push_str(&format!(
+            "- **{}**: {}{}\n",
+            template.name, template.title, recurrence
+        ));
+    }
+
+    for part in split_long_messages(text) {
+        send_ephemeral_reply(&ctx, part).await?;
+    }
+    Ok(())
+}
+
+/// Add an item which every participant of quests created with this template will receive.
+#[poise::command(slash_command)]
+pub async fn add_item(
+    ctx: PoiseContext<'_>,
+    #[description = "Which template?"]
+    #[autocomplete = "autocomplete_quest_template"]
+    name: String,
+    #[description = "Which item?"]
+    #[autocomplete = "autocomplete_item_or_potion"]
+    item: String,
+    #[description = "How many? Defaults to 1."]
+    #[min = 1_u8]
+    amount: Option<u8>,
+) -> Result<(), Error> {
+    let template = find_template(&ctx, &name).await?;
+    let game_data = ctx.data().game.get_by_context(&ctx).await;
+    let Some(item) = InventoryItem::find(game_data, &item) else {
+        return Err(Box::new(ValidationError::new(format!(
+            "Unable to find an item or potion named **{}**, sorry!",
+            item
+        ))));
+    };
+
+    let amount = amount.unwrap_or(1) as i64;
+    sqlx::query!(
+        "INSERT INTO quest_template_reward_item (guild_id, template_name, item_name, amount) VALUES (?, ?, ?, ?) \
+        ON CONFLICT (guild_id, template_name, item_name) DO UPDATE SET amount = amount + excluded.amount",
+        template.guild_id,
+        template.name,
+        item.name,
+        amount
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    send_ephemeral_reply(&ctx, build_template_string(&ctx, &template).await?).await?;
+    log_action(
+        &ActionType::QuestTemplateUpdate,
+        LogActionArguments::triggered_by_user(&ctx),
+        format!(
+            "Added {}x {} to the rewards of the quest template named {}",
+            amount, item.name, template.name
+        ),
+    )
+    .await
+}
+
+/// Remove an item from this template's rewards.
+#[poise::command(slash_command)]
+pub async fn remove_item(
+    ctx: PoiseContext<'_>,
+    #[description = "Which template?"]
+    #[autocomplete = "autocomplete_quest_template"]
+    name: String,
+    #[description = "Which item?"]
+    #[autocomplete = "autocomplete_item_or_potion"]
+    item: String,
+) -> Result<(), Error> {
+    let template = find_template(&ctx, &name).await?;
+    let result = sqlx::query!(
+        "DELETE FROM quest_template_reward_item WHERE guild_id = ? AND template_name = ? AND item_name = ? COLLATE NOCASE",
+        template.guild_id,
+        template.name,
+        item
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{}** isn't part of this template's rewards.",
+            item
+        ))));
+    }
+
+    send_ephemeral_reply(&ctx, build_template_string(&ctx, &template).await?).await?;
+    log_action(
+        &ActionType::QuestTemplateUpdate,
+        LogActionArguments::triggered_by_user(&ctx),
+        format!(
+            "Removed {} from the rewards of the quest template named {}",
+            item, template.name
+        ),
+    )
+    .await
+}
+
+/// Delete a template. Quests which were already created with it stay untouched.
+#[poise::command(slash_command)]
+pub async fn delete(
+    ctx: PoiseContext<'_>,
+    #[description = "Which template?"]
+    #[autocomplete = "autocomplete_quest_template"]
+    name: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    let mut tx = ctx.data().database.begin().await?;
+    sqlx::query!(
+        "DELETE FROM quest_template_reward_item WHERE guild_id = ? AND template_name = ?",
+        guild_id,
+        name
+    )
+    .execute(&mut *tx)
+    .await?;
+    let result = sqlx::query!(
+        "DELETE FROM quest_template WHERE guild_id = ? AND name = ?",
+        guild_id,
+        name
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(Box::new(ValidationError::new(format!(
+            "Unable to find a template named {name}!"
+        ))));
+    }
+    tx.commit().await?;
+
+    send_ephemeral_reply(&ctx, "Template was deleted!").await?;
+    log_action(
+        &ActionType::QuestTemplateDelete,
+        LogActionArguments::triggered_by_user(&ctx),
+        format!("Deleted the quest template named {name}"),
+    )
+    .await
+}
@@ -0,0 +1,34 @@
+use crate::commands::{Error, send_ephemeral_reply};
+use crate::shared::PoiseContext;
+use crate::shared::quest_board::{build_quest_list, can_user_join_quest, get_open_quests};
+use crate::shared::utility::message_splitting::split_long_messages;
+
+/// List all quests which are currently open.
+#[poise::command(slash_command, guild_only)]
+pub async fn quests(
+    ctx: PoiseContext<'_>,
+    #[description = "Only show quests you can still sign up for. Defaults to false."]
+    joinable_only: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    let user_id = ctx.author().id.get() as i64;
+    let database = &ctx.data().database;
+
+    let mut quests = get_open_quests(database, guild_id).await?;
+    if joinable_only.unwrap_or(false) {
+        let mut joinable_quests = Vec::new();
+        for quest in quests {
+            if can_user_join_quest(ctx.data(), guild_id, quest.channel_id, user_id).await? {
+                joinable_quests.push(quest);
+            }
+        }
+        quests = joinable_quests;
+    }
+
+    let text = format!("## 📜 Open Quests\n{}", build_quest_list(&quests));
+    for part in split_long_messages(text) {
+        send_ephemeral_reply(&ctx, part).await?;
+    }
+
+    Ok(())
+}
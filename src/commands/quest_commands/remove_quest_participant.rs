@@ -1,5 +1,6 @@
 use crate::commands::autocompletion::autocomplete_character_name;
 use crate::commands::{parse_character_names, parse_variadic_args, send_error, Error};
+use crate::shared::quest_waitlist::{self, get_quest_participants};
 use crate::shared::utility::quest_message_utils;
 use crate::shared::PoiseContext;
 
@@ -44,6 +45,7 @@ pub async fn remove_quest_participant(
 
     let characters = parse_character_names(&ctx, guild_id.get(), &args).await?;
 
+    let previous_participants = get_quest_participants(&ctx.data().database, channel_id).await?;
     let mut result = String::from("Manually removed the following signups for this quest:\n");
     for x in characters {
         sqlx::query!(
@@ -57,7 +59,19 @@ pub async fn remove_quest_participant(
     }
 
     ctx.say(result).await?;
-    quest_message_utils::update_quest_message(ctx.serenity_context(), ctx.data(), channel_id)
+    quest_message_utils::update_quest_message(
+        ctx.serenity_context(),
+        &ctx.data().database,
+        &ctx.data().game.base_data,
+        channel_id,
+    )
         .await?;
+    quest_waitlist::handle_freed_spots(
+        ctx.serenity_context(),
+        ctx.data(),
+        channel_id,
+        &previous_participants,
+    )
+    .await?;
     Ok(())
 }
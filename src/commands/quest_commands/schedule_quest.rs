@@ -0,0 +1,101 @@
+use crate::commands::{Error, send_ephemeral_reply};
+use crate::shared::PoiseContext;
+use crate::shared::errors::ValidationError;
+use crate::shared::utility::{quest_message_utils, time_offset};
+use chrono::Utc;
+
+/// Set when the quest in this channel starts. Times are in your local time, see /setting_time_offset.
+#[allow(clippy::too_many_arguments)]
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn schedule_quest(
+    ctx: PoiseContext<'_>,
+    #[description = "Which hour?"]
+    #[max = 23_u8]
+    hour: u8,
+    #[description = "Which minute? Defaults to 0."]
+    #[max = 59_u8]
+    minute: Option<u8>,
+    #[description = "Which day? Defaults to today."]
+    #[min = 1_u8]
+    #[max = 31_u8]
+    day: Option<u8>,
+    #[description = "Which month? Defaults to this month."]
+    #[min = 1_u8]
+    #[max = 12_u8]
+    month: Option<u8>,
+    #[description = "Which year? Defaults to this year."] year: Option<u16>,
+) -> Result<(), Error> {
+    let channel_id = ctx.channel_id().get() as i64;
+    let quest = sqlx::query!(
+        "SELECT completion_timestamp FROM quest WHERE channel_id = ?",
+        channel_id
+    )
+    .fetch_optional(&ctx.data().database)
+    .await?;
+    match quest {
+        None => {
+            return Err(Box::new(ValidationError::new(
+                "Doesn't look like there was a quest created within this channel!",
+            )));
+        }
+        Some(quest) if quest.completion_timestamp.is_some() => {
+            return Err(Box::new(ValidationError::new(
+                "This quest has already been completed!",
+            )));
+        }
+        Some(_) => {}
+    }
+
+    let user_id = ctx.author().id.get() as i64;
+    let offset = time_offset::get_user_time_offset(&ctx.data().database, user_id).await;
+    let (hour_offset, minute_offset) = offset.unwrap_or_default();
+    let Some(start_timestamp) = time_offset::local_time_to_unix_timestamp(
+        Some(minute.unwrap_or(0)),
+        Some(hour),
+        day,
+        month,
+        year,
+        hour_offset,
+        minute_offset,
+    ) else {
+        return Err(Box::new(ValidationError::new("That date doesn't exist!")));
+    };
+
+    if start_timestamp <= Utc::now().timestamp() {
+        return Err(Box::new(ValidationError::new(format!(
+            "<t:{}:f> lies in the past!",
+            start_timestamp
+        ))));
+    }
+
+    sqlx::query!(
+        "UPDATE quest SET scheduled_start_timestamp = ?, is_day_before_reminder_sent = FALSE, is_hour_before_reminder_sent = FALSE \
+        WHERE channel_id = ?",
+        start_timestamp,
+        channel_id
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    quest_message_utils::update_quest_message(
+        ctx.serenity_context(),
+        &ctx.data().database,
+        &ctx.data().game.base_data,
+        channel_id,
+    )
+    .await?;
+
+    let mut reply = format!(
+        "The quest will start <t:{0}:f> (<t:{0}:R>). Participants will be reminded a day and an hour before.",
+        start_timestamp
+    );
+    if offset.is_none() {
+        reply.push_str("\n*Looks like you don't have your timezone set up, so the time was interpreted as UTC. Use `/setting_time_offset` to change that.*");
+    }
+    send_ephemeral_reply(&ctx, reply).await?;
+    Ok(())
+}
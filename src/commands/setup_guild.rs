@@ -1,9 +1,9 @@
 use crate::commands::character_commands::validate_user_input;
 use crate::commands::{Error, send_ephemeral_reply};
-use crate::shared::PoiseContext;
+use crate::shared::{PoiseContext, quest_board};
 use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
 use crate::shared::errors::CommandInvocationError;
-use serenity::all::{Mention, Role, RoleId};
+use serenity::all::{ChannelId, Mention, Mentionable, Role, RoleId};
 use serenity::model::channel::Channel;
 
 /// Register this server within the database, or change values that have been set up earlier.
@@ -17,6 +17,8 @@ pub async fn setup_guild(
     name: Option<String>,
     action_log_channel: Option<Channel>,
     default_member_role: Option<Role>,
+    character_application_channel: Option<Channel>,
+    quest_board_channel: Option<Channel>,
 ) -> Result<(), Error> {
     let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
 
@@ -28,18 +30,26 @@ pub async fn setup_guild(
     };
     let action_log_channel_id = action_log_channel.map(|x| x.id().get() as i64);
     let default_member_role_id = default_member_role.map(|x| x.id.get() as i64);
+    let character_application_channel_id =
+        character_application_channel.map(|x| x.id().get() as i64);
+    let quest_board_channel_id = quest_board_channel.map(|x| x.id().get() as i64);
 
     match sqlx::query!(
-        "INSERT INTO guild (id, name, action_log_channel_id, default_member_role_id) VALUES (?, ?, ?, ?)
+        "INSERT INTO guild (id, name, action_log_channel_id, default_member_role_id, character_application_channel_id, quest_board_channel_id) VALUES (?, ?, ?, ?, ?, ?)
 ON CONFLICT (id) DO UPDATE SET
     name = excluded.name,
     action_log_channel_id = excluded.action_log_channel_id,
-    default_member_role_id = excluded.default_member_role_id
+    default_member_role_id = excluded.default_member_role_id,
+    character_application_channel_id = excluded.character_application_channel_id,
+    quest_board_channel_id = excluded.quest_board_channel_id,
+    quest_board_message_id = CASE WHEN quest_board_channel_id IS excluded.quest_board_channel_id THEN quest_board_message_id END
 RETURNING *",
         guild_id,
         name,
         action_log_channel_id,
-        default_member_role_id
+        default_member_role_id,
+        character_application_channel_id,
+        quest_board_channel_id
     )
         .fetch_one(&ctx.data().database)
         .await {
@@ -66,6 +76,25 @@ RETURNING *",
                 )
                     .await?;
             }
+            if let Some(character_application_channel_id) = record.character_application_channel_id {
+                let channel = ChannelId::new(character_application_channel_id as u64);
+                log_action(
+                    &ActionType::Initialization,
+                    LogActionArguments::triggered_by_user(&ctx),
+                    format!("Character applications will be posted in {}", channel.mention()),
+                )
+                    .await?;
+            }
+            if let Some(quest_board_channel_id) = record.quest_board_channel_id {
+                let channel = ChannelId::new(quest_board_channel_id as u64);
+                log_action(
+                    &ActionType::Initialization,
+                    LogActionArguments::triggered_by_user(&ctx),
+                    format!("The quest board will be posted in {}", channel.mention()),
+                )
+                    .await?;
+                quest_board::update_quest_board(ctx.serenity_context(), &ctx.data().database, guild_id).await?;
+            }
 
             Ok(())
         }
@@ -3,7 +3,8 @@ use tokio::join;
 use crate::commands::{Error, ensure_user_exists};
 use crate::shared::PoiseContext;
 use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
-use crate::shared::errors::CommandInvocationError;
+use crate::shared::errors::{CommandInvocationError, ValidationError};
+use crate::shared::quest_gm::{build_gm_mention_list, get_quest_gm_ids, is_quest_gm};
 
 /// Store your GM Experience after a quest.
 #[poise::command(
@@ -16,17 +17,40 @@ pub async fn store_gm_experience(
     #[min = 1_i64]
     #[max = 100_i64]
     amount: i64,
+    #[description = "Also grant this to the other GMs of the quest in this channel?"]
+    share_with_co_gms: Option<bool>,
 ) -> Result<(), Error> {
     let user_id = ctx.author().id.get() as i64;
     let guild_id = ctx.guild().expect("Command is guild_only!").id.get() as i64;
     ensure_user_exists(&ctx, user_id, guild_id).await;
 
+    let co_gm_ids = if share_with_co_gms.unwrap_or(false) {
+        let channel_id = ctx.channel_id().get() as i64;
+        if !is_quest_gm(&ctx.data().database, channel_id, user_id).await? {
+            return Err(Box::new(ValidationError::new(
+                "You need to be a GM of the quest in this channel to share GM Experience!",
+            )));
+        }
+        get_quest_gm_ids(&ctx.data().database, channel_id)
+            .await?
+            .into_iter()
+            .filter(|x| *x != user_id)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    for co_gm_id in &co_gm_ids {
+        ensure_user_exists(&ctx, *co_gm_id, guild_id).await;
+    }
+
+    let mut tx = ctx.data().database.begin().await?;
     match sqlx::query!(
         "SELECT gm_experience FROM user_in_guild WHERE user_id = ? AND guild_id = ?",
         user_id,
         guild_id
     )
-    .fetch_one(&ctx.data().database)
+    .fetch_one(&mut *tx)
     .await
     {
         Ok(record) => {
@@ -37,16 +61,41 @@ pub async fn store_gm_experience(
                 user_id,
                 guild_id
             )
-            .execute(&ctx.data().database)
+            .execute(&mut *tx)
                 .await
             {
                 Ok(_) => {
-                    let text = format!("{} stored {} GM Experience!", ctx.author(), amount);
+                    for co_gm_id in &co_gm_ids {
+                        sqlx::query!(
+                            "UPDATE user_in_guild SET gm_experience = gm_experience + ? WHERE user_id = ? AND guild_id = ?",
+                            amount,
+                            co_gm_id,
+                            guild_id
+                        )
+                        .execute(&mut *tx)
+                        .await?;
+                    }
+                    tx.commit().await?;
+
+                    let mut text = format!("{} stored {} GM Experience!", ctx.author(), amount);
+                    if !co_gm_ids.is_empty() {
+                        text.push_str(&format!(
+                            " {} received the same amount.",
+                            build_gm_mention_list(&co_gm_ids)
+                        ));
+                    }
                     let reply = ctx.say(&text);
                     let log = log_action(&ActionType::StoreGMExperience,
                                          LogActionArguments::triggered_by_user(&ctx),
-                                         &text);
+                                         format!("{} stored {} GM Experience!", ctx.author(), amount));
                     let _ = join!(reply, log);
+
+                    for co_gm_id in &co_gm_ids {
+                        let _ = log_action(&ActionType::StoreGMExperience,
+                                           LogActionArguments::triggered_by_user(&ctx),
+                                           format!("<@{}> received {} GM Experience shared by {}!", co_gm_id, amount, ctx.author()))
+                            .await;
+                    }
                 }
                 Err(e) => {
                     return Err(Box::new(
@@ -0,0 +1,506 @@
+use crate::Error;
+use crate::commands::autocompletion::{
+    autocomplete_character_name, autocomplete_owned_character_name, autocomplete_team_name,
+};
+use crate::commands::character_commands::validate_user_input;
+use crate::commands::wallet_commands::update_wallet_post;
+use crate::commands::{
+    ensure_guild_exists, ensure_user_owns_character, ensure_user_owns_character_or_is_gm,
+    find_character, send_ephemeral_reply,
+};
+use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
+use crate::shared::enums::QuestParticipantSelectionMechanism;
+use crate::shared::errors::ValidationError;
+use crate::shared::quest_restrictions::get_team_signup_candidates;
+use crate::shared::quest_signup::{is_already_signed_up_error, persist_signup};
+use crate::shared::quest_waitlist::get_quest_participants;
+use crate::shared::team::{
+    Team, find_team_by_name, get_team_member_ids_and_names, get_team_of_character,
+};
+use crate::shared::utility::quest_message_utils;
+use crate::shared::{PoiseContext, emoji};
+use chrono::Utc;
+use poise::CreateReply;
+use serenity::all::{
+    ButtonStyle, ChannelId, CreateActionRow, CreateButton, CreateMessage, Mentionable, MessageId,
+    UserId,
+};
+use sqlx::SqliteConnection;
+
+const EMBLEM_MAX_LENGTH: usize = 64;
+
+/// Manage exploration teams.
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("create", "invite", "leave", "info", "sign_up"),
+    subcommand_required
+)]
+pub async fn team(_: PoiseContext<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+async fn find_team(ctx: &PoiseContext<'_>, name: &str) -> Result<Team, Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    match find_team_by_name(&ctx.data().database, guild_id, name).await? {
+        Some(team) => Ok(team),
+        None => Err(Box::new(ValidationError::new(format!(
+            "Unable to find a team named **{}**.",
+            name
+        )))),
+    }
+}
+
+async fn get_team_of_member(
+    ctx: &PoiseContext<'_>,
+    character_id: i64,
+    character_name: &str,
+) -> Result<Team, Error> {
+    match get_team_of_character(&ctx.data().database, character_id).await? {
+        Some(team) => Ok(team),
+        None => Err(Box::new(ValidationError::new(format!(
+            "**{}** isn't part of any team.",
+            character_name
+        )))),
+    }
+}
+
+/// Found a new exploration team. Also creates a shared wallet for it.
+#[poise::command(slash_command)]
+pub async fn create(
+    ctx: PoiseContext<'_>,
+    #[description = "What should the team be called?"] name: String,
+    #[description = "Which of your characters is founding the team?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    founder: String,
+    #[description = "An emoji to represent the team."] emblem: Option<String>,
+) -> Result<(), Error> {
+    if let Err(e) = validate_user_input(name.as_str()) {
+        return Err(Box::new(ValidationError::new(e)));
+    }
+    if let Some(emblem) = &emblem
+        && (emblem.is_empty() || emblem.len() > EMBLEM_MAX_LENGTH)
+    {
+        return Err(Box::new(ValidationError::new(
+            "That emblem doesn't look like an emoji.",
+        )));
+    }
+
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let founder = find_character(ctx.data(), guild_id, &founder).await?;
+    ensure_user_owns_character(ctx.author(), &founder)?;
+    if let Some(team) = get_team_of_character(&ctx.data().database, founder.id).await? {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{}** is already part of {}.",
+            founder.name,
+            team.display_name()
+        ))));
+    }
+
+    let guild_id = guild_id as i64;
+    ensure_guild_exists(&ctx, guild_id).await;
+    let message = ctx
+        .channel_id()
+        .send_message(
+            ctx,
+            CreateMessage::new().content(
+                "[Placeholder. This should get replaced or deleted within a couple seconds.]",
+            ),
+        )
+        .await?;
+    let message_id = message.id.get() as i64;
+    let channel_id = message.channel_id.get() as i64;
+    let timestamp = Utc::now().timestamp();
+
+    let mut transaction = ctx.data().database.begin().await?;
+    let wallet = sqlx::query!(
+        "INSERT INTO wallet (name, guild_id, bot_message_id, bot_message_channel_id, creation_timestamp, money) VALUES (?, ?, ?, ?, ?, 0) RETURNING id",
+        name,
+        guild_id,
+        message_id,
+        channel_id,
+        timestamp,
+    )
+    .fetch_one(&mut *transaction)
+    .await;
+    let Ok(wallet) = wallet else {
+        message.delete(ctx).await?;
+        return Err(Box::new(ValidationError::new(
+            "Something went wrong! Does a wallet or team with this name already exist on this server?",
+        )));
+    };
+
+    let team = sqlx::query!(
+        "INSERT INTO team (guild_id, name, emblem, wallet_id, creation_timestamp) VALUES (?, ?, ?, ?, ?) RETURNING id",
+        guild_id,
+        name,
+        emblem,
+        wallet.id,
+        timestamp
+    )
+    .fetch_one(&mut *transaction)
+    .await;
+    let Ok(team) = team else {
+        message.delete(ctx).await?;
+        return Err(Box::new(ValidationError::new(
+            "Something went wrong! Does a team with this name already exist on this server?",
+        )));
+    };
+
+    sqlx::query!(
+        "INSERT INTO team_member (character_id, team_id) VALUES (?, ?)",
+        founder.id,
+        team.id
+    )
+    .execute(&mut *transaction)
+    .await?;
+    sqlx::query!(
+        "INSERT INTO wallet_owner (wallet_id, character_id) VALUES (?, ?)",
+        wallet.id,
+        founder.id
+    )
+    .execute(&mut *transaction)
+    .await?;
+    transaction.commit().await?;
+
+    let team = Team {
+        id: team.id,
+        name,
+        emblem,
+        wallet_id: wallet.id,
+        rank_points: 0,
+    };
+    ctx.say(format!(
+        "**{}** founded the exploration team **{}**!",
+        founder.name,
+        team.display_name()
+    ))
+    .await?;
+    update_wallet_post(&ctx, wallet.id).await;
+    log_action(
+        &ActionType::TeamChange,
+        LogActionArguments::triggered_by_user(&ctx),
+        format!("{} founded team {}", founder.name, team.name),
+    )
+    .await
+}
+
+/// Invite another character into your team.
+#[poise::command(slash_command)]
+pub async fn invite(
+    ctx: PoiseContext<'_>,
+    #[description = "Which of your team members is sending the invite?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    character: String,
+    #[description = "Who should be invited?"]
+    #[autocomplete = "autocomplete_character_name"]
+    invitee: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character(ctx.author(), &character)?;
+    let team = get_team_of_member(&ctx, character.id, &character.name).await?;
+
+    let invitee = find_character(ctx.data(), guild_id, &invitee).await?;
+    if let Some(invitee_team) = get_team_of_character(&ctx.data().database, invitee.id).await? {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{}** is already part of {}.",
+            invitee.name,
+            invitee_team.display_name()
+        ))));
+    }
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!(
+                "{}, **{}** invited **{}** to join {}!",
+                UserId::new(invitee.user_id).mention(),
+                character.name,
+                invitee.name,
+                team.display_name()
+            ))
+            .components(vec![CreateActionRow::Buttons(vec![
+                CreateButton::new(format!("team-invite_{}_{}", team.id, invitee.id))
+                    .label("Join Team")
+                    .style(ButtonStyle::Success),
+            ])]),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Leave a team.
+#[poise::command(slash_command)]
+pub async fn leave(
+    ctx: PoiseContext<'_>,
+    #[description = "Which character should leave their team?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character_or_is_gm(
+        ctx.author(),
+        ctx.author_member()
+            .await
+            .expect("author_member should be set within guild context."),
+        &character,
+    )?;
+    let team = get_team_of_member(&ctx, character.id, &character.name).await?;
+
+    let mut transaction = ctx.data().database.begin().await?;
+    sqlx::query!(
+        "DELETE FROM team_member WHERE character_id = ?",
+        character.id
+    )
+    .execute(&mut *transaction)
+    .await?;
+    sqlx::query!(
+        "DELETE FROM wallet_owner WHERE wallet_id = ? AND character_id = ?",
+        team.wallet_id,
+        character.id
+    )
+    .execute(&mut *transaction)
+    .await?;
+    let remaining_members = sqlx::query!(
+        "SELECT COUNT(*) as count FROM team_member WHERE team_id = ?",
+        team.id
+    )
+    .fetch_one(&mut *transaction)
+    .await?
+    .count;
+    let wallet_post = if remaining_members == 0 {
+        Some(disband_team(&mut transaction, &team).await?)
+    } else {
+        None
+    };
+    transaction.commit().await?;
+
+    if let Some((channel_id, message_id)) = wallet_post {
+        let _ = ChannelId::new(channel_id as u64)
+            .delete_message(ctx, MessageId::new(message_id as u64))
+            .await;
+        ctx.say(format!(
+            "**{}** left {}. Since nobody is left, the team has been disbanded and its wallet was closed.",
+            character.name,
+            team.display_name()
+        ))
+        .await?;
+        return log_action(
+            &ActionType::TeamChange,
+            LogActionArguments::triggered_by_user(&ctx),
+            format!(
+                "{} left team {}, which got disbanded",
+                character.name, team.name
+            ),
+        )
+        .await;
+    }
+
+    ctx.say(format!(
+        "**{}** left {}.",
+        character.name,
+        team.display_name()
+    ))
+    .await?;
+    update_wallet_post(&ctx, team.wallet_id).await;
+    log_action(
+        &ActionType::TeamChange,
+        LogActionArguments::triggered_by_user(&ctx),
+        format!("{} left team {}", character.name, team.name),
+    )
+    .await
+}
+
+/// Deletes the team alongside its wallet. Ledger entries are kept, but no longer point to the wallet.
+/// Returns the channel and message id of the wallet post, so it can be deleted once the transaction went through.
+async fn disband_team(
+    transaction: &mut SqliteConnection,
+    team: &Team,
+) -> Result<(i64, i64), Error> {
+    sqlx::query!("DELETE FROM team WHERE id = ?", team.id)
+        .execute(&mut *transaction)
+        .await?;
+    sqlx::query!(
+        "UPDATE ledger_entry SET wallet_id = NULL WHERE wallet_id = ?",
+        team.wallet_id
+    )
+    .execute(&mut *transaction)
+    .await?;
+    sqlx::query!(
+        "DELETE FROM wallet_shop_item WHERE wallet_id = ?",
+        team.wallet_id
+    )
+    .execute(&mut *transaction)
+    .await?;
+    sqlx::query!(
+        "DELETE FROM wallet_owner WHERE wallet_id = ?",
+        team.wallet_id
+    )
+    .execute(&mut *transaction)
+    .await?;
+    let wallet = sqlx::query!(
+        "DELETE FROM wallet WHERE id = ? RETURNING bot_message_channel_id, bot_message_id",
+        team.wallet_id
+    )
+    .fetch_one(&mut *transaction)
+    .await?;
+
+    Ok((wallet.bot_message_channel_id, wallet.bot_message_id))
+}
+
+/// Display a team's rank, members and funds.
+#[poise::command(slash_command)]
+pub async fn info(
+    ctx: PoiseContext<'_>,
+    #[description = "Which team?"]
+    #[autocomplete = "autocomplete_team_name"]
+    name: String,
+) -> Result<(), Error> {
+    let team = find_team(&ctx, &name).await?;
+    let members = get_team_member_ids_and_names(&ctx.data().database, team.id).await?;
+    let wallet = sqlx::query!("SELECT money FROM wallet WHERE id = ?", team.wallet_id)
+        .fetch_one(&ctx.data().database)
+        .await?;
+
+    let rank = team.rank();
+    let member_list = if members.is_empty() {
+        String::from("*No active members.*")
+    } else {
+        members
+            .iter()
+            .map(|(_, name)| format!("- {}", name))
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+
+    send_ephemeral_reply(
+        &ctx,
+        format!(
+            "## {}\n{} {} `({} Rank Points)`\n{} {}\n### Members\n{}",
+            team.display_name(),
+            rank.emoji_string(),
+            rank.name_without_emoji(),
+            team.rank_points,
+            wallet.money,
+            emoji::POKE_COIN,
+            member_list
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Sign up all active members of your team for the quest in this channel.
+#[poise::command(slash_command)]
+pub async fn sign_up(
+    ctx: PoiseContext<'_>,
+    #[description = "Which of your team members is signing up the team?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    character: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character(ctx.author(), &character)?;
+    let team = get_team_of_member(&ctx, character.id, &character.name).await?;
+
+    let channel_id = ctx.channel_id().get() as i64;
+    let quest = sqlx::query!(
+        "SELECT completion_timestamp, participant_selection_mechanism FROM quest WHERE channel_id = ?",
+        channel_id
+    )
+    .fetch_optional(&ctx.data().database)
+    .await?;
+    let quest = match quest {
+        None => {
+            return Err(Box::new(ValidationError::new(
+                "Doesn't look like there was a quest created within this channel!",
+            )));
+        }
+        Some(quest) if quest.completion_timestamp.is_some() => {
+            return Err(Box::new(ValidationError::new(
+                "This quest has already been completed.",
+            )));
+        }
+        Some(quest) => quest,
+    };
+
+    let candidates =
+        get_team_signup_candidates(ctx.data(), guild_id as i64, team.id, channel_id).await?;
+    let timestamp = Utc::now().timestamp();
+    let mut signed_up = Vec::new();
+    let mut already_signed_up = Vec::new();
+    let mut rejected = Vec::new();
+    for candidate in candidates {
+        if let Some(reason) = &candidate.ineligibility_reason {
+            rejected.push(format!("- **{}** {}", candidate.name, reason));
+            continue;
+        }
+
+        match persist_signup(&ctx.data().database, channel_id, candidate.id, timestamp).await {
+            Ok(_) => signed_up.push(candidate),
+            Err(error) if is_already_signed_up_error(&error) => {
+                already_signed_up.push(candidate.name)
+            }
+            Err(error) => rejected.push(format!("- **{}**: {}", candidate.name, error)),
+        }
+    }
+
+    let mut text = if signed_up.is_empty() {
+        format!("Nobody from {} was signed up.", team.display_name())
+    } else {
+        let waiting = if quest.participant_selection_mechanism
+            == QuestParticipantSelectionMechanism::FirstComeFirstServe as i64
+        {
+            let participants = get_quest_participants(&ctx.data().database, channel_id).await?;
+            signed_up
+                .iter()
+                .filter(|x| participants.iter().all(|p| p.character_id != x.id))
+                .map(|x| x.name.clone())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let mut text = format!(
+            "Signed up {} for this quest: {}",
+            team.display_name(),
+            signed_up
+                .iter()
+                .map(|x| x.name.as_str())
+                .collect::<Vec<&str>>()
+                .join(", ")
+        );
+        if !waiting.is_empty() {
+            text.push_str(&format!(
+                "\nThe quest is full, so these are on the waiting queue: {}",
+                waiting.join(", ")
+            ));
+        }
+        text
+    };
+    if !already_signed_up.is_empty() {
+        text.push_str(&format!(
+            "\nAlready signed up: {}",
+            already_signed_up.join(", ")
+        ));
+    }
+    if !rejected.is_empty() {
+        text.push_str("\nThese can't join:\n");
+        text.push_str(&rejected.join("\n"));
+    }
+
+    send_ephemeral_reply(&ctx, text).await?;
+    if !signed_up.is_empty() {
+        quest_message_utils::update_quest_message(
+            ctx.serenity_context(),
+            &ctx.data().database,
+            &ctx.data().game.base_data,
+            channel_id,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
-use crate::commands::{send_ephemeral_reply, Error};
+use crate::commands::{send_ephemeral_reply, send_error, Error};
 use crate::shared::PoiseContext;
-use chrono::{Datelike, Duration, FixedOffset, NaiveDate, TimeZone, Timelike, Utc};
+use crate::shared::utility::time_offset;
+use chrono::Utc;
 use poise::ReplyHandle;
 use serenity::utils::MessageBuilder;
 
@@ -76,24 +77,21 @@ async fn print_timestamp<'a>(
     hour_offset: i64,
     minute_offset: i64,
 ) -> Result<(), Error> {
-    let second_offset = ((hour_offset * 60 + minute_offset) * 60) as i32;
-    let offset = FixedOffset::east_opt(second_offset).expect("Should never be out of bounds");
-
-    let local_datetime = offset.from_utc_datetime(&Utc::now().naive_utc());
-    let minute = minute.unwrap_or(local_datetime.minute() as u8);
-    let hour = hour.unwrap_or(local_datetime.hour() as u8);
-    let day = day.unwrap_or(local_datetime.day() as u8);
-    let month = month.unwrap_or(local_datetime.month() as u8);
-    let year = year.unwrap_or(local_datetime.year() as u16);
-    let timestamp = NaiveDate::from_ymd_opt(year.into(), month.into(), day.into())
-        .expect("ymd should be valid!")
-        .and_hms_opt(hour.into(), minute.into(), 0)
-        .expect("hms should be valid!");
-
-    let timestamp_utc = Utc.from_local_datetime(&timestamp).unwrap().naive_utc();
-    let unix_timestamp = timestamp_utc.and_utc().timestamp()
-        - Duration::hours(hour_offset).num_seconds()
-        - Duration::minutes(minute_offset).num_seconds();
+    let Some(unix_timestamp) = time_offset::local_time_to_unix_timestamp(
+        minute,
+        hour,
+        day,
+        month,
+        year,
+        hour_offset,
+        minute_offset,
+    ) else {
+        return send_error(
+            ctx,
+            "That doesn't seem to be a valid date. Please double check the day and month!",
+        )
+        .await;
+    };
 
     let result = std::format!("<t:{0}:f> (<t:{0}:R>)", unix_timestamp.to_string());
     let mut builder = MessageBuilder::default();
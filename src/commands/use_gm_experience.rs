@@ -52,7 +52,7 @@ pub async fn use_gm_experience(
                 .await
             {
                 Ok(_) => {
-                    let emoji = get_character_emoji(ctx.serenity_context(), ctx.data(), character.id).await;
+                    let emoji = get_character_emoji(ctx.serenity_context(), &ctx.data().database, &ctx.data().game.base_data, character.id).await;
                     let text = format!("Used {} GM Experience on {}{}!", amount, emoji.unwrap_or(String::new()), character.name);
                     let reply = ctx.say(&text);
                     let log = log_action(
@@ -9,11 +9,13 @@ use crate::shared::data::Data;
 use crate::shared::{PoiseContext, emoji};
 use poise::Command;
 use serenity::all::{ChannelId, EditMessage, MessageId};
+use sqlx::{Pool, Sqlite};
 
 mod add_wallet_owner;
 mod create_wallet;
 mod edit_wallet;
 mod pay;
+mod shop;
 mod wallet_info;
 mod withdraw;
 
@@ -22,6 +24,7 @@ pub fn get_all_commands() -> Vec<Command<Data, Error>> {
         create_wallet::create_wallet(),
         add_wallet_owner::add_wallet_owner(),
         pay::pay(),
+        shop::shop(),
         withdraw::withdraw(),
         wallet_info::wallet_info(),
         edit_wallet::edit_wallet(),
@@ -29,7 +32,7 @@ pub fn get_all_commands() -> Vec<Command<Data, Error>> {
 }
 
 pub async fn update_wallet_post<'a>(ctx: &PoiseContext<'a>, wallet_id: i64) {
-    if let Some(result) = build_wallet_string(ctx, wallet_id).await {
+    if let Some(result) = build_wallet_string(&ctx.data().database, wallet_id).await {
         let message = ctx
             .serenity_context()
             .http
@@ -57,8 +60,8 @@ pub async fn update_wallet_post<'a>(ctx: &PoiseContext<'a>, wallet_id: i64) {
     }
 }
 
-async fn build_wallet_string<'a>(
-    ctx: &PoiseContext<'a>,
+pub(crate) async fn build_wallet_string(
+    database: &Pool<Sqlite>,
     wallet_id: i64,
 ) -> Option<BuildUpdatedStatMessageStringResult> {
     let entry = sqlx::query!(
@@ -66,7 +69,7 @@ async fn build_wallet_string<'a>(
             FROM wallet WHERE id = ? ORDER BY rowid LIMIT 1",
         wallet_id
     )
-    .fetch_one(&ctx.data().database)
+    .fetch_one(database)
     .await;
 
     let owners = sqlx::query!(
@@ -74,7 +77,7 @@ async fn build_wallet_string<'a>(
                 SELECT character_id FROM wallet_owner WHERE wallet_id = ?)",
         wallet_id
     )
-    .fetch_all(&ctx.data().database)
+    .fetch_all(database)
     .await;
 
     let owner_line;
@@ -168,7 +171,7 @@ pub async fn change_wallet_stat_after_validation<'a>(
             }
 
             log_action(action_type,
-                       LogActionArguments::triggered_by_user(ctx),
+                       LogActionArguments::triggered_by_user(ctx).with_wallet_change(record.id, database_column, amount),
                        format!("{} {} {} {} {}", added_or_removed, amount.abs(), action, to_or_from, record.name).as_str()).await
         }
         Err(_) => {
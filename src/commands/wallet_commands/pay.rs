@@ -6,9 +6,10 @@ use crate::commands::wallet_commands::change_wallet_stat_after_validation;
 use crate::commands::{
     Error, ensure_character_has_money, ensure_user_owns_character, find_character, find_wallet,
 };
-use crate::shared::action_log::ActionType;
+use crate::shared::action_log::{ActionType, create_undo_button};
 use crate::shared::cache::{CharacterCacheItem, WalletCacheItem};
 use crate::shared::{PoiseContext, emoji};
+use poise::CreateReply;
 
 async fn transfer_money_from_character_to_wallet<'a>(
     ctx: &PoiseContext<'a>,
@@ -39,13 +40,17 @@ async fn transfer_money_from_character_to_wallet<'a>(
         )
         .await
         {
-            ctx.say(format!(
-                "***{}** paid {} {} to **{}***!",
-                character.name,
-                amount,
-                emoji::POKE_COIN,
-                wallet.name
-            ))
+            ctx.send(
+                CreateReply::default()
+                    .content(format!(
+                        "***{}** paid {} {} to **{}***!",
+                        character.name,
+                        amount,
+                        emoji::POKE_COIN,
+                        wallet.name
+                    ))
+                    .components(vec![create_undo_button(ctx.id())]),
+            )
             .await?;
         } else {
             // TODO: The undo might fail.
@@ -75,7 +80,6 @@ pub async fn pay(
     #[autocomplete = "autocomplete_wallet_name"]
     wallet: String,
 ) -> Result<(), Error> {
-    // TODO: Button to undo the transaction which lasts for a minute or so.
     let guild_id = ctx.guild_id().expect("Command is guild_only").get();
     let character = find_character(ctx.data(), guild_id, &character).await?;
     let wallet = find_wallet(ctx.data(), guild_id, &wallet).await?;
@@ -0,0 +1,338 @@
+use crate::Error;
+use crate::commands::autocompletion::{
+    autocomplete_item, autocomplete_owned_character_name, autocomplete_wallet_name,
+};
+use crate::commands::wallet_commands::update_wallet_post;
+use crate::commands::{
+    ensure_user_owns_character, ensure_user_owns_wallet_or_is_gm, find_character, find_wallet,
+};
+use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
+use crate::shared::character::update_character_post_with_poise_context;
+use crate::shared::errors::ValidationError;
+use crate::shared::inventory::InventoryItem;
+use crate::shared::{PoiseContext, emoji, inventory};
+
+/// Buy stuff from wallets, or set up a shop for your own.
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("browse", "buy", "add", "remove"),
+    subcommand_required
+)]
+pub async fn shop(_: PoiseContext<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// See what's for sale.
+#[poise::command(slash_command)]
+pub async fn browse(
+    ctx: PoiseContext<'_>,
+    #[description = "What's the wallet's name?"]
+    #[autocomplete = "autocomplete_wallet_name"]
+    wallet: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let wallet = find_wallet(ctx.data(), guild_id, &wallet).await?;
+
+    let entries = sqlx::query!(
+        "SELECT item_name, price, stock FROM wallet_shop_item WHERE wallet_id = ? ORDER BY item_name",
+        wallet.id
+    )
+    .fetch_all(&ctx.data().database)
+    .await?;
+
+    if entries.is_empty() {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{}** doesn't sell anything right now.",
+            wallet.name
+        ))));
+    }
+
+    let mut message = format!("## 🛒 {}\n", wallet.name);
+    for entry in entries {
+        let stock = match entry.stock {
+            Some(0) => String::from(" *(Sold out!)*"),
+            Some(stock) => format!(" *({} left)*", stock),
+            None => String::new(),
+        };
+
+        message.push_str(&format!(
+            "- **{}**: {} {}{}\n",
+            entry.item_name,
+            entry.price,
+            emoji::POKE_COIN,
+            stock
+        ));
+    }
+
+    ctx.say(message).await?;
+    Ok(())
+}
+
+/// Buy an item. The money goes into the wallet, the item into your backpack.
+#[poise::command(slash_command)]
+pub async fn buy(
+    ctx: PoiseContext<'_>,
+    #[description = "Who's buying?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    character: String,
+    #[description = "What's the wallet's name?"]
+    #[autocomplete = "autocomplete_wallet_name"]
+    wallet: String,
+    #[description = "Which item?"]
+    #[autocomplete = "autocomplete_item"]
+    item: String,
+    #[description = "How many? Defaults to 1."]
+    #[min = 1_u8]
+    amount: Option<u8>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    let wallet = find_wallet(ctx.data(), guild_id, &wallet).await?;
+    ensure_user_owns_character(ctx.author(), &character)?;
+
+    let amount = amount.unwrap_or(1) as i64;
+    let game_data = ctx.data().game.get_by_context(&ctx).await;
+    let Some(item) = InventoryItem::find(game_data, &item) else {
+        return Err(Box::new(ValidationError::new(format!(
+            "Unable to find an item named **{}**, sorry!",
+            item
+        ))));
+    };
+
+    let mut transaction = ctx.data().database.begin().await?;
+    let Some(entry) = sqlx::query!(
+        "SELECT price, stock FROM wallet_shop_item WHERE wallet_id = ? AND item_name = ?",
+        wallet.id,
+        item.name
+    )
+    .fetch_optional(&mut *transaction)
+    .await?
+    else {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{}** doesn't sell {}.",
+            wallet.name, item.name
+        ))));
+    };
+
+    if let Some(stock) = entry.stock {
+        if stock < amount {
+            return Err(Box::new(ValidationError::new(format!(
+                "**{}** only has {} {} left in stock.",
+                wallet.name, stock, item.name
+            ))));
+        }
+
+        sqlx::query!(
+            "UPDATE wallet_shop_item SET stock = stock - ? WHERE wallet_id = ? AND item_name = ?",
+            amount,
+            wallet.id,
+            item.name
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    let total_price = entry.price * amount;
+    let result = sqlx::query!(
+        "UPDATE character SET money = money - ? WHERE id = ? AND money >= ?",
+        total_price,
+        character.id,
+        total_price
+    )
+    .execute(&mut *transaction)
+    .await?;
+    if result.rows_affected() != 1 {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{} can't afford this.** {}x {} costs {} {}.",
+            character.name,
+            amount,
+            item.name,
+            total_price,
+            emoji::POKE_COIN
+        ))));
+    }
+
+    sqlx::query!(
+        "UPDATE wallet SET money = money + ? WHERE id = ?",
+        total_price,
+        wallet.id
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    inventory::add_item(
+        &mut transaction,
+        game_data,
+        character.id,
+        &character.name,
+        &item,
+        amount,
+    )
+    .await?;
+    transaction.commit().await?;
+
+    ctx.say(format!(
+        "***{}** bought {}x {} from **{}** for {} {}*!",
+        character.name,
+        amount,
+        item.name,
+        wallet.name,
+        total_price,
+        emoji::POKE_COIN
+    ))
+    .await?;
+
+    update_character_post_with_poise_context(&ctx, character.id).await;
+    update_wallet_post(&ctx, wallet.id).await;
+    log_action(
+        &ActionType::ShopPurchase,
+        LogActionArguments::triggered_by_user(&ctx),
+        format!(
+            "Removed {} {} from {} and added {}x {} to their backpack",
+            total_price,
+            emoji::POKE_COIN,
+            character.name,
+            amount,
+            item.name
+        ),
+    )
+    .await?;
+    log_action(
+        &ActionType::ShopPurchase,
+        LogActionArguments::triggered_by_user(&ctx),
+        format!(
+            "Added {} {} to {}",
+            total_price,
+            emoji::POKE_COIN,
+            wallet.name
+        ),
+    )
+    .await
+}
+
+/// Put an item up for sale, or change its price and stock.
+#[poise::command(slash_command)]
+pub async fn add(
+    ctx: PoiseContext<'_>,
+    #[description = "What's the wallet's name?"]
+    #[autocomplete = "autocomplete_wallet_name"]
+    wallet: String,
+    #[description = "Which item?"]
+    #[autocomplete = "autocomplete_item"]
+    item: String,
+    #[description = "Defaults to the item's usual price."]
+    #[min = 0_u32]
+    price: Option<u32>,
+    #[description = "Leave empty for an unlimited supply."]
+    #[min = 0_u32]
+    stock: Option<u32>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let wallet = find_wallet(ctx.data(), guild_id, &wallet).await?;
+    ensure_user_owns_wallet_or_is_gm(
+        ctx.data(),
+        ctx.author().id.get() as i64,
+        ctx.author_member()
+            .await
+            .expect("author_member should be set within guild context."),
+        &wallet,
+    )
+    .await?;
+
+    let game_data = ctx.data().game.get_by_context(&ctx).await;
+    let Some(item) = game_data.items.get(&item.to_lowercase()) else {
+        return Err(Box::new(ValidationError::new(format!(
+            "Unable to find an item named **{}**, sorry!",
+            item
+        ))));
+    };
+
+    let Some(price) = price.map(|x| x as i64).or(item.price.map(|x| x as i64)) else {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{}** doesn't have a default price. Please provide one!",
+            item.name
+        ))));
+    };
+    let stock = stock.map(|x| x as i64);
+
+    sqlx::query!(
+        "INSERT INTO wallet_shop_item (wallet_id, item_name, price, stock) VALUES (?, ?, ?, ?) \
+        ON CONFLICT (wallet_id, item_name) DO UPDATE SET (price, stock) = (excluded.price, excluded.stock)",
+        wallet.id,
+        item.name,
+        price,
+        stock
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    ctx.say(format!(
+        "**{}** now sells {} for {} {}.",
+        wallet.name,
+        item.name,
+        price,
+        emoji::POKE_COIN
+    ))
+    .await?;
+    log_action(
+        &ActionType::ShopEdit,
+        LogActionArguments::triggered_by_user(&ctx),
+        format!(
+            "{} now sells {} for {} {}",
+            wallet.name,
+            item.name,
+            price,
+            emoji::POKE_COIN
+        ),
+    )
+    .await
+}
+
+/// Stop selling an item.
+#[poise::command(slash_command)]
+pub async fn remove(
+    ctx: PoiseContext<'_>,
+    #[description = "What's the wallet's name?"]
+    #[autocomplete = "autocomplete_wallet_name"]
+    wallet: String,
+    #[description = "Which item?"]
+    #[autocomplete = "autocomplete_item"]
+    item: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let wallet = find_wallet(ctx.data(), guild_id, &wallet).await?;
+    ensure_user_owns_wallet_or_is_gm(
+        ctx.data(),
+        ctx.author().id.get() as i64,
+        ctx.author_member()
+            .await
+            .expect("author_member should be set within guild context."),
+        &wallet,
+    )
+    .await?;
+
+    let result = sqlx::query!(
+        "DELETE FROM wallet_shop_item WHERE wallet_id = ? AND item_name = ?",
+        wallet.id,
+        item
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{}** doesn't sell {}.",
+            wallet.name, item
+        ))));
+    }
+
+    ctx.say(format!("**{}** no longer sells {}.", wallet.name, item))
+        .await?;
+    log_action(
+        &ActionType::ShopEdit,
+        LogActionArguments::triggered_by_user(&ctx),
+        format!("{} no longer sells {}", wallet.name, item),
+    )
+    .await
+}
@@ -4,9 +4,10 @@ use crate::commands::wallet_commands::change_wallet_stat_after_validation;
 use crate::commands::{
     Error, ensure_user_owns_wallet_or_is_gm, ensure_wallet_has_money, find_character, find_wallet,
 };
-use crate::shared::action_log::ActionType;
+use crate::shared::action_log::{ActionType, create_undo_button};
 use crate::shared::cache::{CharacterCacheItem, WalletCacheItem};
 use crate::shared::{PoiseContext, emoji};
+use poise::CreateReply;
 
 async fn transfer_money_from_wallet_to_character<'a>(
     ctx: &PoiseContext<'a>,
@@ -45,13 +46,17 @@ async fn transfer_money_from_wallet_to_character<'a>(
         )
         .await
         {
-            ctx.say(format!(
-                "***{}** has withdrawn {} {} from **{}***!",
-                character.name,
-                amount,
-                emoji::POKE_COIN,
-                wallet.name
-            ))
+            ctx.send(
+                CreateReply::default()
+                    .content(format!(
+                        "***{}** has withdrawn {} {} from **{}***!",
+                        character.name,
+                        amount,
+                        emoji::POKE_COIN,
+                        wallet.name
+                    ))
+                    .components(vec![create_undo_button(ctx.id())]),
+            )
             .await?;
         } else {
             // TODO: The undo might fail.
@@ -75,7 +80,6 @@ pub async fn withdraw(
     #[autocomplete = "autocomplete_character_name"]
     character: String,
 ) -> Result<(), Error> {
-    // TODO: Button to undo the transaction which lasts for a minute or so.
     let guild_id = ctx.guild_id().expect("Command is guild_only").get();
     let character = find_character(ctx.data(), guild_id, &character).await?;
     let wallet = find_wallet(ctx.data(), guild_id, &wallet).await?;
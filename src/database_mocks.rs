@@ -103,4 +103,26 @@ pub mod create_mock {
         .execute(db)
         .await;
     }
+
+    pub async fn quest_completion(
+        db: &Pool<Sqlite>,
+        quest_id: i64,
+        character_id: i64,
+        completion_timestamp: i64,
+    ) {
+        let _ = sqlx::query!(
+            "UPDATE quest SET completion_timestamp = ? WHERE channel_id = ?",
+            completion_timestamp,
+            quest_id
+        )
+        .execute(db)
+        .await;
+        let _ = sqlx::query!(
+            "INSERT INTO quest_completion (quest_id, character_id) VALUES (?, ?)",
+            quest_id,
+            character_id
+        )
+        .execute(db)
+        .await;
+    }
 }
@@ -9,7 +9,9 @@ use serenity::builder::{CreateActionRow, CreateButton};
 use serenity::client::Context;
 
 use crate::events::{
-    FrameworkContext, character_stat_edit, parse_interaction_command, quests, send_ephemeral_reply,
+    FrameworkContext, character_application, character_move_edit, character_stat_edit,
+    character_transfer, ledger_undo, parse_interaction_command, quests, send_ephemeral_reply,
+    team_invite,
 };
 use crate::shared::errors::CommandInvocationError;
 use crate::shared::game_data::GameData;
@@ -178,6 +180,14 @@ pub async fn handle_button_interaction(
             )
             .await?;
         }
+        "quest-confirm-weighted-draw" => {
+            quests::quest_weighted_random_draw::confirm_weighted_draw(
+                context,
+                interaction,
+                framework.user_data,
+            )
+            .await?;
+        }
         "quest-list-all-participants" => {
             quests::quest_list_all_participants::quest_list_all_participants(
                 context,
@@ -186,6 +196,14 @@ pub async fn handle_button_interaction(
             )
             .await?;
         }
+        "quest-poll-vote" => {
+            quests::quest_poll::quest_poll_vote(context, interaction, framework.user_data, args)
+                .await?;
+        }
+        "quest-poll-schedule" => {
+            quests::quest_poll::quest_poll_schedule(context, interaction, framework.user_data)
+                .await?;
+        }
         "quest-history" => {
             return post_quest_history(context, &framework, interaction, args).await;
         }
@@ -199,6 +217,60 @@ pub async fn handle_button_interaction(
             )
             .await?;
         }
+        "move-edit-initialize" => {
+            character_move_edit::initialize(
+                context,
+                interaction,
+                framework.user_data,
+                get_game_data(&framework, interaction).await,
+                args,
+            )
+            .await?;
+        }
+        "move-edit-page" => {
+            character_move_edit::change_page(
+                context,
+                interaction,
+                framework.user_data,
+                get_game_data(&framework, interaction).await,
+                args,
+            )
+            .await?;
+        }
+        "ledger-undo" => {
+            ledger_undo::undo(
+                context,
+                interaction,
+                framework.user_data,
+                get_game_data(&framework, interaction).await,
+                args,
+            )
+            .await?;
+        }
+        "character-application" => {
+            character_application::review_character_application(
+                context,
+                interaction,
+                framework.user_data,
+                get_game_data(&framework, interaction).await,
+                args,
+            )
+            .await?;
+        }
+        "character-transfer" => {
+            character_transfer::handle_character_transfer(
+                context,
+                interaction,
+                framework.user_data,
+                get_game_data(&framework, interaction).await,
+                args,
+            )
+            .await?;
+        }
+        "team-invite" => {
+            team_invite::accept_team_invite(context, interaction, framework.user_data, args)
+                .await?;
+        }
         "toggle-role" => toggle_role(context, interaction, args).await?,
         &_ => {}
     }
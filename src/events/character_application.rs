@@ -0,0 +1,396 @@
+use crate::Error;
+use crate::commands::is_user_admin_or_gm;
+use crate::events::{send_error, update_character_post};
+use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
+use crate::shared::character_application::{
+    CharacterApplication, build_review_message, change_application_status,
+    get_character_application, get_status_history,
+};
+use crate::shared::character_creation::{
+    DEFAULT_STARTING_EXPERIENCE, DEFAULT_STARTING_MONEY, NewCharacter, insert_new_character,
+};
+use crate::shared::data::Data;
+use crate::shared::emoji;
+use crate::shared::enums::CharacterApplicationStatus;
+use crate::shared::game_data::{GameData, PokemonApiId};
+use crate::shared::guild_settings::get_progression_rules;
+use poise::Modal;
+use serenity::all::{
+    ChannelId, ComponentInteraction, CreateAllowedMentions, CreateInteractionResponseFollowup,
+    CreateMessage, EditMessage, GuildId, Mentionable, MessageId, UserId,
+};
+use serenity::client::Context;
+use std::borrow::Cow;
+use std::str::FromStr;
+
+#[derive(Debug, Modal)]
+#[name = "Request Changes"]
+struct ChangeRequestModal {
+    #[name = "What should be changed?"]
+    #[paragraph]
+    #[max_length = 1000]
+    changes: String,
+}
+
+#[derive(Debug, Modal)]
+#[name = "Reject Application"]
+struct RejectionModal {
+    #[name = "Reason"]
+    #[placeholder = "Optional. Will be shown to the player."]
+    #[paragraph]
+    #[max_length = 1000]
+    reason: Option<String>,
+}
+
+/// Handles the Approve, Request Changes and Reject buttons below a character application.
+pub async fn review_character_application(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+    game_data: &GameData,
+    args: Vec<&str>,
+) -> Result<(), Error> {
+    if args.len() != 2 {
+        return send_error(
+            &interaction,
+            context,
+            "Are you trying to do anything cheesy?",
+        )
+        .await;
+    }
+
+    let is_gm = interaction
+        .member
+        .as_ref()
+        .is_some_and(|x| is_user_admin_or_gm(Cow::Borrowed(x)));
+    if !is_gm {
+        return send_error(
+            &interaction,
+            context,
+            "Only GMs can review character applications.",
+        )
+        .await;
+    }
+
+    let application_id = i64::from_str(args[0])?;
+    let Some(application) = get_character_application(&data.database, application_id).await? else {
+        return send_error(
+            &interaction,
+            context,
+            "That application doesn't seem to exist anymore.",
+        )
+        .await;
+    };
+    if application.status() != CharacterApplicationStatus::Pending {
+        return send_error(
+            &interaction,
+            context,
+            "This application isn't waiting for a review right now.",
+        )
+        .await;
+    }
+
+    match args[1] {
+        "approve" => approve(context, interaction, data, game_data, application).await,
+        "request-changes" => {
+            let Some(modal) = poise::execute_modal_on_component_interaction::<ChangeRequestModal>(
+                Cow::Borrowed(context),
+                interaction.clone(),
+                None,
+                None,
+            )
+            .await?
+            else {
+                return Ok(());
+            };
+
+            finish_review(
+                context,
+                interaction,
+                data,
+                &application,
+                CharacterApplicationStatus::ChangesRequested,
+                Some(&modal.changes),
+            )
+            .await?;
+            notify_player(
+                context,
+                &application,
+                format!(
+                    "a GM requested some changes to your character application for **{}**:\n> {}\nOnce you're done, use `/apply_character` with the same name to submit it again.",
+                    application.name,
+                    modal.changes.replace('\n', "\n> ")
+                ),
+            )
+            .await
+        }
+        "reject" => {
+            let Some(modal) = poise::execute_modal_on_component_interaction::<RejectionModal>(
+                Cow::Borrowed(context),
+                interaction.clone(),
+                None,
+                None,
+            )
+            .await?
+            else {
+                return Ok(());
+            };
+
+            finish_review(
+                context,
+                interaction,
+                data,
+                &application,
+                CharacterApplicationStatus::Rejected,
+                modal.reason.as_deref(),
+            )
+            .await?;
+            let reason = match &modal.reason {
+                Some(reason) => format!("\n> {}", reason.replace('\n', "\n> ")),
+                None => String::new(),
+            };
+            notify_player(
+                context,
+                &application,
+                format!(
+                    "unfortunately your character application for **{}** has been rejected.{}",
+                    application.name, reason
+                ),
+            )
+            .await
+        }
+        _ => {
+            send_error(
+                &interaction,
+                context,
+                "Are you trying to do anything cheesy?",
+            )
+            .await
+        }
+    }
+}
+
+async fn approve(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+    game_data: &GameData,
+    application: CharacterApplication,
+) -> Result<(), Error> {
+    interaction.defer(context).await?;
+
+    let api_id = PokemonApiId(application.species_api_id as u16);
+    let Some(pokemon) = data.game.base_data.pokemon_by_api_id.get(&api_id) else {
+        return send_followup(
+            context,
+            interaction,
+            format!("Unable to find a pokemon with the api id {:?}!", api_id),
+        )
+        .await;
+    };
+
+    let message = ChannelId::new(application.channel_id as u64)
+        .send_message(
+            context,
+            CreateMessage::new().content(
+                "[Placeholder. This should get replaced or deleted within a couple seconds.]",
+            ),
+        )
+        .await?;
+
+    let new_character = NewCharacter {
+        user_id: application.user_id,
+        name: application.name.clone(),
+        pokemon,
+        is_shiny: application.is_shiny,
+        gender: application.gender(),
+        exp: DEFAULT_STARTING_EXPERIENCE,
+        money: DEFAULT_STARTING_MONEY,
+    };
+    let rules = get_progression_rules(&data.database, application.guild_id).await?;
+    let reviewer_id = interaction.user.id.get() as i64;
+    let mut transaction = data.database.begin().await?;
+    if let Err(e) = change_application_status(
+        &mut transaction,
+        application.id,
+        CharacterApplicationStatus::Pending,
+        CharacterApplicationStatus::Approved,
+        reviewer_id,
+        None,
+    )
+    .await
+    {
+        message.delete(context).await?;
+        return send_followup(context, interaction, e.to_string()).await;
+    }
+
+    let character_id = match insert_new_character(
+        &mut *transaction,
+        &data.game.base_data,
+        application.guild_id,
+        message.channel_id.get() as i64,
+        message.id.get() as i64,
+        &rules,
+        &new_character,
+    )
+    .await
+    {
+        Ok(character_id) => character_id,
+        Err(_) => {
+            message.delete(context).await?;
+            return send_followup(
+                context,
+                interaction,
+                "Something went wrong! Does a character with this name already exist for this specific player?",
+            )
+            .await;
+        }
+    };
+    sqlx::query!(
+        "UPDATE character_application SET character_id = ? WHERE id = ?",
+        character_id,
+        application.id
+    )
+    .execute(&mut *transaction)
+    .await?;
+    transaction.commit().await?;
+
+    let emoji_results = emoji::create_missing_guild_emojis(
+        context,
+        &data.database,
+        GuildId::new(application.guild_id as u64),
+        pokemon,
+        &new_character.gender,
+        new_character.is_shiny,
+    )
+    .await;
+    for result in emoji_results {
+        if let Err(e) = result {
+            send_followup(context, interaction, e).await?;
+        }
+    }
+
+    update_review_message(context, data, application.id).await?;
+
+    update_character_post(context, &data.database, game_data, character_id).await;
+    data.cache.update_character_names(&data.database).await;
+    log_action(
+        &ActionType::Initialization,
+        LogActionArguments::triggered_by_interaction(context, &data.database, interaction),
+        &format!(
+            "Initialized character {} ({}) with {} {} and {} exp from application #{}.",
+            new_character.name,
+            pokemon.name,
+            new_character.money,
+            emoji::POKE_COIN,
+            new_character.exp,
+            application.id
+        ),
+    )
+    .await?;
+
+    notify_player(
+        context,
+        &application,
+        format!(
+            "your character application for **{}** has been approved! {}",
+            application.name,
+            emoji::PARTY_POPPER
+        ),
+    )
+    .await
+}
+
+/// Persists the new status and updates the review message, removing its buttons.
+async fn finish_review(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+    application: &CharacterApplication,
+    status: CharacterApplicationStatus,
+    note: Option<&str>,
+) -> Result<(), Error> {
+    let reviewer_id = interaction.user.id.get() as i64;
+    let mut transaction = data.database.begin().await?;
+    change_application_status(
+        &mut transaction,
+        application.id,
+        CharacterApplicationStatus::Pending,
+        status,
+        reviewer_id,
+        note,
+    )
+    .await?;
+    transaction.commit().await?;
+
+    update_review_message(context, data, application.id).await
+}
+
+/// Shows the current status and history on the review message, removing its buttons.
+async fn update_review_message(
+    context: &Context,
+    data: &Data,
+    application_id: i64,
+) -> Result<(), Error> {
+    let application = get_character_application(&data.database, application_id)
+        .await?
+        .expect("Application should still exist!");
+    let history = get_status_history(&data.database, application.id).await?;
+    let pokemon = data
+        .game
+        .base_data
+        .pokemon_by_api_id
+        .get(&PokemonApiId(application.species_api_id as u16));
+
+    if let (Some(channel_id), Some(message_id)) = (
+        application.review_message_channel_id,
+        application.review_message_id,
+    ) {
+        ChannelId::new(channel_id as u64)
+            .edit_message(
+                context,
+                MessageId::new(message_id as u64),
+                EditMessage::new()
+                    .content(build_review_message(&application, pokemon, &history))
+                    .components(Vec::new()),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn notify_player(
+    context: &Context,
+    application: &CharacterApplication,
+    message: String,
+) -> Result<(), Error> {
+    let user = UserId::new(application.user_id as u64);
+    ChannelId::new(application.channel_id as u64)
+        .send_message(
+            context,
+            CreateMessage::new()
+                .content(format!("{}, {}", user.mention(), message))
+                .allowed_mentions(CreateAllowedMentions::default().users(vec![user])),
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn send_followup(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    content: impl Into<String>,
+) -> Result<(), Error> {
+    interaction
+        .create_followup(
+            context,
+            CreateInteractionResponseFollowup::new()
+                .content(content)
+                .ephemeral(true),
+        )
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,218 @@
+use crate::Error;
+use crate::events::{send_error, update_character_post};
+use crate::shared::character_moves;
+use crate::shared::data::Data;
+use crate::shared::game_data::GameData;
+use serenity::all::{
+    ComponentInteraction, ComponentInteractionDataKind, CreateInteractionResponse,
+    CreateInteractionResponseMessage,
+};
+use serenity::client::Context;
+use std::str::FromStr;
+
+/// Opens the move editor for a character.
+pub async fn initialize(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+    game_data: &GameData,
+    args: Vec<&str>,
+) -> Result<(), Error> {
+    let Some(character_id) = args.first() else {
+        return send_error(
+            &interaction,
+            context,
+            "Are you trying to do anything cheesy?",
+        )
+        .await;
+    };
+    let character_id = i64::from_str(character_id)?;
+
+    let user_id = interaction.user.id.get() as i64;
+    let edit_data =
+        character_moves::get_move_edit_data(&data.database, game_data, character_id).await?;
+    if edit_data.user_id != user_id {
+        return send_error(
+            &interaction,
+            context,
+            "You don't seem to own this character. No touchies! *hiss*",
+        )
+        .await;
+    }
+
+    let (content, components) =
+        character_moves::create_move_edit_message(context, data, game_data, character_id, 0)
+            .await?;
+    interaction
+        .create_response(
+            context,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .ephemeral(true)
+                    .content(content)
+                    .components(components),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Switches the move editor to the screen containing the given page.
+pub async fn change_page(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+    game_data: &GameData,
+    args: Vec<&str>,
+) -> Result<(), Error> {
+    if args.len() < 2 {
+        return send_error(
+            &interaction,
+            context,
+            "Are you trying to do anything cheesy?",
+        )
+        .await;
+    }
+    let character_id = i64::from_str(args[0])?;
+    let page = usize::from_str(args[1])?;
+
+    let user_id = interaction.user.id.get() as i64;
+    let edit_data =
+        character_moves::get_move_edit_data(&data.database, game_data, character_id).await?;
+    if edit_data.user_id != user_id {
+        return send_error(
+            &interaction,
+            context,
+            "You don't seem to own this character. No touchies! *hiss*",
+        )
+        .await;
+    }
+
+    let (content, components) =
+        character_moves::create_move_edit_message(context, data, game_data, character_id, page)
+            .await?;
+    interaction
+        .create_response(
+            context,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .components(components),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Applies the selection of one of the move editor's select menus.
+pub async fn handle_move_selection(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+    game_data: &GameData,
+    args: Vec<&str>,
+) -> Result<(), Error> {
+    if args.len() < 2 {
+        return send_error(
+            &interaction,
+            context,
+            "Are you trying to do anything cheesy?",
+        )
+        .await;
+    }
+    let character_id = i64::from_str(args[0])?;
+    let page = usize::from_str(args[1])?;
+
+    let selected_values = match &interaction.data.kind {
+        ComponentInteractionDataKind::StringSelect { values } => values.clone(),
+        _ => Vec::new(),
+    };
+
+    let user_id = interaction.user.id.get() as i64;
+    let edit_data =
+        character_moves::get_move_edit_data(&data.database, game_data, character_id).await?;
+    if edit_data.user_id != user_id {
+        return send_error(
+            &interaction,
+            context,
+            "You don't seem to own this character. No touchies! *hiss*",
+        )
+        .await;
+    }
+
+    let page_moves = edit_data.get_page(page);
+    if selected_values
+        .iter()
+        .any(|x| !page_moves.iter().any(|y| y == x))
+    {
+        return send_error(
+            &interaction,
+            context,
+            "The move selection seems to be outdated. Please open the editor again!",
+        )
+        .await;
+    }
+
+    let is_on_page = |move_name: &String| {
+        page_moves
+            .iter()
+            .any(|x| x.to_lowercase() == move_name.to_lowercase())
+    };
+    let remaining_move_count = edit_data
+        .known_moves
+        .iter()
+        .filter(|x| !is_on_page(x))
+        .count();
+    let new_move_count = remaining_move_count + selected_values.len();
+    if new_move_count as i64 > edit_data.move_limit {
+        return send_error(
+            &interaction,
+            context,
+            &format!(
+                "{} can only use {} moves at once, but this would result in {}. Deselect something else first!",
+                edit_data.name, edit_data.move_limit, new_move_count
+            ),
+        )
+        .await;
+    }
+
+    let mut transaction = data.database.begin().await?;
+    for move_name in edit_data.known_moves.iter().filter(|x| is_on_page(x)) {
+        sqlx::query!(
+            "DELETE FROM character_move WHERE character_id = ? AND move_name = ?",
+            character_id,
+            move_name
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+    for move_name in &selected_values {
+        sqlx::query!(
+            "INSERT INTO character_move (character_id, move_name) VALUES (?, ?)",
+            character_id,
+            move_name
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+    transaction.commit().await?;
+
+    let (content, components) =
+        character_moves::create_move_edit_message(context, data, game_data, character_id, page)
+            .await?;
+    interaction
+        .create_response(
+            context,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .components(components),
+            ),
+        )
+        .await?;
+
+    update_character_post(context, &data.database, game_data, character_id).await;
+    Ok(())
+}
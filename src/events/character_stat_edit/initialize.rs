@@ -4,9 +4,11 @@ use crate::events::character_stat_edit::{
     create_stat_edit_overview_message, reset_stat_edit_values, StatType,
 };
 use crate::events::send_error;
+use crate::shared::character_skills;
 use crate::shared::character_stats::GenericCharacterStats;
 use crate::shared::enums::MysteryDungeonRank;
 use crate::shared::game_data::{GameData, PokemonApiId};
+use crate::shared::guild_settings::get_progression_rules;
 use crate::shared::utility::level_calculations;
 use crate::Error;
 use serenity::all::{ComponentInteraction, Context, CreateInteractionResponse};
@@ -22,6 +24,7 @@ pub async fn initialize(
     match args.remove(0) {
         "combat" => initialize_combat(ctx, interaction, database, game_data, args).await,
         "social" => initialize_social(ctx, interaction, database, game_data, args).await,
+        "skills" => initialize_skills(ctx, interaction, database, game_data, args).await,
         &_ => send_error(&interaction, ctx, "Are you trying to do anything cheesy?").await,
     }
 }
@@ -38,7 +41,7 @@ async fn initialize_combat(
     if let Some(character_id) = args.first() {
         let character_id = i64::from_str(character_id)?;
         let record = sqlx::query!(
-            "SELECT experience, species_api_id, species_override_for_stats,\
+            "SELECT guild_id, experience, species_api_id, species_override_for_stats,\
                       stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight 
                 FROM character WHERE id = ? AND user_id = ? \
                 ORDER BY rowid \
@@ -51,9 +54,12 @@ async fn initialize_combat(
 
         return match record {
             Ok(record) => {
-                let level = level_calculations::calculate_level_from_experience(record.experience);
-                let experience = record.experience % 100;
-                let rank = MysteryDungeonRank::from_level(level as u8);
+                let rules = get_progression_rules(database, record.guild_id).await?;
+                let level =
+                    level_calculations::calculate_level_from_experience(&rules, record.experience);
+                let experience =
+                    level_calculations::calculate_current_experience(&rules, record.experience);
+                let rank = MysteryDungeonRank::from_level(level);
                 let pokemon = game_data
                     .pokemon_by_api_id
                     .get(&PokemonApiId(
@@ -66,6 +72,7 @@ async fn initialize_combat(
 
                 let pokemon_evolution_form_for_stats =
                     level_calculations::get_usual_evolution_stage_for_level(
+                        &rules,
                         level,
                         pokemon,
                         game_data,
@@ -80,8 +87,9 @@ async fn initialize_combat(
                     record.stat_insight,
                 );
 
-                let remaining_points = level_calculations::calculate_available_combat_points(level)
-                    - combat_stats.calculate_invested_stat_points();
+                let remaining_points =
+                    level_calculations::calculate_available_combat_points(&rules, level)
+                        - combat_stats.calculate_invested_stat_points(&rules);
 
                 if remaining_points <= 0 {
                     return send_error(
@@ -137,7 +145,7 @@ async fn initialize_social(
     if let Some(character_id) = args.first() {
         let character_id = i64::from_str(character_id)?;
         let record = sqlx::query!(
-            "SELECT experience, species_api_id, species_override_for_stats, \
+            "SELECT guild_id, experience, species_api_id, species_override_for_stats, \
                     stat_tough, stat_cool, stat_beauty, stat_cute, stat_clever
                 FROM character WHERE id = ? AND user_id = ? \
                 ORDER BY rowid \
@@ -150,9 +158,12 @@ async fn initialize_social(
 
         return match record {
             Ok(record) => {
-                let level = level_calculations::calculate_level_from_experience(record.experience);
-                let experience = record.experience % 100;
-                let rank = MysteryDungeonRank::from_level(level as u8);
+                let rules = get_progression_rules(database, record.guild_id).await?;
+                let level =
+                    level_calculations::calculate_level_from_experience(&rules, record.experience);
+                let experience =
+                    level_calculations::calculate_current_experience(&rules, record.experience);
+                let rank = MysteryDungeonRank::from_level(level);
                 let pokemon = game_data
                     .pokemon_by_api_id
                     .get(&PokemonApiId(
@@ -165,6 +176,7 @@ async fn initialize_social(
 
                 let pokemon_evolution_form_for_stats =
                     level_calculations::get_usual_evolution_stage_for_level(
+                        &rules,
                         level,
                         pokemon,
                         game_data,
@@ -178,8 +190,8 @@ async fn initialize_social(
                     record.stat_clever,
                 );
 
-                let remaining_points = rank.social_stat_points() as i64
-                    - social_stats.calculate_invested_stat_points();
+                let remaining_points = rules.social_stat_points(&rank)
+                    - social_stats.calculate_invested_stat_points(&rules);
 
                 if remaining_points <= 0 {
                     return send_error(
@@ -223,3 +235,76 @@ async fn initialize_social(
 
     send_error(&interaction, ctx, "Are you trying to do anything cheesy?").await
 }
+
+async fn initialize_skills(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    database: &Pool<Sqlite>,
+    game_data: &GameData,
+    args: Vec<&str>,
+) -> Result<(), Error> {
+    let user_id = interaction.user.id.get() as i64;
+
+    if let Some(character_id) = args.first() {
+        let character_id = i64::from_str(character_id)?;
+        let record = sqlx::query!(
+            "SELECT guild_id, experience FROM character WHERE id = ? AND user_id = ?",
+            character_id,
+            user_id
+        )
+        .fetch_one(database)
+        .await;
+
+        return match record {
+            Ok(record) => {
+                let rules = get_progression_rules(database, record.guild_id).await?;
+                let level =
+                    level_calculations::calculate_level_from_experience(&rules, record.experience);
+                let rank = MysteryDungeonRank::from_level(level);
+                let skills = character_skills::get_character_skills(database, character_id).await?;
+
+                let remaining_points =
+                    rank.skill_points() as i64 - skills.calculate_invested_skill_points();
+
+                if remaining_points <= 0 {
+                    return send_error(
+                        &interaction,
+                        ctx,
+                        "This character doesn't seem to have any remaining skill points.",
+                    )
+                    .await;
+                }
+
+                reset_stat_edit_values(database, character_id).await;
+                let _ = interaction
+                    .create_response(
+                        ctx,
+                        CreateInteractionResponse::Message(
+                            create_stat_edit_overview_message(
+                                ctx,
+                                database,
+                                game_data,
+                                character_id,
+                                StatType::Skills,
+                            )
+                            .await
+                            .into(),
+                        ),
+                    )
+                    .await;
+
+                Ok(())
+            }
+            _ => {
+                send_error(
+                    &interaction,
+                    ctx,
+                    "You don't seem to own this character. No touchies! *hiss*",
+                )
+                .await
+            }
+        };
+    }
+
+    send_error(&interaction, ctx, "Are you trying to do anything cheesy?").await
+}
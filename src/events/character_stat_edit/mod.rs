@@ -1,17 +1,22 @@
 use crate::events::send_error;
+use crate::shared::character_skills::CharacterSkills;
 use crate::shared::character_stats::GenericCharacterStats;
-use crate::shared::emoji;
-use crate::shared::enums::{Gender, MysteryDungeonRank};
+use crate::shared::enums::{Gender, MysteryDungeonRank, Skill};
+use crate::shared::{character_skills, emoji};
 use crate::shared::game_data::{GameData, PokemonApiId};
+use crate::shared::guild_settings::{get_progression_rules, ProgressionRules};
 use crate::shared::utility::level_calculations;
 use crate::Error;
 use serenity::all::{
-    ButtonStyle, ComponentInteraction, CreateActionRow, CreateInteractionResponseMessage,
-    EditInteractionResponse, EditMessage, ReactionType,
+    ButtonStyle, ComponentInteraction, ComponentInteractionDataKind, CreateActionRow,
+    CreateInteractionResponseMessage, CreateSelectMenu, CreateSelectMenuKind,
+    CreateSelectMenuOption, EditInteractionResponse, EditMessage, ReactionType,
 };
 use serenity::builder::CreateButton;
 use serenity::client::Context;
 use sqlx::{Pool, Sqlite};
+use std::str::FromStr;
+use strum::IntoEnumIterator;
 
 mod initialize;
 mod stat_edit;
@@ -30,10 +35,42 @@ pub async fn handle_character_editor_command(
     }
 }
 
+/// Applies the selection of one of the skill editor's select menus.
+pub async fn handle_skill_selection(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    database: &Pool<Sqlite>,
+    game_data: &GameData,
+    args: Vec<&str>,
+) -> Result<(), Error> {
+    let selected_value = match &interaction.data.kind {
+        ComponentInteractionDataKind::StringSelect { values } => values.first(),
+        _ => None,
+    };
+
+    let (Some(character_id), Some(operation), Some(selected_value)) =
+        (args.first(), args.get(1), selected_value)
+    else {
+        return send_error(&interaction, context, "Are you trying to do anything cheesy?").await;
+    };
+
+    let character_id = i64::from_str(character_id)?;
+    let Ok(skill) = Skill::from_str(selected_value) else {
+        return send_error(&interaction, context, "Are you trying to do anything cheesy?").await;
+    };
+
+    match *operation {
+        "add" => stat_edit::edit_skill(context, interaction, database, game_data, character_id, skill, 1).await,
+        "subtract" => stat_edit::edit_skill(context, interaction, database, game_data, character_id, skill, -1).await,
+        &_ => send_error(&interaction, context, "Are you trying to do anything cheesy?").await,
+    }
+}
+
 #[derive(PartialOrd, PartialEq)]
 enum StatType {
     Combat,
     Social,
+    Skills,
 }
 
 async fn reset_stat_edit_values(database: &Pool<Sqlite>, character_id: i64) {
@@ -54,6 +91,13 @@ WHERE id = ?",
     )
     .execute(database)
     .await;
+
+    let _ = sqlx::query!(
+        "UPDATE character_skill SET edit_rank = rank WHERE character_id = ?",
+        character_id
+    )
+    .execute(database)
+    .await;
 }
 
 fn create_combat_buttons(character_id: i64) -> Vec<CreateActionRow> {
@@ -154,23 +198,67 @@ fn create_social_buttons(character_id: i64) -> Vec<CreateActionRow> {
     ]
 }
 
+fn create_skill_components(character_id: i64) -> Vec<CreateActionRow> {
+    let options = || {
+        Skill::iter()
+            .map(|x| {
+                CreateSelectMenuOption::new(x.to_string(), x.to_string())
+                    .description(x.category())
+            })
+            .collect::<Vec<CreateSelectMenuOption>>()
+    };
+
+    vec![
+        CreateActionRow::SelectMenu(
+            CreateSelectMenu::new(
+                format!("ce-skill_{}_add", character_id),
+                CreateSelectMenuKind::String { options: options() },
+            )
+            .placeholder("Increase a skill..."),
+        ),
+        CreateActionRow::SelectMenu(
+            CreateSelectMenu::new(
+                format!("ce-skill_{}_subtract", character_id),
+                CreateSelectMenuKind::String { options: options() },
+            )
+            .placeholder("Decrease a skill..."),
+        ),
+        CreateActionRow::Buttons(vec![
+            CreateButton::new(format!("ce_edit-stat_{}_apply-skills", character_id))
+                .label("Apply")
+                .emoji(ReactionType::Unicode(emoji::UNICODE_CHECK_MARK.to_string()))
+                .style(ButtonStyle::Primary),
+            CreateButton::new(format!("ce_edit-stat_{}_cancel", character_id))
+                .label("Cancel")
+                .emoji(ReactionType::Unicode(emoji::UNICODE_CROSS_MARK.to_string()))
+                .style(ButtonStyle::Secondary),
+        ]),
+    ]
+}
+
 struct CharacterDataForStatEditing {
     id: i64,
     name: String,
     emoji: String,
     level: i64,
     rank: MysteryDungeonRank,
+    rules: ProgressionRules,
     combat_stats: GenericCharacterStats,
     social_stats: GenericCharacterStats,
+    skills: CharacterSkills,
 }
 
 impl CharacterDataForStatEditing {
     pub fn remaining_combat_points(&self) -> i64 {
-        level_calculations::calculate_available_combat_points(self.level)
-            - self.combat_stats.calculate_invested_stat_points()
+        level_calculations::calculate_available_combat_points(&self.rules, self.level)
+            - self.combat_stats.calculate_invested_stat_points(&self.rules)
     }
     pub fn remaining_social_points(&self) -> i64 {
-        self.rank.social_stat_points() as i64 - self.social_stats.calculate_invested_stat_points()
+        self.rules.social_stat_points(&self.rank)
+            - self.social_stats.calculate_invested_stat_points(&self.rules)
+    }
+    pub fn remaining_skill_points(&self) -> i64 {
+        self.rank.skill_points() as i64 - self.skills.calculate_invested_skill_points()
     }
 }
 
@@ -196,8 +284,11 @@ async fn get_character_data_for_edit(
         .await
         .unwrap();
 
-    let level = level_calculations::calculate_level_from_experience(record.experience);
-    let rank = MysteryDungeonRank::from_level(level as u8);
+    let rules = get_progression_rules(database, record.guild_id)
+        .await
+        .unwrap_or_default();
+    let level = level_calculations::calculate_level_from_experience(&rules, record.experience);
+    let rank = MysteryDungeonRank::from_level(level);
     let pokemon = game_data
         .pokemon_by_api_id
         .get(&PokemonApiId(
@@ -220,6 +311,7 @@ async fn get_character_data_for_edit(
     .unwrap_or(format!("[{}]", pokemon.name));
 
     let pokemon_evolution_form_for_stats = level_calculations::get_usual_evolution_stage_for_level(
+        &rules,
         level,
         pokemon,
         game_data,
@@ -252,14 +344,20 @@ async fn get_character_data_for_edit(
         record.stat_clever,
     );
 
+    let skills = character_skills::get_character_skill_edits(database, character_id)
+        .await
+        .unwrap_or_else(|_| CharacterSkills::default());
+
     CharacterDataForStatEditing {
         name: record.name,
         id: character_id,
         emoji,
         level,
         rank,
+        rules,
         combat_stats,
         social_stats,
+        skills,
     }
 }
 
@@ -304,6 +402,25 @@ async fn create_stat_edit_overview_message(
     let character_data =
         get_character_data_for_edit(context, database, game_data, character_id).await;
 
+    if stat_type == StatType::Skills {
+        let remaining_points = character_data.remaining_skill_points();
+        return MessageContent {
+            content: format!(
+                "### {}{}\n```\n{}```\n{} Remaining Points. At {}, skills can go up to rank {}.",
+                character_data.emoji,
+                character_data.name,
+                character_data
+                    .skills
+                    .build_string(character_data.rank.max_skill_rank() as i64),
+                remaining_points,
+                character_data.rank.name_without_emoji(),
+                character_data.rank.max_skill_rank(),
+            ),
+            ephemeral: true,
+            components: create_skill_components(character_id),
+        };
+    }
+
     let (stats, remaining_points) = match stat_type {
         StatType::Combat => {
             let combat_stats = &character_data.combat_stats;
@@ -317,12 +434,16 @@ async fn create_stat_edit_overview_message(
 
             (social_stats, remaining_points)
         }
+        StatType::Skills => unreachable!("Skills are handled above."),
     };
 
     let limit_break_substring = if stats.is_any_stat_at_or_above_max() {
         format!(
             "\nLimit breaking would cost you {}.",
-            level_calculations::calculate_next_limit_break_cost(stats.count_limit_breaks())
+            level_calculations::calculate_next_limit_break_cost(
+                &character_data.rules,
+                stats.count_limit_breaks()
+            )
         )
     } else {
         String::new()
@@ -350,6 +471,7 @@ async fn create_stat_edit_overview_message(
         components: match stat_type {
             StatType::Combat => create_combat_buttons(character_id),
             StatType::Social => create_social_buttons(character_id),
+            StatType::Skills => create_skill_components(character_id),
         },
     }
 }
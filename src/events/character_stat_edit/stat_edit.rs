@@ -5,7 +5,9 @@ use crate::events::character_stat_edit::{
     StatType,
 };
 use crate::events::{send_error, update_character_post};
+use crate::shared::character_skills;
 use crate::shared::character_stats::SingleCharacterStatType;
+use crate::shared::enums::Skill;
 use crate::shared::game_data::GameData;
 use crate::shared::utility::level_calculations;
 use crate::Error;
@@ -27,6 +29,7 @@ pub async fn handle_edit_stat_request(
         "subtract" => edit_stat(ctx, interaction, database, game_data, character_id, args, -1).await,
         "apply-combat" => apply_combat_stats(ctx, interaction, database, game_data, character_id).await,
         "apply-social" => apply_social_stats(ctx, interaction, database, game_data, character_id).await,
+        "apply-skills" => apply_skills(ctx, interaction, database, game_data, character_id).await,
         "cancel" => cancel(ctx, interaction).await,
         &_ => send_error(&interaction, ctx, "Are you trying to do anything cheesy?").await,
     }
@@ -105,6 +108,35 @@ WHERE id = ?",
     Ok(())
 }
 
+async fn apply_skills(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    database: &Pool<Sqlite>,
+    game_data: &GameData,
+    character_id: i64,
+) -> Result<(), Error> {
+    let deferred_interaction = interaction.defer(ctx);
+    let _ = sqlx::query!(
+        "UPDATE character_skill SET rank = edit_rank WHERE character_id = ?",
+        character_id
+    )
+    .execute(database)
+    .await;
+
+    let _ = deferred_interaction.await;
+    let _ = interaction
+        .edit_response(
+            ctx,
+            EditInteractionResponse::new()
+                .content("Successfully applied your skills.")
+                .components(Vec::new()),
+        )
+        .await;
+
+    update_character_post(ctx, database, game_data, character_id).await;
+    Ok(())
+}
+
 async fn cancel(ctx: &Context, interaction: &ComponentInteraction) -> Result<(), Error> {
     let _ = interaction.defer(ctx).await;
     let _ = interaction
@@ -184,7 +216,7 @@ async fn edit_specific_stat(
         }
 
         let points_required_for_limit_break =
-            level_calculations::calculate_next_limit_break_cost(limit_break_count);
+            level_calculations::calculate_next_limit_break_cost(&character.rules, limit_break_count);
         if edited_stat.current + 1 > edited_stat.species_max
             && remaining_points < points_required_for_limit_break
         {
@@ -242,6 +274,75 @@ async fn edit_specific_stat(
     Ok(())
 }
 
+pub async fn edit_skill(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    database: &Pool<Sqlite>,
+    game_data: &GameData,
+    character_id: i64,
+    skill: Skill,
+    amount: i64,
+) -> Result<(), Error> {
+    let deferred_interaction = interaction.defer(ctx);
+    let character = get_character_data_for_edit(ctx, database, game_data, character_id).await;
+    let current_rank = character.skills.get(skill);
+
+    if amount > 0 {
+        if character.remaining_skill_points() <= 0 {
+            return send_error(
+                &interaction,
+                ctx,
+                "Seems like you don't have any remaining skill points!",
+            )
+            .await;
+        }
+
+        let max_rank = character.rank.max_skill_rank() as i64;
+        if current_rank + amount > max_rank {
+            return send_error(
+                &interaction,
+                ctx,
+                &format!(
+                    "Unable to raise {} any further. At {}, skills can only go up to rank {}.",
+                    skill,
+                    character.rank.name_without_emoji(),
+                    max_rank
+                ),
+            )
+            .await;
+        }
+    } else {
+        let applied_rank = character_skills::get_character_skills(database, character_id)
+            .await?
+            .get(skill);
+        if current_rank + amount < applied_rank {
+            return send_error(
+                &interaction,
+                ctx,
+                &format!("Unable to reduce your {} any further. You cannot remove skill points which you've previously assigned.", skill),
+            )
+            .await;
+        }
+    }
+
+    character_skills::set_skill_edit_rank(database, character_id, skill, current_rank + amount)
+        .await?;
+
+    let edit_message = create_stat_edit_overview_message(
+        ctx,
+        database,
+        game_data,
+        character_id,
+        StatType::Skills,
+    )
+    .await;
+
+    let _ = deferred_interaction.await;
+    let _ = interaction.edit_response(ctx, edit_message.into()).await;
+
+    Ok(())
+}
+
 fn get_edit_string(stat: SingleCharacterStatType) -> String {
     match stat {
         SingleCharacterStatType::Strength => String::from("stat_edit_strength"),
@@ -0,0 +1,150 @@
+use crate::Error;
+use crate::events::{send_error, update_character_post};
+use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
+use crate::shared::data::Data;
+use crate::shared::game_data::GameData;
+use serenity::all::{
+    ComponentInteraction, CreateInteractionResponse, CreateInteractionResponseMessage, Mentionable,
+    UserId,
+};
+use serenity::client::Context;
+use std::str::FromStr;
+
+/// Accepts or declines a character transfer started with /transfer_character.
+pub async fn handle_character_transfer(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+    game_data: &GameData,
+    args: Vec<&str>,
+) -> Result<(), Error> {
+    if args.len() != 4 {
+        return send_error(
+            &interaction,
+            context,
+            "Are you trying to do anything cheesy?",
+        )
+        .await;
+    }
+    let character_id = i64::from_str(args[0])?;
+    let old_owner_id = i64::from_str(args[1])?;
+    let new_owner_id = i64::from_str(args[2])?;
+    let presser_id = interaction.user.id.get() as i64;
+
+    let character = sqlx::query!(
+        "SELECT name, user_id FROM character WHERE id = ?",
+        character_id
+    )
+    .fetch_optional(&data.database)
+    .await?;
+    let Some(character) = character else {
+        return send_error(
+            &interaction,
+            context,
+            "That character doesn't seem to exist anymore.",
+        )
+        .await;
+    };
+
+    if args[3] == "decline" {
+        if presser_id != new_owner_id && presser_id != old_owner_id {
+            return send_error(
+                &interaction,
+                context,
+                "Only the involved players can cancel this transfer.",
+            )
+            .await;
+        }
+
+        return update_transfer_message(
+            context,
+            interaction,
+            format!("The transfer of **{}** has been cancelled.", character.name),
+        )
+        .await;
+    }
+
+    if presser_id != new_owner_id {
+        return send_error(
+            &interaction,
+            context,
+            "Only the recipient can accept this transfer!",
+        )
+        .await;
+    }
+    if character.user_id != old_owner_id {
+        return update_transfer_message(
+            context,
+            interaction,
+            format!(
+                "**{}** changed hands since this transfer was started, so it has been cancelled.",
+                character.name
+            ),
+        )
+        .await;
+    }
+
+    let result = sqlx::query!(
+        "UPDATE character SET user_id = ? WHERE id = ? AND user_id = ?",
+        new_owner_id,
+        character_id,
+        old_owner_id
+    )
+    .execute(&data.database)
+    .await;
+    let Ok(result) = result else {
+        return send_error(
+            &interaction,
+            context,
+            "Something went wrong! Do you already own a character with this name?",
+        )
+        .await;
+    };
+    if result.rows_affected() != 1 {
+        return update_transfer_message(
+            context,
+            interaction,
+            format!(
+                "**{}** changed hands while this transfer was being accepted, so it has been cancelled.",
+                character.name
+            ),
+        )
+        .await;
+    }
+
+    let message = format!(
+        "**{}** has been transferred from {} to {}.",
+        character.name,
+        UserId::new(old_owner_id as u64).mention(),
+        UserId::new(new_owner_id as u64).mention()
+    );
+    update_transfer_message(context, interaction, message.clone()).await?;
+
+    data.cache.update_character_names(&data.database).await;
+    update_character_post(context, &data.database, game_data, character_id).await;
+    log_action(
+        &ActionType::CharacterTransfer,
+        LogActionArguments::triggered_by_interaction(context, &data.database, interaction),
+        message,
+    )
+    .await
+}
+
+async fn update_transfer_message(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    content: String,
+) -> Result<(), Error> {
+    interaction
+        .create_response(
+            context,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .components(Vec::new()),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,242 @@
+use crate::Error;
+use crate::commands::is_user_admin_or_gm;
+use crate::events::{send_error, update_character_post, update_wallet_post};
+use crate::shared::action_log::{
+    ActionType, LedgerTarget, LogActionArguments, UNDO_TIMEOUT_IN_SECONDS, log_action,
+};
+use crate::shared::character_condition::change_character_condition;
+use crate::shared::data::Data;
+use crate::shared::game_data::GameData;
+use chrono::Utc;
+use serenity::all::{
+    ComponentInteraction, CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use serenity::client::Context;
+use std::borrow::Cow;
+use std::str::FromStr;
+
+struct UndoableLedgerEntry {
+    id: i64,
+    actor_id: Option<i64>,
+    character_id: Option<i64>,
+    wallet_id: Option<i64>,
+    field: Option<String>,
+    delta: Option<i64>,
+    timestamp: i64,
+    is_undone: bool,
+}
+
+/// Character fields which need to stay within the bounds of [change_character_condition].
+const CONDITION_FIELDS: [&str; 3] = ["hp_lost", "will_lost", "wounds"];
+
+/// Table and column names are inserted into queries directly, so only these (table, column) pairs can ever be undone.
+const UNDOABLE_FIELDS: [(&str, &str); 29] = [
+    ("character", "experience"),
+    ("character", "money"),
+    ("character", "battle_points"),
+    ("character", "total_spar_count"),
+    ("character", "weekly_spar_count"),
+    ("character", "total_new_player_combat_tutorial_count"),
+    ("character", "total_new_player_tour_count"),
+    ("character", "hp_lost"),
+    ("character", "will_lost"),
+    ("character", "wounds"),
+    ("character", "tera_unlocked_normal"),
+    ("character", "tera_unlocked_fighting"),
+    ("character", "tera_unlocked_flying"),
+    ("character", "tera_unlocked_poison"),
+    ("character", "tera_unlocked_ground"),
+    ("character", "tera_unlocked_rock"),
+    ("character", "tera_unlocked_bug"),
+    ("character", "tera_unlocked_ghost"),
+    ("character", "tera_unlocked_steel"),
+    ("character", "tera_unlocked_fire"),
+    ("character", "tera_unlocked_water"),
+    ("character", "tera_unlocked_grass"),
+    ("character", "tera_unlocked_electric"),
+    ("character", "tera_unlocked_psychic"),
+    ("character", "tera_unlocked_ice"),
+    ("character", "tera_unlocked_dragon"),
+    ("character", "tera_unlocked_dark"),
+    ("character", "tera_unlocked_fairy"),
+    ("wallet", "money"),
+];
+
+fn find_undoable_field(table: &str, field: &str) -> Option<(&'static str, &'static str)> {
+    UNDOABLE_FIELDS
+        .iter()
+        .find(|(x, y)| *x == table && *y == field)
+        .copied()
+}
+
+/// Reverts all changes a command made, as long as it's still recent enough.
+pub async fn undo(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+    game_data: &GameData,
+    args: Vec<&str>,
+) -> Result<(), Error> {
+    let Some(interaction_id) = args.first() else {
+        return send_error(
+            &interaction,
+            context,
+            "Are you trying to do anything cheesy?",
+        )
+        .await;
+    };
+    let interaction_id = i64::from_str(interaction_id)?;
+
+    let entries = sqlx::query_as!(
+        UndoableLedgerEntry,
+        "SELECT id, actor_id, character_id, wallet_id, field, delta, timestamp, is_undone \
+        FROM ledger_entry WHERE interaction_id = ? AND delta IS NOT NULL",
+        interaction_id
+    )
+    .fetch_all(&data.database)
+    .await?;
+
+    let Some(first_entry) = entries.first() else {
+        return send_error(&interaction, context, "There's nothing to undo here.").await;
+    };
+
+    let is_gm = interaction
+        .member
+        .as_ref()
+        .is_some_and(|x| is_user_admin_or_gm(Cow::Borrowed(x)));
+    if !is_gm && first_entry.actor_id != Some(interaction.user.id.get() as i64) {
+        return send_error(
+            &interaction,
+            context,
+            "Only the person who used the command or a GM can undo this.",
+        )
+        .await;
+    }
+
+    if entries.iter().any(|x| x.is_undone) {
+        return send_error(&interaction, context, "This has already been undone.").await;
+    }
+
+    if Utc::now().timestamp() - first_entry.timestamp > UNDO_TIMEOUT_IN_SECONDS {
+        return send_error(
+            &interaction,
+            context,
+            &format!(
+                "Undoing is only possible within {} minutes. You'll have to fix this manually.",
+                UNDO_TIMEOUT_IN_SECONDS / 60
+            ),
+        )
+        .await;
+    }
+
+    let mut log_arguments =
+        LogActionArguments::triggered_by_interaction(context, &data.database, interaction);
+    let mut transaction = data.database.begin().await?;
+    for entry in &entries {
+        let (Some(field), Some(delta)) = (&entry.field, entry.delta) else {
+            continue;
+        };
+        let (table, target_id) = match (entry.character_id, entry.wallet_id) {
+            (Some(character_id), _) => ("character", character_id),
+            (None, Some(wallet_id)) => ("wallet", wallet_id),
+            (None, None) => continue,
+        };
+        let Some((table, field)) = find_undoable_field(table, field) else {
+            return send_error(
+                &interaction,
+                context,
+                "Something went wrong while undoing this. Please fix it manually.",
+            )
+            .await;
+        };
+
+        // Condition values have an upper bound, so they get clamped instead of failing.
+        let applied_delta = if table == "character" && CONDITION_FIELDS.contains(&field) {
+            let (hp_lost, will_lost, wounds) = match field {
+                "hp_lost" => (-delta, 0, 0),
+                "will_lost" => (0, -delta, 0),
+                _ => (0, 0, -delta),
+            };
+            let change = change_character_condition(
+                &mut transaction,
+                game_data,
+                target_id,
+                hp_lost,
+                will_lost,
+                wounds,
+            )
+            .await?;
+            change.hp_lost + change.will_lost + change.wounds
+        } else {
+            let result = sqlx::query(&format!(
+                "UPDATE {} SET {} = {} - ? WHERE id = ? AND {} - ? >= 0",
+                table, field, field, field
+            ))
+            .bind(delta)
+            .bind(target_id)
+            .bind(delta)
+            .execute(&mut *transaction)
+            .await?;
+            if result.rows_affected() != 1 {
+                return send_error(
+                    &interaction,
+                    context,
+                    "Unable to undo this without something dropping below zero. Did someone already spend it?",
+                )
+                .await;
+            }
+            -delta
+        };
+
+        // Guards against two undo clicks happening at the same time.
+        let result = sqlx::query!(
+            "UPDATE ledger_entry SET is_undone = TRUE WHERE id = ? AND is_undone = FALSE",
+            entry.id
+        )
+        .execute(&mut *transaction)
+        .await?;
+        if result.rows_affected() != 1 {
+            return send_error(&interaction, context, "This has already been undone.").await;
+        }
+
+        log_arguments = match table {
+            "character" => log_arguments.with_character_change(target_id, field, applied_delta),
+            _ => log_arguments.with_wallet_change(target_id, field, applied_delta),
+        };
+    }
+    transaction.commit().await?;
+
+    interaction
+        .create_response(
+            context,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content(format!(
+                        "~~{}~~\n*Undone by {}.*",
+                        interaction.message.content, interaction.user
+                    ))
+                    .components(Vec::new()),
+            ),
+        )
+        .await?;
+
+    for change in &log_arguments.changes {
+        match change.target {
+            LedgerTarget::Character(id) => {
+                update_character_post(context, &data.database, game_data, id).await
+            }
+            LedgerTarget::Wallet(id) => update_wallet_post(context, &data.database, id).await,
+        }
+    }
+
+    log_action(
+        &ActionType::Undo,
+        log_arguments,
+        format!(
+            "Reverted {} change(s) from {}",
+            entries.len(),
+            interaction.message.link()
+        ),
+    )
+    .await
+}
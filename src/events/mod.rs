@@ -1,4 +1,5 @@
 use crate::Error;
+use crate::commands::wallet_commands::build_wallet_string;
 use crate::shared::data::Data;
 use crate::shared::game_data::GameData;
 use crate::shared::utility::error_handling;
@@ -15,13 +16,20 @@ use tokio::join;
 
 mod backups;
 mod button_interaction;
+mod character_application;
+mod character_move_edit;
 mod character_stat_edit;
+mod character_transfer;
 mod guild_member_removal;
 mod handle_emoji_reaction;
+mod ledger_undo;
 mod monthly_reset;
+mod quest_reminders;
 mod quests;
+mod recurring_quests;
 mod select_menu_interaction;
 mod status_messages;
+mod team_invite;
 mod weekly_reset;
 
 type FrameworkContext<'a> = poise::FrameworkContext<'a, Data, Error>;
@@ -92,6 +100,8 @@ pub async fn handle_events<'a>(
                 backups::start_backup_thread(context, framework.user_data),
                 weekly_reset::start_weekly_reset_thread(context, framework.user_data),
                 monthly_reset::start_monthly_reset_thread(context, framework.user_data),
+                quest_reminders::start_quest_reminder_thread(context, framework.user_data),
+                recurring_quests::start_recurring_quest_thread(context, framework.user_data),
                 status_messages::restart_message(context, framework.user_data),
                 framework
                     .user_data
@@ -274,6 +284,26 @@ async fn update_character_post<'a>(
     }
 }
 
+async fn update_wallet_post(ctx: &Context, database: &Pool<Sqlite>, wallet_id: i64) {
+    if let Some(result) = build_wallet_string(database, wallet_id).await {
+        let message = ctx
+            .http
+            .get_message(
+                ChannelId::from(result.stat_channel_id as u64),
+                MessageId::from(result.stat_message_id as u64),
+            )
+            .await;
+        if let Ok(mut message) = message
+            && let Err(e) = message
+                .edit(ctx, EditMessage::new().content(&result.message))
+                .await
+        {
+            handle_error_during_message_edit(ctx, e, message, result.message, None, result.name)
+                .await;
+        }
+    }
+}
+
 async fn handle_error_during_message_edit(
     ctx: &Context,
     e: serenity::Error,
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use chrono::Utc;
+use serenity::all::{CreateAllowedMentions, CreateMessage, Mentionable, UserId};
+use serenity::model::id::ChannelId;
+use serenity::prelude::Context;
+use sqlx::{Pool, Sqlite};
+
+use crate::Error;
+use crate::events::send_error_to_log_channel;
+use crate::shared::data::Data;
+use crate::shared::quest_waitlist::get_quest_participants;
+
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+const SECONDS_PER_HOUR: i64 = 60 * 60;
+const SECONDS_PER_DAY: i64 = 24 * SECONDS_PER_HOUR;
+
+/// Since the reminder state is persisted within the quest table, reminders which were due while the bot was offline
+/// will be sent once it's back up, as long as the quest hasn't started yet.
+pub async fn start_quest_reminder_thread(ctx: &Context, data: &Data) {
+    let ctx = Arc::new(ctx.clone());
+    if !data
+        .is_quest_reminder_thread_running
+        .load(Ordering::Relaxed)
+    {
+        let ctx_in_thread = Arc::clone(&ctx);
+        let database = data.database.clone();
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = send_due_reminders(&ctx_in_thread, &database).await {
+                    send_error_to_log_channel(&ctx_in_thread, e.to_string()).await;
+                }
+                tokio::time::sleep(CHECK_INTERVAL).await;
+            }
+        });
+
+        data.is_quest_reminder_thread_running
+            .swap(true, Ordering::Relaxed);
+    }
+}
+
+async fn send_due_reminders(ctx: &Arc<Context>, database: &Pool<Sqlite>) -> Result<(), Error> {
+    let now = Utc::now().timestamp();
+    let day_from_now = now + SECONDS_PER_DAY;
+    let quests = sqlx::query!(
+        "SELECT channel_id, scheduled_start_timestamp as \"scheduled_start_timestamp!: i64\", is_day_before_reminder_sent \
+        FROM quest \
+        WHERE completion_timestamp IS NULL AND scheduled_start_timestamp > ? AND scheduled_start_timestamp <= ? \
+        AND is_hour_before_reminder_sent = FALSE",
+        now,
+        day_from_now
+    )
+    .fetch_all(database)
+    .await?;
+
+    for quest in quests {
+        let is_within_the_hour = quest.scheduled_start_timestamp - now <= SECONDS_PER_HOUR;
+        if quest.is_day_before_reminder_sent && !is_within_the_hour {
+            continue;
+        }
+
+        sqlx::query!(
+            "UPDATE quest SET is_day_before_reminder_sent = TRUE, is_hour_before_reminder_sent = ? WHERE channel_id = ?",
+            is_within_the_hour,
+            quest.channel_id
+        )
+        .execute(database)
+        .await?;
+
+        let mut participants: Vec<UserId> = Vec::new();
+        for participant in get_quest_participants(database, quest.channel_id).await? {
+            let user_id = UserId::new(participant.user_id as u64);
+            if !participants.contains(&user_id) {
+                participants.push(user_id);
+            }
+        }
+        if participants.is_empty() {
+            continue;
+        }
+
+        let mentions = participants
+            .iter()
+            .map(|x| x.mention().to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        let _ = ChannelId::new(quest.channel_id as u64)
+            .send_message(
+                ctx,
+                CreateMessage::new()
+                    .content(format!(
+                        "⏰ {}\nReminder: This quest starts <t:{1}:R>, at <t:{1}:f>!",
+                        mentions, quest.scheduled_start_timestamp
+                    ))
+                    .allowed_mentions(CreateAllowedMentions::default().users(participants)),
+            )
+            .await;
+    }
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
 pub mod quest_add_random_participants;
 pub mod quest_list_all_participants;
+pub mod quest_poll;
 pub mod quest_sign_out;
 pub mod quest_sign_up;
+pub mod quest_weighted_random_draw;
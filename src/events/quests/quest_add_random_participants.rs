@@ -1,4 +1,7 @@
+use crate::events::quests::quest_weighted_random_draw;
 use crate::shared::data::Data;
+use crate::shared::enums::QuestParticipantSelectionMechanism;
+use crate::shared::quest_gm;
 use crate::shared::utility::quest_message_utils;
 use crate::Error;
 use rand::Rng;
@@ -15,6 +18,19 @@ pub async fn quest_add_random_participants(
     let user_id = interaction.user.id.get() as i64;
     let channel_id = interaction.channel_id.get() as i64;
 
+    let quest_record = sqlx::query!(
+        "SELECT participant_selection_mechanism FROM quest WHERE channel_id = ?",
+        channel_id
+    )
+    .fetch_one(&data.database)
+    .await?;
+    if quest_record.participant_selection_mechanism
+        == QuestParticipantSelectionMechanism::WeightedRandom as i64
+    {
+        return quest_weighted_random_draw::preview_weighted_draw(context, interaction, data)
+            .await;
+    }
+
     let execution_result = execute(data, user_id, channel_id).await;
     let was_error = execution_result.is_err();
     let result = execution_result.unwrap_or_else(|error| error.to_string());
@@ -31,22 +47,32 @@ pub async fn quest_add_random_participants(
         .await?;
 
     if !was_error {
-        quest_message_utils::update_quest_message(context, data, channel_id).await?;
+        quest_message_utils::update_quest_message(
+            context,
+            &data.database,
+            &data.game.base_data,
+            channel_id,
+        ).await?;
     }
 
     Ok(())
 }
 
-async fn execute(data: &Data, user_id: i64, channel_id: i64) -> Result<String, Error> {
+/// Ensures only the quest owner or a co-GM can draw, and returns how many spots are still open.
+pub async fn validate_random_draw(
+    data: &Data,
+    user_id: i64,
+    channel_id: i64,
+) -> Result<i64, Error> {
     let quest_record = sqlx::query!(
-        "SELECT creator_id, maximum_participant_count FROM quest WHERE channel_id = ?",
+        "SELECT maximum_participant_count FROM quest WHERE channel_id = ?",
         channel_id
     )
     .fetch_one(&data.database)
     .await?;
 
-    if quest_record.creator_id != user_id {
-        return Err(Error::from("Only the quest owner can do this, sorry!"));
+    if !quest_gm::is_quest_gm(&data.database, channel_id, user_id).await? {
+        return Err(Error::from("Only the quest GMs can do this, sorry!"));
     }
 
     let accepted_participants = sqlx::query!(
@@ -60,6 +86,12 @@ async fn execute(data: &Data, user_id: i64, channel_id: i64) -> Result<String, E
         return Err(Error::from("The quest is already full! If you want to add more participants, either add them manually or remove one of the already accepted players."));
     }
 
+    Ok(quest_record.maximum_participant_count - accepted_participants.count as i64)
+}
+
+async fn execute(data: &Data, user_id: i64, channel_id: i64) -> Result<String, Error> {
+    let open_spots = validate_random_draw(data, user_id, channel_id).await?;
+
     let mut floating_participants = sqlx::query!(
         "SELECT character.id as character_id, character.name as character_name, character.user_id as user_id
 FROM quest_signup
@@ -82,7 +114,7 @@ ORDER BY quest_signup.timestamp DESC",
     let mut chosen_character_ids = Vec::new();
     {
         let mut rng = rand::rng();
-        for _ in 0..quest_record.maximum_participant_count - accepted_participants.count as i64 {
+        for _ in 0..open_spots {
             if floating_participants.is_empty() {
                 break;
             }
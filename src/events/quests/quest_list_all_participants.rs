@@ -26,7 +26,8 @@ pub async fn quest_list_all_participants(
 
     let (text, _) = quest_message_utils::create_quest_participant_list(
         context,
-        data,
+        &data.database,
+        &data.game.base_data,
         channel_id,
         quest_record.maximum_participant_count,
         selection_mechanism,
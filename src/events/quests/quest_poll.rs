@@ -0,0 +1,190 @@
+use crate::Error;
+use crate::shared::data::Data;
+use crate::shared::quest_gm::is_quest_gm;
+use crate::shared::quest_poll::{
+    build_quest_poll_message, delete_quest_poll, get_accepted_user_ids, get_quest_poll_slots,
+    pick_best_slot,
+};
+use crate::shared::utility::quest_message_utils;
+use chrono::Utc;
+use serenity::all::{
+    ComponentInteraction, CreateAllowedMentions, CreateInteractionResponse,
+    CreateInteractionResponseMessage,
+};
+use serenity::client::Context;
+
+async fn respond_ephemeral(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    text: &str,
+) -> Result<(), Error> {
+    interaction
+        .create_response(
+            context,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .ephemeral(true)
+                    .content(text),
+            ),
+        )
+        .await?;
+    Ok(())
+}
+
+/// Toggles the availability of the user for the given slot.
+pub async fn quest_poll_vote(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+    args: Vec<&str>,
+) -> Result<(), Error> {
+    let user_id = interaction.user.id.get() as i64;
+    let channel_id = interaction.channel_id.get() as i64;
+    let start_timestamp: i64 = args
+        .first()
+        .and_then(|x| x.parse().ok())
+        .ok_or("Invalid poll button!")?;
+
+    let mut transaction = data.database.begin().await?;
+    let slot = sqlx::query!(
+        "SELECT quest.completion_timestamp FROM quest_poll_slot \
+        INNER JOIN quest ON quest_poll_slot.quest_id = quest.channel_id \
+        WHERE quest_poll_slot.quest_id = ? AND quest_poll_slot.start_timestamp = ?",
+        channel_id,
+        start_timestamp
+    )
+    .fetch_optional(&mut *transaction)
+    .await?;
+    match slot {
+        None => {
+            return respond_ephemeral(context, interaction, "This poll is no longer active!").await;
+        }
+        Some(slot) if slot.completion_timestamp.is_some() => {
+            return respond_ephemeral(
+                context,
+                interaction,
+                "This quest has already been completed!",
+            )
+            .await;
+        }
+        Some(_) => {}
+    }
+
+    let signup = sqlx::query!(
+        "SELECT COUNT(*) as count FROM quest_signup \
+        INNER JOIN character ON quest_signup.character_id = character.id \
+        WHERE quest_signup.quest_id = ? AND character.user_id = ?",
+        channel_id,
+        user_id
+    )
+    .fetch_one(&mut *transaction)
+    .await?;
+    if signup.count == 0 {
+        return respond_ephemeral(
+            context,
+            interaction,
+            "You need to sign up for this quest before you can vote!",
+        )
+        .await;
+    }
+
+    // Double clicks must not be able to insert the same vote twice, so the insert decides which way to toggle.
+    let added = sqlx::query!(
+        "INSERT INTO quest_poll_vote (quest_id, start_timestamp, user_id) VALUES (?, ?, ?) ON CONFLICT DO NOTHING",
+        channel_id,
+        start_timestamp,
+        user_id
+    )
+    .execute(&mut *transaction)
+    .await?;
+    if added.rows_affected() == 0 {
+        sqlx::query!(
+            "DELETE FROM quest_poll_vote WHERE quest_id = ? AND start_timestamp = ? AND user_id = ?",
+            channel_id,
+            start_timestamp,
+            user_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+    transaction.commit().await?;
+
+    let (text, components) = build_quest_poll_message(&data.database, channel_id).await?;
+    interaction
+        .create_response(
+            context,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content(text)
+                    .components(components)
+                    .allowed_mentions(CreateAllowedMentions::new()),
+            ),
+        )
+        .await?;
+    Ok(())
+}
+
+/// Sets the suggested slot as the quest's start time and closes the poll.
+pub async fn quest_poll_schedule(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+) -> Result<(), Error> {
+    let user_id = interaction.user.id.get() as i64;
+    let channel_id = interaction.channel_id.get() as i64;
+    if !is_quest_gm(&data.database, channel_id, user_id).await? {
+        return respond_ephemeral(
+            context,
+            interaction,
+            "Only the quest GMs can do this, sorry!",
+        )
+        .await;
+    }
+
+    let slots = get_quest_poll_slots(&data.database, channel_id).await?;
+    let accepted_user_ids = get_accepted_user_ids(&data.database, channel_id).await?;
+    let Some(best_slot) = pick_best_slot(&slots, &accepted_user_ids, Utc::now().timestamp())
+    else {
+        return respond_ephemeral(
+            context,
+            interaction,
+            "Nobody voted for any upcoming slot yet, so there's nothing to schedule!",
+        )
+        .await;
+    };
+    let start_timestamp = best_slot.start_timestamp;
+
+    let mut transaction = data.database.begin().await?;
+    sqlx::query!(
+        "UPDATE quest SET scheduled_start_timestamp = ?, is_day_before_reminder_sent = FALSE, is_hour_before_reminder_sent = FALSE \
+        WHERE channel_id = ?",
+        start_timestamp,
+        channel_id
+    )
+    .execute(&mut *transaction)
+    .await?;
+    delete_quest_poll(&mut transaction, channel_id).await?;
+    transaction.commit().await?;
+
+    interaction
+        .create_response(
+            context,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content(format!(
+                        "## 🗓️ The quest will start <t:{0}:F> (<t:{0}:R>)!\nParticipants will be reminded a day and an hour before.",
+                        start_timestamp
+                    ))
+                    .components(Vec::new()),
+            ),
+        )
+        .await?;
+
+    quest_message_utils::update_quest_message(
+        context,
+        &data.database,
+        &data.game.base_data,
+        channel_id,
+    )
+    .await
+}
@@ -1,4 +1,5 @@
 use crate::shared::data::Data;
+use crate::shared::quest_waitlist::{self, get_quest_participants};
 use crate::shared::utility::quest_message_utils;
 use crate::Error;
 use serenity::all::{
@@ -18,6 +19,7 @@ pub async fn quest_sign_out(
     let user_id = interaction.user.id.get() as i64;
     let channel_id = interaction.channel_id.get() as i64;
 
+    let previous_participants = get_quest_participants(&data.database, channel_id).await?;
     let rows_affected = execute_sign_out(data, guild_id, user_id, channel_id).await?;
 
     let text = if rows_affected > 0 {
@@ -39,7 +41,14 @@ pub async fn quest_sign_out(
         .await?;
 
     if rows_affected > 0 {
-        quest_message_utils::update_quest_message(context, data, channel_id).await?;
+        quest_message_utils::update_quest_message(
+            context,
+            &data.database,
+            &data.game.base_data,
+            channel_id,
+        ).await?;
+        quest_waitlist::handle_freed_spots(context, data, channel_id, &previous_participants)
+            .await?;
     }
 
     Ok(())
@@ -1,4 +1,6 @@
 use crate::shared::data::Data;
+use crate::shared::quest_restrictions::{get_signup_candidates, SignupCandidate};
+use crate::shared::quest_signup::{is_already_signed_up_error, persist_signup};
 use crate::shared::utility::{button_building, quest_message_utils};
 use crate::Error;
 use chrono::Utc;
@@ -27,19 +29,26 @@ pub async fn quest_sign_up(
     let user_id = interaction.user.id.get() as i64;
     let channel_id = interaction.channel_id.get() as i64;
 
-    let available_characters = sqlx::query!(
-        "SELECT id, name FROM character WHERE user_id = ? AND guild_id = ? AND is_retired = false",
-        user_id,
-        guild_id
-    )
-    .fetch_all(&data.database)
-    .await?;
+    let candidates = get_signup_candidates(data, guild_id, user_id, channel_id).await?;
+    let (available_characters, excluded_characters): (Vec<_>, Vec<_>) = candidates
+        .into_iter()
+        .partition(|x| x.ineligibility_reason.is_none());
 
     if args.len() == 2 {
         let character_id = i64::from_str(args[0])?;
         let timestamp = i64::from_str(args[1])?;
 
         if available_characters.iter().all(|x| x.id != character_id) {
+            if let Some(excluded) = excluded_characters.iter().find(|x| x.id == character_id) {
+                return respond(
+                    context,
+                    interaction,
+                    MessageType::UpdateMessage,
+                    build_exclusion_line(excluded),
+                )
+                .await;
+            }
+
             // TODO: Handle Invalid button input. That's a biiiig red flag!
             return Ok(());
         }
@@ -57,7 +66,7 @@ pub async fn quest_sign_up(
     }
 
     let timestamp = Utc::now().timestamp();
-    if available_characters.len() == 1 {
+    if available_characters.len() == 1 && excluded_characters.is_empty() {
         return process_signup(
             context,
             interaction,
@@ -70,6 +79,18 @@ pub async fn quest_sign_up(
         .await;
     }
 
+    let mut content = if available_characters.is_empty() && !excluded_characters.is_empty() {
+        String::from("None of your characters can join this quest:\n")
+    } else if excluded_characters.is_empty() {
+        String::from("Which character would you like to sign up?")
+    } else {
+        String::from("Which character would you like to sign up? These can't join:\n")
+    };
+    for excluded in &excluded_characters {
+        content.push_str(&build_exclusion_line(excluded));
+        content.push('\n');
+    }
+
     let character_buttons = available_characters
         .iter()
         .map(|x| {
@@ -90,7 +111,7 @@ pub async fn quest_sign_up(
             CreateInteractionResponse::Message(
                 CreateInteractionResponseMessage::new()
                     .ephemeral(true)
-                    .content("Which character would you like to sign up?")
+                    .content(content)
                     .components(character_buttons),
             ),
         )
@@ -114,10 +135,10 @@ async fn process_signup(
         timestamp
     };
 
-    let result = persist_signup(data, channel_id, character_id, timestamp).await;
+    let result = persist_signup(&data.database, channel_id, character_id, timestamp).await;
 
     let text = if let Some(error) = result.err() {
-        if error.contains("UNIQUE constraint failed") {
+        if is_already_signed_up_error(&error) {
             String::from("Seems like you are already signed up!")
         } else {
             error
@@ -126,6 +147,35 @@ async fn process_signup(
         String::from("Successfully signed up!")
     };
 
+    respond(context, interaction, response_type, text).await?;
+
+    quest_message_utils::update_quest_message(
+        context,
+        &data.database,
+        &data.game.base_data,
+        channel_id,
+    ).await?;
+
+    Ok(())
+}
+
+fn build_exclusion_line(character: &SignupCandidate) -> String {
+    format!(
+        "- **{}** {}",
+        character.name,
+        character
+            .ineligibility_reason
+            .as_deref()
+            .unwrap_or("can join")
+    )
+}
+
+async fn respond(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    response_type: MessageType,
+    text: String,
+) -> Result<(), Error> {
     let message = CreateInteractionResponseMessage::new()
         .ephemeral(true)
         .content(text)
@@ -142,89 +192,5 @@ async fn process_signup(
     }
     .await?;
 
-    quest_message_utils::update_quest_message(context, data, channel_id).await?;
-
     Ok(())
 }
-
-async fn persist_signup(
-    data: &Data,
-    channel_id: i64,
-    character_id: i64,
-    timestamp: i64,
-) -> Result<(), String> {
-    let result = sqlx::query!(
-        "INSERT INTO quest_signup (quest_id, character_id, timestamp) VALUES (?, ?, ?)",
-        channel_id,
-        character_id,
-        timestamp
-    )
-    .execute(&data.database)
-    .await;
-
-    match result {
-        Ok(result) => {
-            if result.rows_affected() > 0 {
-                Ok(())
-            } else {
-                Err(String::from("Unable to persist quest signup!"))
-            }
-        }
-        Err(e) => Err(format!("**Something went wrong!**\n{}", e)),
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::events::quests::quest_sign_up::persist_signup;
-    use crate::shared::enums::QuestParticipantSelectionMechanism;
-    use crate::{database_mocks, Error};
-    use chrono::Utc;
-    use sqlx::{Pool, Sqlite};
-
-    #[sqlx::test]
-    async fn sign_up(db: Pool<Sqlite>) -> Result<(), Error> {
-        let data = database_mocks::create_mock::data(db).await;
-        let channel_id = 100;
-        let creator_id = 200;
-        let guild_id = 300;
-        let bot_message_id = 400;
-        let character_id = 500;
-        let character_name = String::from("test");
-
-        database_mocks::create_mock::guild(&data.database, guild_id).await;
-        database_mocks::create_mock::user(&data.database, creator_id).await;
-        database_mocks::create_mock::quest(
-            &data.database,
-            channel_id,
-            guild_id,
-            creator_id,
-            bot_message_id,
-            5,
-            QuestParticipantSelectionMechanism::Random,
-        )
-        .await;
-        database_mocks::create_mock::character(
-            &data,
-            guild_id,
-            creator_id,
-            character_id,
-            &character_name,
-        )
-        .await;
-
-        let timestamp = Utc::now().timestamp();
-        persist_signup(&data, channel_id, character_id, timestamp).await?;
-
-        let signups = sqlx::query!("SELECT quest_id, character_id, timestamp FROM quest_signup")
-            .fetch_all(&data.database)
-            .await?;
-
-        let signup = signups.first().unwrap();
-        assert_eq!(channel_id, signup.quest_id);
-        assert_eq!(character_id, signup.character_id);
-        assert_eq!(timestamp, signup.timestamp);
-
-        Ok(())
-    }
-}
@@ -0,0 +1,512 @@
+use crate::Error;
+use crate::events::quests::quest_add_random_participants::validate_random_draw;
+use crate::shared::data::Data;
+use crate::shared::utility::{button_building, quest_message_utils};
+use chrono::Utc;
+use rand::Rng;
+use serenity::all::{
+    ButtonStyle, ComponentInteraction, CreateActionRow, CreateInteractionResponse,
+    CreateInteractionResponseMessage,
+};
+use serenity::client::Context;
+
+/// Quest completions older than this don't lower a character's weight anymore.
+const RECENT_COMPLETION_DAYS: i64 = 30;
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+pub struct WeightedCandidate {
+    pub character_id: i64,
+    pub character_name: String,
+    pub user_id: i64,
+    pub recent_completions: i64,
+    pub lost_draws: i64,
+}
+
+impl WeightedCandidate {
+    /// Every lost draw raises the odds of the player, every recently completed quest lowers those of the character.
+    pub fn weight(&self) -> f64 {
+        (1 + self.lost_draws) as f64 / (1 + self.recent_completions) as f64
+    }
+}
+
+pub async fn preview_weighted_draw(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+) -> Result<(), Error> {
+    let guild_id = interaction
+        .guild_id
+        .expect("Command should be guild_only")
+        .get() as i64;
+    let user_id = interaction.user.id.get() as i64;
+    let channel_id = interaction.channel_id.get() as i64;
+
+    let message = match build_preview(data, guild_id, user_id, channel_id).await {
+        Ok(text) => CreateInteractionResponseMessage::new()
+            .content(text)
+            .components(vec![CreateActionRow::Buttons(vec![
+                button_building::create_styled_button(
+                    "Confirm Draw",
+                    "quest-confirm-weighted-draw",
+                    false,
+                    ButtonStyle::Success,
+                ),
+            ])]),
+        Err(e) => CreateInteractionResponseMessage::new().content(e.to_string()),
+    };
+
+    interaction
+        .create_response(
+            context,
+            CreateInteractionResponse::Message(message.ephemeral(true)),
+        )
+        .await?;
+
+    Ok(())
+}
+
+pub async fn confirm_weighted_draw(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+) -> Result<(), Error> {
+    let guild_id = interaction
+        .guild_id
+        .expect("Command should be guild_only")
+        .get() as i64;
+    let user_id = interaction.user.id.get() as i64;
+    let channel_id = interaction.channel_id.get() as i64;
+
+    let execution_result = execute_weighted_draw(data, guild_id, user_id, channel_id).await;
+    let was_error = execution_result.is_err();
+    let result = execution_result.unwrap_or_else(|error| error.to_string());
+
+    interaction
+        .create_response(
+            context,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .ephemeral(was_error)
+                    .content(result),
+            ),
+        )
+        .await?;
+
+    if !was_error {
+        quest_message_utils::update_quest_message(
+            context,
+            &data.database,
+            &data.game.base_data,
+            channel_id,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn build_preview(
+    data: &Data,
+    guild_id: i64,
+    user_id: i64,
+    channel_id: i64,
+) -> Result<String, Error> {
+    let open_spots = validate_random_draw(data, user_id, channel_id).await?;
+    let candidates = get_weighted_candidates(data, guild_id, channel_id).await?;
+    if candidates.is_empty() {
+        return Err(Error::from(
+            "Doesn't seem like there are any participants waiting for a spot!",
+        ));
+    }
+
+    Ok(format!(
+        "**{} open spot(s).** These are the current odds for a single spot:\n{}\n*Weight = (1 + lost draws of the player) / (1 + quests completed by the character within the last {} days)*",
+        open_spots,
+        build_weight_list(&candidates),
+        RECENT_COMPLETION_DAYS
+    ))
+}
+
+/// All signups which haven't been accepted yet, along with the stats their weight is based on.
+pub async fn get_weighted_candidates(
+    data: &Data,
+    guild_id: i64,
+    channel_id: i64,
+) -> Result<Vec<WeightedCandidate>, Error> {
+    let cutoff = Utc::now().timestamp() - RECENT_COMPLETION_DAYS * SECONDS_PER_DAY;
+    let candidates = sqlx::query_as!(
+        WeightedCandidate,
+        "SELECT character.id as character_id, character.name as character_name, character.user_id as user_id,
+    (SELECT COUNT(*) FROM quest_completion
+        INNER JOIN quest ON quest_completion.quest_id = quest.channel_id
+        WHERE quest_completion.character_id = character.id AND quest.completion_timestamp >= ?) as \"recent_completions!: i64\",
+    COALESCE((SELECT loss_count FROM quest_draw_loss
+        WHERE quest_draw_loss.guild_id = ? AND quest_draw_loss.user_id = character.user_id), 0) as \"lost_draws!: i64\"
+FROM quest_signup
+INNER JOIN character ON
+    quest_signup.character_id = character.id
+WHERE quest_signup.quest_id = ? AND quest_signup.accepted = false
+ORDER BY quest_signup.timestamp",
+        cutoff,
+        guild_id,
+        channel_id
+    )
+    .fetch_all(&data.database)
+    .await?;
+
+    Ok(candidates)
+}
+
+fn build_weight_list(candidates: &[WeightedCandidate]) -> String {
+    let total_weight: f64 = candidates.iter().map(|x| x.weight()).sum();
+    candidates
+        .iter()
+        .map(|x| {
+            format!(
+                "- {} (<@{}>) | {} recent quests, {} lost draws | Weight {:.2} ({:.0}%)\n",
+                x.character_name,
+                x.user_id,
+                x.recent_completions,
+                x.lost_draws,
+                x.weight(),
+                x.weight() / total_weight * 100.0
+            )
+        })
+        .collect()
+}
+
+/// Draws up to `count` winners without replacement. Returns the winners and everyone who lost.
+pub fn pick_weighted_winners(
+    mut candidates: Vec<WeightedCandidate>,
+    count: usize,
+    rng: &mut impl Rng,
+) -> (Vec<WeightedCandidate>, Vec<WeightedCandidate>) {
+    let mut winners = Vec::new();
+    while winners.len() < count && !candidates.is_empty() {
+        let total_weight: f64 = candidates.iter().map(|x| x.weight()).sum();
+        let mut roll = rng.random_range(0.0..total_weight);
+        let mut index = candidates.len() - 1;
+        for (i, candidate) in candidates.iter().enumerate() {
+            if roll < candidate.weight() {
+                index = i;
+                break;
+            }
+            roll -= candidate.weight();
+        }
+
+        winners.push(candidates.remove(index));
+    }
+
+    (winners, candidates)
+}
+
+async fn execute_weighted_draw(
+    data: &Data,
+    guild_id: i64,
+    user_id: i64,
+    channel_id: i64,
+) -> Result<String, Error> {
+    let open_spots = validate_random_draw(data, user_id, channel_id).await?;
+    let candidates = get_weighted_candidates(data, guild_id, channel_id).await?;
+    if candidates.is_empty() {
+        return Err(Error::from(
+            "Doesn't seem like there are any participants waiting for a spot!",
+        ));
+    }
+
+    let weight_list = build_weight_list(&candidates);
+    let (winners, losers) =
+        pick_weighted_winners(candidates, open_spots as usize, &mut rand::rng());
+    persist_draw_results(data, guild_id, channel_id, &winners, &losers).await?;
+
+    let mut result = String::from("The following participants were chosen by weighted draw:\n");
+    for winner in &winners {
+        result.push_str(&format!(
+            "- {} (<@{}>)\n",
+            winner.character_name, winner.user_id
+        ));
+    }
+    result.push_str(&format!("\n**Odds before the draw:**\n{}", weight_list));
+
+    Ok(result)
+}
+
+/// Accepts the winners. Players who won get their lost draws reset, everyone else who lost gets one more.
+async fn persist_draw_results(
+    data: &Data,
+    guild_id: i64,
+    channel_id: i64,
+    winners: &[WeightedCandidate],
+    losers: &[WeightedCandidate],
+) -> Result<(), Error> {
+    let mut transaction = data.database.begin().await?;
+    for winner in winners {
+        sqlx::query!(
+            "UPDATE quest_signup SET accepted = true WHERE quest_id = ? AND character_id = ?",
+            channel_id,
+            winner.character_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query!(
+            "INSERT INTO quest_draw_loss (guild_id, user_id, loss_count) VALUES (?, ?, 0) \
+            ON CONFLICT (guild_id, user_id) DO UPDATE SET loss_count = 0",
+            guild_id,
+            winner.user_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    let mut losing_user_ids: Vec<i64> = Vec::new();
+    for loser in losers {
+        if winners.iter().any(|x| x.user_id == loser.user_id)
+            || losing_user_ids.contains(&loser.user_id)
+        {
+            continue;
+        }
+        losing_user_ids.push(loser.user_id);
+    }
+
+    for user_id in losing_user_ids {
+        sqlx::query!(
+            "INSERT INTO quest_draw_loss (guild_id, user_id, loss_count) VALUES (?, ?, 1) \
+            ON CONFLICT (guild_id, user_id) DO UPDATE SET loss_count = loss_count + 1",
+            guild_id,
+            user_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    transaction.commit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::events::quests::quest_weighted_random_draw::{
+        WeightedCandidate, execute_weighted_draw, get_weighted_candidates, pick_weighted_winners,
+    };
+    use crate::shared::data::Data;
+    use crate::shared::enums::QuestParticipantSelectionMechanism;
+    use crate::{Error, database_mocks};
+    use chrono::Utc;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use sqlx::{Pool, Sqlite};
+
+    const CHANNEL_ID: i64 = 100;
+    const GUILD_ID: i64 = 300;
+    const BOT_MESSAGE_ID: i64 = 400;
+    const USER1_ID: i64 = 201;
+    const USER2_ID: i64 = 202;
+    const CHARACTER1_ID: i64 = 511;
+    const CHARACTER2_ID: i64 = 521;
+
+    async fn setup_draw(data: &Data) {
+        database_mocks::create_mock::guild(&data.database, GUILD_ID).await;
+        database_mocks::create_mock::user(&data.database, USER1_ID).await;
+        database_mocks::create_mock::user(&data.database, USER2_ID).await;
+        database_mocks::create_mock::quest(
+            &data.database,
+            CHANNEL_ID,
+            GUILD_ID,
+            USER1_ID,
+            BOT_MESSAGE_ID,
+            1,
+            QuestParticipantSelectionMechanism::WeightedRandom,
+        )
+        .await;
+        database_mocks::create_mock::character(
+            data,
+            GUILD_ID,
+            USER1_ID,
+            CHARACTER1_ID,
+            &String::from("test1"),
+        )
+        .await;
+        database_mocks::create_mock::character(
+            data,
+            GUILD_ID,
+            USER2_ID,
+            CHARACTER2_ID,
+            &String::from("test2"),
+        )
+        .await;
+        database_mocks::create_mock::quest_signup(&data.database, CHANNEL_ID, CHARACTER1_ID).await;
+        database_mocks::create_mock::quest_signup(&data.database, CHANNEL_ID, CHARACTER2_ID).await;
+    }
+
+    fn candidate(character_id: i64, user_id: i64) -> WeightedCandidate {
+        WeightedCandidate {
+            character_id,
+            character_name: character_id.to_string(),
+            user_id,
+            recent_completions: 0,
+            lost_draws: 0,
+        }
+    }
+
+    #[sqlx::test]
+    async fn weights_consider_recent_completions_and_lost_draws(
+        db: Pool<Sqlite>,
+    ) -> Result<(), Error> {
+        let data = database_mocks::create_mock::data(db).await;
+        setup_draw(&data).await;
+
+        let now = Utc::now().timestamp();
+        let recent_quest_id = 101;
+        let old_quest_id = 102;
+        for quest_id in [recent_quest_id, old_quest_id] {
+            database_mocks::create_mock::quest(
+                &data.database,
+                quest_id,
+                GUILD_ID,
+                USER2_ID,
+                BOT_MESSAGE_ID + quest_id,
+                5,
+                QuestParticipantSelectionMechanism::Random,
+            )
+            .await;
+        }
+        database_mocks::create_mock::quest_completion(
+            &data.database,
+            recent_quest_id,
+            CHARACTER1_ID,
+            now - 60 * 60,
+        )
+        .await;
+        database_mocks::create_mock::quest_completion(
+            &data.database,
+            old_quest_id,
+            CHARACTER1_ID,
+            now - 60 * 24 * 60 * 60,
+        )
+        .await;
+        sqlx::query!(
+            "INSERT INTO quest_draw_loss (guild_id, user_id, loss_count) VALUES (?, ?, 2)",
+            GUILD_ID,
+            USER2_ID
+        )
+        .execute(&data.database)
+        .await?;
+
+        let candidates = get_weighted_candidates(&data, GUILD_ID, CHANNEL_ID).await?;
+        assert_eq!(2, candidates.len());
+
+        let first = candidates
+            .iter()
+            .find(|x| x.character_id == CHARACTER1_ID)
+            .unwrap();
+        assert_eq!(1, first.recent_completions);
+        assert_eq!(0, first.lost_draws);
+        assert_eq!(0.5, first.weight());
+
+        let second = candidates
+            .iter()
+            .find(|x| x.character_id == CHARACTER2_ID)
+            .unwrap();
+        assert_eq!(0, second.recent_completions);
+        assert_eq!(2, second.lost_draws);
+        assert_eq!(3.0, second.weight());
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn draw_resets_winner_and_counts_loser(db: Pool<Sqlite>) -> Result<(), Error> {
+        let data = database_mocks::create_mock::data(db).await;
+        setup_draw(&data).await;
+
+        execute_weighted_draw(&data, GUILD_ID, USER1_ID, CHANNEL_ID).await?;
+
+        let accepted = sqlx::query!(
+            "SELECT character.user_id FROM quest_signup \
+            INNER JOIN character ON quest_signup.character_id = character.id \
+            WHERE quest_id = ? AND accepted = true",
+            CHANNEL_ID
+        )
+        .fetch_all(&data.database)
+        .await?;
+        assert_eq!(1, accepted.len());
+
+        let winner_id = accepted[0].user_id;
+        let loser_id = if winner_id == USER1_ID {
+            USER2_ID
+        } else {
+            USER1_ID
+        };
+        let losses = sqlx::query!(
+            "SELECT user_id, loss_count FROM quest_draw_loss WHERE guild_id = ?",
+            GUILD_ID
+        )
+        .fetch_all(&data.database)
+        .await?;
+        assert_eq!(2, losses.len());
+        for loss in losses {
+            if loss.user_id == winner_id {
+                assert_eq!(0, loss.loss_count);
+            } else {
+                assert_eq!(loser_id, loss.user_id);
+                assert_eq!(1, loss.loss_count);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn only_the_quest_owner_can_draw(db: Pool<Sqlite>) -> Result<(), Error> {
+        let data = database_mocks::create_mock::data(db).await;
+        setup_draw(&data).await;
+
+        let result = execute_weighted_draw(&data, GUILD_ID, USER2_ID, CHANNEL_ID).await;
+        assert!(result.is_err());
+
+        let accepted = sqlx::query!(
+            "SELECT COUNT(*) as count FROM quest_signup WHERE quest_id = ? AND accepted = true",
+            CHANNEL_ID
+        )
+        .fetch_one(&data.database)
+        .await?;
+        assert_eq!(0, accepted.count);
+
+        Ok(())
+    }
+
+    #[test]
+    fn pick_weighted_winners_respects_count() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let candidates = vec![candidate(1, 10), candidate(2, 20), candidate(3, 30)];
+        let (winners, losers) = pick_weighted_winners(candidates, 2, &mut rng);
+        assert_eq!(2, winners.len());
+        assert_eq!(1, losers.len());
+
+        let candidates = vec![candidate(1, 10), candidate(2, 20)];
+        let (winners, losers) = pick_weighted_winners(candidates, 5, &mut rng);
+        assert_eq!(2, winners.len());
+        assert!(losers.is_empty());
+    }
+
+    #[test]
+    fn pick_weighted_winners_favors_higher_weights() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut favored_wins = 0;
+        for _ in 0..1000 {
+            let mut favored = candidate(1, 10);
+            favored.lost_draws = 9;
+            let mut unfavored = candidate(2, 20);
+            unfavored.recent_completions = 9;
+
+            let (winners, _) = pick_weighted_winners(vec![favored, unfavored], 1, &mut rng);
+            if winners[0].character_id == 1 {
+                favored_wins += 1;
+            }
+        }
+
+        // Weights are 10 and 0.1, so the favored character should win roughly 99% of all draws.
+        assert!(favored_wins > 950);
+    }
+}
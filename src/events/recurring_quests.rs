@@ -0,0 +1,164 @@
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use chrono::Utc;
+use serenity::all::{CreateForumPost, CreateMessage};
+use serenity::model::id::ChannelId;
+use serenity::prelude::Context;
+use sqlx::{Pool, Sqlite};
+
+use crate::Error;
+use crate::events::send_error_to_log_channel;
+use crate::shared::data::Data;
+use crate::shared::game_data::GameData;
+use crate::shared::quest_creation::{apply_quest_settings, create_quest_impl, delete_unused_quest};
+use crate::shared::quest_template::{
+    QuestTemplate, get_due_recurring_quest_templates, get_quest_template_reward_items,
+};
+use crate::shared::utility::quest_message_utils;
+
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Posts for recurring quest templates which were due while the bot was offline will be created once it's back up.
+/// Missed occurrences beyond that are skipped.
+pub async fn start_recurring_quest_thread(ctx: &Context, data: &Data) {
+    let ctx = Arc::new(ctx.clone());
+    if !data
+        .is_recurring_quest_thread_running
+        .load(Ordering::Relaxed)
+    {
+        let ctx_in_thread = Arc::clone(&ctx);
+        let database = data.database.clone();
+        let game = Arc::clone(&data.game);
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) =
+                    post_due_recurring_quests(&ctx_in_thread, &database, &game.base_data).await
+                {
+                    send_error_to_log_channel(&ctx_in_thread, e.to_string()).await;
+                }
+                tokio::time::sleep(CHECK_INTERVAL).await;
+            }
+        });
+
+        data.is_recurring_quest_thread_running
+            .swap(true, Ordering::Relaxed);
+    }
+}
+
+async fn post_due_recurring_quests(
+    ctx: &Context,
+    database: &Pool<Sqlite>,
+    game_data: &GameData,
+) -> Result<(), Error> {
+    let now = Utc::now().timestamp();
+    for template in get_due_recurring_quest_templates(database, now).await? {
+        let (Some(forum_channel_id), Some(interval_days), Some(mut next_timestamp)) = (
+            template.recurring_forum_channel_id,
+            template.recurrence_interval_days,
+            template.next_recurrence_timestamp,
+        ) else {
+            continue;
+        };
+
+        // Schedule the next post first, so a failing one doesn't get retried every minute.
+        while next_timestamp <= now {
+            next_timestamp += interval_days * SECONDS_PER_DAY;
+        }
+        sqlx::query!(
+            "UPDATE quest_template SET next_recurrence_timestamp = ? WHERE guild_id = ? AND name = ?",
+            next_timestamp,
+            template.guild_id,
+            template.name
+        )
+        .execute(database)
+        .await?;
+
+        if let Err(e) =
+            post_recurring_quest(ctx, database, game_data, &template, forum_channel_id).await
+        {
+            send_error_to_log_channel(
+                ctx,
+                format!("Unable to post recurring quest {}: {}", template.name, e),
+            )
+            .await;
+        }
+    }
+
+    Ok(())
+}
+
+async fn post_recurring_quest(
+    ctx: &Context,
+    database: &Pool<Sqlite>,
+    game_data: &GameData,
+    template: &QuestTemplate,
+    forum_channel_id: i64,
+) -> Result<(), Error> {
+    let thread = ChannelId::new(forum_channel_id as u64)
+        .create_forum_post(
+            ctx,
+            CreateForumPost::new(
+                &template.title,
+                CreateMessage::new().content(&template.description),
+            ),
+        )
+        .await?;
+
+    // Without a quest the post would just be clutter and vice versa, so get rid of both again.
+    if let Err(e) = create_recurring_quest(ctx, database, game_data, template, thread.id).await {
+        let _ = thread.id.delete(ctx).await;
+        if let Err(delete_error) = delete_unused_quest(database, thread.id.get() as i64).await {
+            log::error!(
+                "Unable to delete the quest for the removed recurring post {}: {}",
+                thread.id,
+                delete_error
+            );
+        }
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+async fn create_recurring_quest(
+    ctx: &Context,
+    database: &Pool<Sqlite>,
+    game_data: &GameData,
+    template: &QuestTemplate,
+    thread_id: ChannelId,
+) -> Result<(), Error> {
+    let bot_message = thread_id
+        .send_message(ctx, CreateMessage::new().content("Creating Quest..."))
+        .await?;
+
+    // The starter message of a forum post shares its id with the thread.
+    let channel_id = thread_id.get() as i64;
+    let items =
+        get_quest_template_reward_items(database, template.guild_id, &template.name).await?;
+    let mut transaction = database.begin().await?;
+    create_quest_impl(
+        &mut *transaction,
+        template.guild_id,
+        channel_id,
+        template.creator_id,
+        bot_message.id.get() as i64,
+        template.maximum_participant_count,
+        template.selection_mechanism(),
+        channel_id,
+    )
+    .await?;
+    apply_quest_settings(
+        &mut transaction,
+        channel_id,
+        &template.reward(items),
+        &template.restrictions(),
+    )
+    .await?;
+    transaction.commit().await?;
+    quest_message_utils::update_quest_message(ctx, database, game_data, channel_id).await?;
+
+    let _ = bot_message.pin(ctx).await;
+    Ok(())
+}
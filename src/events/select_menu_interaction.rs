@@ -13,11 +13,40 @@ pub async fn handle_select_menu_interaction(
         return Ok(());
     }
 
-    let (command, _) = events::parse_interaction_command(interaction.data.custom_id.as_str());
-    if command == "timestamp-offset" {
-        timestamp_offset(context, framework, interaction).await
-    } else {
-        Ok(())
+    let (command, args) = events::parse_interaction_command(interaction.data.custom_id.as_str());
+    match command {
+        "timestamp-offset" => timestamp_offset(context, framework, interaction).await,
+        "move-edit" => {
+            let game_data = framework
+                .user_data
+                .game
+                .get_by_interaction(interaction, &framework.user_data.database)
+                .await;
+            events::character_move_edit::handle_move_selection(
+                context,
+                interaction,
+                framework.user_data,
+                game_data,
+                args,
+            )
+            .await
+        }
+        "ce-skill" => {
+            let game_data = framework
+                .user_data
+                .game
+                .get_by_interaction(interaction, &framework.user_data.database)
+                .await;
+            events::character_stat_edit::handle_skill_selection(
+                context,
+                interaction,
+                &framework.user_data.database,
+                game_data,
+                args,
+            )
+            .await
+        }
+        _ => Ok(()),
     }
 }
 
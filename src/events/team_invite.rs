@@ -0,0 +1,122 @@
+use crate::Error;
+use crate::events::{send_error, update_wallet_post};
+use crate::shared::action_log::{ActionType, LogActionArguments, log_action};
+use crate::shared::data::Data;
+use crate::shared::team::{Team, get_team_of_character};
+use serenity::all::{
+    ComponentInteraction, CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use serenity::client::Context;
+use std::str::FromStr;
+
+/// Adds the invited character to the team, as long as the button was pressed by their owner.
+pub async fn accept_team_invite(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+    args: Vec<&str>,
+) -> Result<(), Error> {
+    if args.len() != 2 {
+        return send_error(
+            &interaction,
+            context,
+            "Are you trying to do anything cheesy?",
+        )
+        .await;
+    }
+    let team_id = i64::from_str(args[0])?;
+    let character_id = i64::from_str(args[1])?;
+    let user_id = interaction.user.id.get() as i64;
+
+    let character = sqlx::query!(
+        "SELECT name, user_id FROM character WHERE id = ? AND is_retired = false",
+        character_id
+    )
+    .fetch_optional(&data.database)
+    .await?;
+    let Some(character) = character else {
+        return send_error(
+            &interaction,
+            context,
+            "That character doesn't seem to exist anymore.",
+        )
+        .await;
+    };
+    if character.user_id != user_id {
+        return send_error(
+            &interaction,
+            context,
+            "Only the owner of the invited character can accept this invite!",
+        )
+        .await;
+    }
+
+    if let Some(team) = get_team_of_character(&data.database, character_id).await? {
+        return send_error(
+            &interaction,
+            context,
+            &format!(
+                "**{}** is already part of {}.",
+                character.name,
+                team.display_name()
+            ),
+        )
+        .await;
+    }
+
+    let team = sqlx::query_as!(
+        Team,
+        "SELECT id, name, emblem, wallet_id, rank_points FROM team WHERE id = ?",
+        team_id
+    )
+    .fetch_optional(&data.database)
+    .await?;
+    let Some(team) = team else {
+        return send_error(
+            &interaction,
+            context,
+            "That team doesn't seem to exist anymore.",
+        )
+        .await;
+    };
+
+    let mut transaction = data.database.begin().await?;
+    sqlx::query!(
+        "INSERT INTO team_member (character_id, team_id) VALUES (?, ?)",
+        character_id,
+        team_id
+    )
+    .execute(&mut *transaction)
+    .await?;
+    sqlx::query!(
+        "INSERT OR IGNORE INTO wallet_owner (wallet_id, character_id) VALUES (?, ?)",
+        team.wallet_id,
+        character_id
+    )
+    .execute(&mut *transaction)
+    .await?;
+    transaction.commit().await?;
+
+    interaction
+        .create_response(
+            context,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content(format!(
+                        "**{}** joined {}!",
+                        character.name,
+                        team.display_name()
+                    ))
+                    .components(Vec::new()),
+            ),
+        )
+        .await?;
+
+    update_wallet_post(context, &data.database, team.wallet_id).await;
+    log_action(
+        &ActionType::TeamChange,
+        LogActionArguments::triggered_by_interaction(context, &data.database, interaction),
+        format!("{} joined team {}", character.name, team.name),
+    )
+    .await
+}
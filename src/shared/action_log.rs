@@ -1,11 +1,19 @@
 use crate::Error;
 use crate::shared::{PoiseContext, SerenityContext};
-use serenity::all::{ChannelId, CreateAllowedMentions, CreateMessage, GetMessages, GuildId, User};
+use chrono::Utc;
+use serenity::all::{
+    ButtonStyle, ChannelId, ComponentInteraction, CreateActionRow, CreateAllowedMentions,
+    CreateButton, CreateMessage, GetMessages, GuildId, User,
+};
 use sqlx::{Pool, Sqlite};
 use std::fmt;
 use std::fmt::Formatter;
+use strum_macros::{EnumString, IntoStaticStr};
 
-#[derive(PartialEq)]
+/// How long the undo button below reward and payment messages stays usable.
+pub const UNDO_TIMEOUT_IN_SECONDS: i64 = 300;
+
+#[derive(PartialEq, EnumString, IntoStaticStr)]
 pub enum ActionType {
     Initialization,
     Reward,
@@ -26,12 +34,23 @@ pub enum ActionType {
     CharacterStatReset,
     CharacterRetirement,
     CharacterUnRetirement,
+    CharacterTransfer,
     TerastallizationUnlock,
     StoreGMExperience,
     UseGMExperience,
     RuleUpdate,
     RuleDelete,
     RuleClone,
+    InventoryChange,
+    ItemUse,
+    ShopPurchase,
+    ShopEdit,
+    Evolution,
+    ConditionChange,
+    TeamChange,
+    QuestTemplateUpdate,
+    QuestTemplateDelete,
+    GuildSettingsUpdate,
     DoNotLog,
 }
 
@@ -57,26 +76,52 @@ impl fmt::Display for ActionType {
             ActionType::CharacterStatReset => "📝 [Edit]",
             ActionType::CharacterRetirement => "💤 [Retirement]",
             ActionType::CharacterUnRetirement => "⏰ [UnRetirement]",
+            ActionType::CharacterTransfer => "🤝 [Transfer]",
             ActionType::TerastallizationUnlock => "💎 [Terastallization Unlock]",
             ActionType::StoreGMExperience => "🏦⬅️ [GM Experience]",
             ActionType::UseGMExperience => "🏦➡️ [GM Experience]",
             ActionType::RuleUpdate => "⚖️🌟 [Rule Update]",
             ActionType::RuleDelete => "⚖️❌ [Rule Deletion]",
             ActionType::RuleClone => "⚖️⚖️⚖️ [Rule Clone]",
+            ActionType::InventoryChange => "🎒 [Inventory]",
+            ActionType::ItemUse => "🍎 [Item Use]",
+            ActionType::ShopPurchase => "🛒 [Purchase]",
+            ActionType::ShopEdit => "🛒📝 [Shop Edit]",
+            ActionType::Evolution => "🧬 [Evolution]",
+            ActionType::ConditionChange => "❤️ [Condition]",
+            ActionType::TeamChange => "🚩 [Team]",
+            ActionType::QuestTemplateUpdate => "📋🌟 [Quest Template Update]",
+            ActionType::QuestTemplateDelete => "📋❌ [Quest Template Deletion]",
+            ActionType::GuildSettingsUpdate => "⚙️ [Guild Settings]",
             ActionType::DoNotLog => "",
         })
     }
 }
 
+/// The target of a [LedgerChange].
+pub enum LedgerTarget {
+    Character(i64),
+    Wallet(i64),
+}
+
+/// A single numeric change which will be persisted inside the ledger alongside the log message.
+pub struct LedgerChange {
+    pub target: LedgerTarget,
+    pub field: String,
+    pub delta: i64,
+}
+
 /// Necessary arguments for [log_action].
 ///
-/// Use either [LogActionArguments::triggered_by_bot] or [LogActionArguments::triggered_by_user] to instantiate these.
+/// Use either [LogActionArguments::triggered_by_bot], [LogActionArguments::triggered_by_user] or [LogActionArguments::triggered_by_interaction] to instantiate these.
 pub struct LogActionArguments<'a> {
     pub author: Option<&'a User>,
     pub channel_id: Option<ChannelId>,
     pub guild_id: Option<GuildId>,
+    pub interaction_id: Option<u64>,
     pub context: &'a SerenityContext,
     pub database: &'a Pool<Sqlite>,
+    pub changes: Vec<LedgerChange>,
 }
 
 impl<'a> LogActionArguments<'a> {
@@ -85,8 +130,10 @@ impl<'a> LogActionArguments<'a> {
             author: None,
             channel_id: None,
             guild_id: None,
+            interaction_id: None,
             context,
             database,
+            changes: Vec::new(),
         }
     }
 
@@ -95,10 +142,112 @@ impl<'a> LogActionArguments<'a> {
             author: Some(context.author()),
             channel_id: Some(context.channel_id()),
             guild_id: context.guild_id(),
+            interaction_id: Some(context.id()),
             context: context.serenity_context(),
             database: &context.data().database,
+            changes: Vec::new(),
+        }
+    }
+
+    pub fn triggered_by_interaction(
+        context: &'a SerenityContext,
+        database: &'a Pool<Sqlite>,
+        interaction: &'a ComponentInteraction,
+    ) -> Self {
+        LogActionArguments {
+            author: Some(&interaction.user),
+            channel_id: Some(interaction.channel_id),
+            guild_id: interaction.guild_id,
+            interaction_id: Some(interaction.id.get()),
+            context,
+            database,
+            changes: Vec::new(),
         }
     }
+
+    pub fn with_character_change(self, character_id: i64, field: &str, delta: i64) -> Self {
+        self.with_change(LedgerTarget::Character(character_id), field, delta)
+    }
+
+    pub fn with_wallet_change(self, wallet_id: i64, field: &str, delta: i64) -> Self {
+        self.with_change(LedgerTarget::Wallet(wallet_id), field, delta)
+    }
+
+    fn with_change(mut self, target: LedgerTarget, field: &str, delta: i64) -> Self {
+        self.changes.push(LedgerChange {
+            target,
+            field: field.to_string(),
+            delta,
+        });
+        self
+    }
+}
+
+/// Creates a button which reverts all ledger changes made during the given interaction.
+pub fn create_undo_button(interaction_id: u64) -> CreateActionRow {
+    CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("ledger-undo_{}", interaction_id))
+            .label("Undo")
+            .emoji('↩')
+            .style(ButtonStyle::Secondary),
+    ])
+}
+
+async fn persist_ledger_entries(
+    action_type: &ActionType,
+    args: &LogActionArguments<'_>,
+    guild_id: i64,
+    message: &str,
+) -> Result<(), Error> {
+    let action_type: &'static str = action_type.into();
+    let actor_id = args.author.map(|x| x.id.get() as i64);
+    let channel_id = args.channel_id.map(|x| x.get() as i64);
+    let interaction_id = args.interaction_id.map(|x| x as i64);
+    let timestamp = Utc::now().timestamp();
+
+    if args.changes.is_empty() {
+        sqlx::query!(
+            "INSERT INTO ledger_entry (guild_id, actor_id, channel_id, interaction_id, action_type, message, timestamp) \
+            VALUES (?, ?, ?, ?, ?, ?, ?)",
+            guild_id,
+            actor_id,
+            channel_id,
+            interaction_id,
+            action_type,
+            message,
+            timestamp,
+        )
+        .execute(args.database)
+        .await?;
+        return Ok(());
+    }
+
+    for change in &args.changes {
+        let (character_id, wallet_id) = match change.target {
+            LedgerTarget::Character(id) => (Some(id), None),
+            LedgerTarget::Wallet(id) => (None, Some(id)),
+        };
+
+        sqlx::query!(
+            "INSERT INTO ledger_entry (guild_id, actor_id, channel_id, interaction_id, action_type, character_id, wallet_id, field, delta, message, timestamp) \
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            guild_id,
+            actor_id,
+            channel_id,
+            interaction_id,
+            action_type,
+            character_id,
+            wallet_id,
+            change.field,
+            change.delta,
+            message,
+            timestamp,
+        )
+        .execute(args.database)
+        .await?;
+    }
+
+    Ok(())
 }
 
 pub async fn log_action<'a>(
@@ -112,6 +261,10 @@ pub async fn log_action<'a>(
         return Ok(());
     };
     let guild_id_value = guild_id.get() as i64;
+    let message = message.into();
+    if action_type != &ActionType::DoNotLog {
+        persist_ledger_entries(action_type, &args, guild_id_value, &message).await?;
+    }
 
     let record = sqlx::query!(
         "SELECT action_log_channel_id FROM guild WHERE id = ?",
@@ -145,7 +298,7 @@ pub async fn log_action<'a>(
                                 .content(std::format!(
                                     "{} {} (triggered by {}{})",
                                     action_type,
-                                    message.into(),
+                                    message,
                                     author,
                                     origin
                                 ))
@@ -1,15 +1,143 @@
+use crate::Error;
 use crate::commands::BuildUpdatedStatMessageStringResult;
+use crate::shared::character_condition::CharacterCondition;
+use crate::shared::character_skills::CharacterSkills;
 use crate::shared::character_stats::GenericCharacterStats;
 use crate::shared::data::Data;
 use crate::shared::enums::{Gender, MysteryDungeonRank, PokemonTypeWithoutShadow};
 use crate::shared::game_data::{GameData, PokemonApiId};
+use crate::shared::guild_settings::ProgressionRules;
+use crate::shared::move_dice_pools::DicePoolStats;
 use crate::shared::utility::{error_handling, level_calculations};
-use crate::shared::{PoiseContext, SerenityContext, constants, emoji};
+use crate::shared::{
+    PoiseContext, SerenityContext, character_moves, character_skills, emoji, guild_settings,
+    inventory, quest_archive,
+};
 use serenity::all::{
     ButtonStyle, ChannelId, CreateActionRow, CreateButton, EditMessage, GuildId, MessageId, UserId,
 };
 use sqlx::{Pool, Sqlite};
 
+const RECENT_QUEST_DISPLAY_COUNT: i64 = 3;
+
+/// Stat points which haven't been invested yet.
+pub struct RemainingStatPoints {
+    pub combat: i64,
+    pub social: i64,
+    pub skills: i64,
+}
+
+impl RemainingStatPoints {
+    pub fn calculate(
+        rules: &ProgressionRules,
+        level: i64,
+        combat_stats: &GenericCharacterStats,
+        social_stats: &GenericCharacterStats,
+        skills: &CharacterSkills,
+    ) -> Self {
+        let rank = MysteryDungeonRank::from_level(level);
+        RemainingStatPoints {
+            combat: level_calculations::calculate_available_combat_points(rules, level)
+                - combat_stats.calculate_invested_stat_points(rules),
+            social: rules.social_stat_points(&rank)
+                - social_stats.calculate_invested_stat_points(rules),
+            skills: rank.skill_points() as i64 - skills.calculate_invested_skill_points(),
+        }
+    }
+
+    /// Buttons which open the stat editor, or None in case there's nothing left to spend.
+    pub fn create_stat_edit_buttons(&self, character_id: i64) -> Option<CreateActionRow> {
+        let mut action_row = Vec::new();
+        if self.combat > 0 {
+            action_row.push(
+                CreateButton::new(format!("ce_initialize_combat_{}", character_id))
+                    .label(format!("{} Remaining Stat Points", self.combat))
+                    .style(ButtonStyle::Primary),
+            );
+        }
+        if self.social > 0 {
+            action_row.push(
+                CreateButton::new(format!("ce_initialize_social_{}", character_id))
+                    .label(format!("{} Remaining Social Points", self.social))
+                    .style(ButtonStyle::Primary),
+            );
+        }
+        if self.skills > 0 {
+            action_row.push(
+                CreateButton::new(format!("ce_initialize_skills_{}", character_id))
+                    .label(format!("{} Remaining Skill Points", self.skills))
+                    .style(ButtonStyle::Primary),
+            );
+        }
+
+        if action_row.is_empty() {
+            None
+        } else {
+            Some(CreateActionRow::Buttons(action_row))
+        }
+    }
+}
+
+pub async fn get_remaining_stat_points(
+    database: &Pool<Sqlite>,
+    game_data: &GameData,
+    character_id: i64,
+) -> Result<RemainingStatPoints, Error> {
+    let record = sqlx::query!(
+        "SELECT guild_id, experience, species_api_id, species_override_for_stats, \
+        stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight, \
+        stat_tough, stat_cool, stat_beauty, stat_cute, stat_clever \
+        FROM character WHERE id = ?",
+        character_id
+    )
+    .fetch_one(database)
+    .await?;
+
+    let rules = guild_settings::get_progression_rules(database, record.guild_id).await?;
+    let level = level_calculations::calculate_level_from_experience(&rules, record.experience);
+    let pokemon = game_data
+        .pokemon_by_api_id
+        .get(&PokemonApiId(
+            record
+                .species_api_id
+                .try_into()
+                .expect("Should always be in valid range."),
+        ))
+        .expect("All mons inside the Database should have a valid API ID assigned.");
+
+    let combat_stats = GenericCharacterStats::from_combat(
+        level_calculations::get_usual_evolution_stage_for_level(
+            &rules,
+            level,
+            pokemon,
+            game_data,
+            record.species_override_for_stats,
+        ),
+        record.stat_strength,
+        record.stat_dexterity,
+        record.stat_vitality,
+        record.stat_special,
+        record.stat_insight,
+    );
+    let social_stats = GenericCharacterStats::from_social(
+        record.stat_tough,
+        record.stat_cool,
+        record.stat_beauty,
+        record.stat_cute,
+        record.stat_clever,
+    );
+
+    let skills = character_skills::get_character_skills(database, character_id).await?;
+
+    Ok(RemainingStatPoints::calculate(
+        &rules,
+        level,
+        &combat_stats,
+        &social_stats,
+        &skills,
+    ))
+}
+
 pub async fn build_character_string(
     context: &serenity::all::Context,
     database: &Pool<Sqlite>,
@@ -26,11 +154,20 @@ pub async fn build_character_string(
     .await;
 
     let completed_quest_count = count_completed_quests(database, character_id).await;
+    let recent_quest_ids =
+        quest_archive::get_recent_quest_ids(database, character_id, RECENT_QUEST_DISPLAY_COUNT)
+            .await
+            .unwrap_or_default();
     match entry {
         Ok(record) => {
-            let level = level_calculations::calculate_level_from_experience(record.experience);
-            let experience = level_calculations::calculate_current_experience(record.experience);
-            let rank = MysteryDungeonRank::from_level(level as u8);
+            let rules = guild_settings::get_progression_rules(database, record.guild_id)
+                .await
+                .unwrap_or_default();
+            let level =
+                level_calculations::calculate_level_from_experience(&rules, record.experience);
+            let experience =
+                level_calculations::calculate_current_experience(&rules, record.experience);
+            let rank = MysteryDungeonRank::from_level(level);
             let pokemon = game_data
                 .pokemon_by_api_id
                 .get(&PokemonApiId(
@@ -77,6 +214,7 @@ pub async fn build_character_string(
 
             let pokemon_evolution_form_for_stats =
                 level_calculations::get_usual_evolution_stage_for_level(
+                    &rules,
                     level,
                     pokemon,
                     game_data,
@@ -99,6 +237,35 @@ pub async fn build_character_string(
                 record.stat_clever,
             );
 
+            let condition = CharacterCondition::new(
+                &combat_stats,
+                record.hp_lost,
+                record.will_lost,
+                record.wounds,
+            );
+
+            let skills = character_skills::get_character_skills(database, character_id)
+                .await
+                .unwrap_or_default();
+            let skill_section = if skills.calculate_invested_skill_points() > 0 {
+                format!("### Skills\n{}\n", skills.build_compact_string())
+            } else {
+                String::new()
+            };
+
+            let moves = character_moves::build_character_move_section(
+                database,
+                game_data,
+                character_id,
+                &DicePoolStats::from_character_stats(
+                    &combat_stats,
+                    &social_stats,
+                    rank,
+                    pokemon_evolution_form_for_stats.base_hp,
+                ),
+            )
+            .await;
+
             let ability_list = pokemon
                 .abilities
                 .build_simple_ability_list(record.is_hidden_ability_unlocked, false);
@@ -135,25 +302,45 @@ pub async fn build_character_string(
                 tera_charges.insert_str(0, "### Terastallization Charges\n");
             }
 
+            let inventory = inventory::get_inventory(database, character_id)
+                .await
+                .unwrap_or_default();
+            let used_backpack_slots = inventory::count_used_slots(game_data, &inventory);
+            let economy_settings =
+                guild_settings::get_guild_economy_settings(database, record.guild_id)
+                    .await
+                    .unwrap_or_default();
+            let backpack = if inventory.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    "### Backpack\n{}\n",
+                    inventory::build_inventory_string(&inventory)
+                )
+            };
+
             let mut message = format!(
                 "\
 ## {} {} {} {}
-**Level {}** `({} / 100)`
+**Level {}** `({} / {})`
+{}
 {} {} {}
 ### Stats {}{}
 ```
 {}
 {}
 ```
-### Abilities 
-{}{}### Statistics
-{} Backpack Slots: {}\n\n",
+{}### Abilities 
+{}{}{}{}### Statistics
+{} Backpack Slots: {} / {}\n\n",
                 rank.emoji_string(),
                 record.name,
                 emoji,
                 retired_or_not,
                 level,
                 experience,
+                rules.experience_per_level,
+                condition.build_string(),
                 record.money,
                 emoji::POKE_COIN,
                 battle_point,
@@ -161,10 +348,14 @@ pub async fn build_character_string(
                 species_override_for_stats,
                 combat_stats.build_string(),
                 social_stats.build_string(),
+                skill_section,
                 ability_list,
+                moves,
                 tera_charges,
+                backpack,
                 emoji::BACKPACK,
-                record.backpack_upgrade_count + constants::DEFAULT_BACKPACK_SLOTS,
+                used_backpack_slots,
+                economy_settings.backpack_slots(record.backpack_upgrade_count),
             );
 
             if completed_quest_count > 0 {
@@ -174,6 +365,14 @@ pub async fn build_character_string(
                     completed_quest_count
                 ));
             }
+            if !recent_quest_ids.is_empty() {
+                let links = recent_quest_ids
+                    .iter()
+                    .map(|x| format!("<#{}>", x))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                message.push_str(&format!("Recent Quests: {}\n", links));
+            }
 
             if record.total_spar_count > 0 {
                 message.push_str(&format!(
@@ -199,44 +398,30 @@ pub async fn build_character_string(
                 ));
             }
 
-            let remaining_combat_points =
-                level_calculations::calculate_available_combat_points(level)
-                    - combat_stats.calculate_invested_stat_points();
-            let remaining_social_points =
-                rank.social_stat_points() as i64 - social_stats.calculate_invested_stat_points();
-
             let mut components = Vec::new();
-            if remaining_combat_points + remaining_social_points > 0 {
-                let mut action_row = Vec::new();
-                if remaining_combat_points > 0 {
-                    action_row.push(
-                        CreateButton::new(format!("ce_initialize_combat_{}", character_id))
-                            .label(format!("{} Remaining Stat Points", remaining_combat_points))
-                            .style(ButtonStyle::Primary),
-                    );
-                }
-                if remaining_social_points > 0 {
-                    action_row.push(
-                        CreateButton::new(format!("ce_initialize_social_{}", character_id))
-                            .label(format!(
-                                "{} Remaining Social Points",
-                                remaining_social_points
-                            ))
-                            .style(ButtonStyle::Primary),
-                    )
-                }
-
-                components.push(CreateActionRow::Buttons(action_row));
+            let remaining_points = RemainingStatPoints::calculate(
+                &rules,
+                level,
+                &combat_stats,
+                &social_stats,
+                &skills,
+            );
+            if let Some(action_row) = remaining_points.create_stat_edit_buttons(character_id) {
+                components.push(action_row);
             }
+            let mut secondary_button_row = vec![
+                CreateButton::new(format!("move-edit-initialize_{}", character_id))
+                    .label("Edit Moves")
+                    .style(ButtonStyle::Secondary),
+            ];
             if completed_quest_count > 0 {
-                let mut quest_history_button_row = Vec::new();
-                quest_history_button_row.push(
+                secondary_button_row.push(
                     CreateButton::new(format!("quest-history_{}", character_id))
                         .label("Show Quest History")
                         .style(ButtonStyle::Secondary),
                 );
-                components.push(CreateActionRow::Buttons(quest_history_button_row));
             }
+            components.push(CreateActionRow::Buttons(secondary_button_row));
 
             Some(BuildUpdatedStatMessageStringResult {
                 message,
@@ -0,0 +1,208 @@
+use crate::Error;
+use crate::shared::enums::{CharacterApplicationStatus, Gender};
+use crate::shared::errors::ValidationError;
+use crate::shared::game_data::pokemon::Pokemon;
+use chrono::Utc;
+use serenity::all::{ButtonStyle, CreateActionRow, CreateButton, Mentionable, UserId};
+use sqlx::SqliteExecutor;
+
+pub struct CharacterApplication {
+    pub id: i64,
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub channel_id: i64,
+    pub name: String,
+    pub species_api_id: i64,
+    pub phenotype: i64,
+    pub is_shiny: bool,
+    pub backstory: Option<String>,
+    pub status: i64,
+    pub review_message_channel_id: Option<i64>,
+    pub review_message_id: Option<i64>,
+}
+
+impl CharacterApplication {
+    pub fn status(&self) -> CharacterApplicationStatus {
+        CharacterApplicationStatus::from_repr(self.status).expect("Should always be valid!")
+    }
+
+    pub fn gender(&self) -> Gender {
+        Gender::from_phenotype(self.phenotype)
+    }
+}
+
+pub struct CharacterApplicationStatusChange {
+    pub status: i64,
+    pub user_id: i64,
+    pub note: Option<String>,
+    pub timestamp: i64,
+}
+
+pub async fn get_character_application(
+    executor: impl SqliteExecutor<'_>,
+    application_id: i64,
+) -> Result<Option<CharacterApplication>, Error> {
+    let application = sqlx::query_as!(
+        CharacterApplication,
+        "SELECT id, guild_id, user_id, channel_id, name, species_api_id, phenotype, is_shiny, backstory, status, \
+        review_message_channel_id, review_message_id \
+        FROM character_application WHERE id = ?",
+        application_id
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(application)
+}
+
+/// An application which is still waiting for a review or for the player to apply the requested changes.
+pub async fn find_open_character_application(
+    executor: impl SqliteExecutor<'_>,
+    guild_id: i64,
+    user_id: i64,
+    name: &str,
+) -> Result<Option<CharacterApplication>, Error> {
+    let pending = CharacterApplicationStatus::Pending as i64;
+    let changes_requested = CharacterApplicationStatus::ChangesRequested as i64;
+    let application = sqlx::query_as!(
+        CharacterApplication,
+        "SELECT id, guild_id, user_id, channel_id, name, species_api_id, phenotype, is_shiny, backstory, status, \
+        review_message_channel_id, review_message_id \
+        FROM character_application WHERE guild_id = ? AND user_id = ? AND name = ? AND status IN (?, ?)",
+        guild_id,
+        user_id,
+        name,
+        pending,
+        changes_requested
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(application)
+}
+
+pub async fn get_status_history(
+    executor: impl SqliteExecutor<'_>,
+    application_id: i64,
+) -> Result<Vec<CharacterApplicationStatusChange>, Error> {
+    let history = sqlx::query_as!(
+        CharacterApplicationStatusChange,
+        "SELECT status, user_id, note, timestamp FROM character_application_status_change \
+        WHERE application_id = ? ORDER BY id",
+        application_id
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(history)
+}
+
+/// Updates the application's status and appends the change to its history.
+/// Fails in case the application isn't in the expected status anymore, e.g. because another GM was faster.
+pub async fn change_application_status(
+    executor: &mut sqlx::SqliteConnection,
+    application_id: i64,
+    expected_status: CharacterApplicationStatus,
+    status: CharacterApplicationStatus,
+    user_id: i64,
+    note: Option<&str>,
+) -> Result<(), Error> {
+    let expected_status = expected_status as i64;
+    let status = status as i64;
+    let timestamp = Utc::now().timestamp();
+    let result = sqlx::query!(
+        "UPDATE character_application SET status = ? WHERE id = ? AND status = ?",
+        status,
+        application_id,
+        expected_status
+    )
+    .execute(&mut *executor)
+    .await?;
+    if result.rows_affected() != 1 {
+        return Err(Box::new(ValidationError::new(
+            "This application has been updated by someone else in the meantime.",
+        )));
+    }
+
+    sqlx::query!(
+        "INSERT INTO character_application_status_change (application_id, status, user_id, note, timestamp) VALUES (?, ?, ?, ?, ?)",
+        application_id,
+        status,
+        user_id,
+        note,
+        timestamp
+    )
+    .execute(&mut *executor)
+    .await?;
+
+    Ok(())
+}
+
+pub fn build_review_message(
+    application: &CharacterApplication,
+    pokemon: Option<&Pokemon>,
+    history: &[CharacterApplicationStatusChange],
+) -> String {
+    let species = match pokemon {
+        Some(pokemon) => pokemon.name.clone(),
+        None => format!("Unknown Pokemon ({})", application.species_api_id),
+    };
+    let shiny = if application.is_shiny {
+        ", Shiny ✨"
+    } else {
+        ""
+    };
+
+    let mut result = format!(
+        "## 📋 Character Application #{}\n**Player:** {}\n**Name:** {}\n**Species:** {} ({}{})\n",
+        application.id,
+        UserId::new(application.user_id as u64).mention(),
+        application.name,
+        species,
+        application.gender(),
+        shiny
+    );
+
+    if let Some(backstory) = &application.backstory {
+        result.push_str("**Backstory:**\n");
+        for line in backstory.lines() {
+            result.push_str(&format!("> {}\n", line));
+        }
+    }
+
+    result.push_str(&format!("### Status: {}\n", application.status().name()));
+    for change in history {
+        let status = CharacterApplicationStatus::from_repr(change.status)
+            .map(|x| x.name())
+            .unwrap_or("Unknown");
+        result.push_str(&format!(
+            "- <t:{}:f> {} by {}",
+            change.timestamp,
+            status,
+            UserId::new(change.user_id as u64).mention()
+        ));
+        if let Some(note) = &change.note {
+            result.push_str(&format!(": {}", note));
+        }
+        result.push('\n');
+    }
+
+    result
+}
+
+pub fn create_review_buttons(application_id: i64) -> Vec<CreateActionRow> {
+    vec![CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("character-application_{}_approve", application_id))
+            .label("Approve")
+            .style(ButtonStyle::Success),
+        CreateButton::new(format!(
+            "character-application_{}_request-changes",
+            application_id
+        ))
+        .label("Request Changes")
+        .style(ButtonStyle::Secondary),
+        CreateButton::new(format!("character-application_{}_reject", application_id))
+            .label("Reject")
+            .style(ButtonStyle::Danger),
+    ])]
+}
@@ -0,0 +1,320 @@
+use crate::Error;
+use crate::shared::character_stats::{
+    CharacterStat, GenericCharacterStats, SingleCharacterStatType,
+};
+use crate::shared::emoji;
+use crate::shared::enums::{Gender, MysteryDungeonRank, PokemonType};
+use crate::shared::game_data::pokemon::Pokemon;
+use crate::shared::game_data::{GameData, PokemonApiId};
+use crate::shared::guild_settings::get_progression_rules;
+use crate::shared::utility::level_calculations;
+use crate::shared::utility::pixel_font::{GLYPH_HEIGHT, draw_text, measure_text};
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use sqlx::{Pool, Sqlite};
+use std::io::Cursor;
+
+const CARD_WIDTH: u32 = 520;
+const CARD_HEIGHT: u32 = 260;
+const PADDING: u32 = 16;
+const BORDER: u32 = 4;
+const SPRITE_PANEL_SIZE: u32 = 160;
+const SPRITE_SIZE: u32 = 144;
+const INFO_X: u32 = PADDING + SPRITE_PANEL_SIZE + PADDING;
+const SOCIAL_X: u32 = INFO_X + 164;
+const STAT_ROW_Y: u32 = 128;
+const STAT_ROW_HEIGHT: u32 = 24;
+const STAT_LABEL_WIDTH: u32 = 40;
+const DOT_RADIUS: i32 = 5;
+const DOT_SPACING: u32 = 13;
+const MAX_DOTS: i64 = 9;
+
+const BACKGROUND: Rgba<u8> = Rgba([35, 39, 47, 255]);
+const PANEL: Rgba<u8> = Rgba([47, 52, 62, 255]);
+const TEXT: Rgba<u8> = Rgba([240, 240, 240, 255]);
+const TEXT_DIM: Rgba<u8> = Rgba([160, 165, 175, 255]);
+const DOT_FILLED: Rgba<u8> = Rgba([240, 240, 240, 255]);
+const DOT_EMPTY: Rgba<u8> = Rgba([90, 96, 108, 255]);
+const DOT_OVERCHARGED: Rgba<u8> = Rgba([255, 196, 0, 255]);
+
+fn type_color(pokemon_type: &PokemonType) -> Rgba<u8> {
+    match pokemon_type {
+        PokemonType::Normal => Rgba([168, 168, 120, 255]),
+        PokemonType::Fighting => Rgba([192, 48, 40, 255]),
+        PokemonType::Flying => Rgba([168, 144, 240, 255]),
+        PokemonType::Poison => Rgba([160, 64, 160, 255]),
+        PokemonType::Ground => Rgba([224, 192, 104, 255]),
+        PokemonType::Rock => Rgba([184, 160, 56, 255]),
+        PokemonType::Bug => Rgba([168, 184, 32, 255]),
+        PokemonType::Ghost => Rgba([112, 88, 152, 255]),
+        PokemonType::Steel => Rgba([184, 184, 208, 255]),
+        PokemonType::Fire => Rgba([240, 128, 48, 255]),
+        PokemonType::Water => Rgba([104, 144, 240, 255]),
+        PokemonType::Grass => Rgba([120, 200, 80, 255]),
+        PokemonType::Electric => Rgba([248, 208, 48, 255]),
+        PokemonType::Psychic => Rgba([248, 88, 136, 255]),
+        PokemonType::Ice => Rgba([152, 216, 216, 255]),
+        PokemonType::Dragon => Rgba([112, 56, 248, 255]),
+        PokemonType::Dark => Rgba([112, 88, 72, 255]),
+        PokemonType::Fairy => Rgba([238, 153, 172, 255]),
+        PokemonType::Shadow => Rgba([64, 48, 80, 255]),
+    }
+}
+
+fn rank_color(rank: &MysteryDungeonRank) -> Rgba<u8> {
+    match rank {
+        MysteryDungeonRank::Bronze => Rgba([205, 127, 50, 255]),
+        MysteryDungeonRank::Silver => Rgba([192, 192, 192, 255]),
+        MysteryDungeonRank::Gold => Rgba([255, 215, 0, 255]),
+        MysteryDungeonRank::Platinum => Rgba([160, 200, 220, 255]),
+        MysteryDungeonRank::Diamond => Rgba([185, 242, 255, 255]),
+    }
+}
+
+fn rank_name(rank: &MysteryDungeonRank) -> &str {
+    match rank {
+        MysteryDungeonRank::Bronze => "Bronze",
+        MysteryDungeonRank::Silver => "Silver",
+        MysteryDungeonRank::Gold => "Gold",
+        MysteryDungeonRank::Platinum => "Platinum",
+        MysteryDungeonRank::Diamond => "Diamond",
+    }
+}
+
+fn fill_rect(image: &mut RgbaImage, x: u32, y: u32, width: u32, height: u32, color: Rgba<u8>) {
+    for py in y..(y + height).min(image.height()) {
+        for px in x..(x + width).min(image.width()) {
+            image.put_pixel(px, py, color);
+        }
+    }
+}
+
+fn fill_circle(image: &mut RgbaImage, center_x: i32, center_y: i32, radius: i32, color: Rgba<u8>) {
+    for dy in -radius..=radius {
+        for dx in -radius..=radius {
+            if dx * dx + dy * dy > radius * radius {
+                continue;
+            }
+
+            let (px, py) = (center_x + dx, center_y + dy);
+            if px >= 0 && py >= 0 && (px as u32) < image.width() && (py as u32) < image.height() {
+                image.put_pixel(px as u32, py as u32, color);
+            }
+        }
+    }
+}
+
+/// A rectangle with some centered text on top.
+fn draw_badge(
+    image: &mut RgbaImage,
+    text: &str,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    color: Rgba<u8>,
+) {
+    fill_rect(image, x, y, width, height, color);
+    let scale = 2;
+    let text_width = measure_text(text, scale);
+    let text_x = x + width.saturating_sub(text_width) / 2;
+    let text_y = y + height.saturating_sub(GLYPH_HEIGHT * scale) / 2;
+    draw_text(image, text, text_x, text_y, scale, BACKGROUND);
+}
+
+/// Mirrors the dot style used inside the text version of character posts.
+fn draw_stat_row(image: &mut RgbaImage, label: &str, stat: &CharacterStat, x: u32, y: u32) {
+    draw_text(image, label, x, y + 2, 2, TEXT_DIM);
+
+    let dot_count = stat.current.max(stat.species_max).min(MAX_DOTS);
+    for i in 0..dot_count {
+        let color = if i >= stat.species_max {
+            DOT_OVERCHARGED
+        } else if i < stat.current {
+            DOT_FILLED
+        } else {
+            DOT_EMPTY
+        };
+
+        let center_x = (x + STAT_LABEL_WIDTH + i as u32 * DOT_SPACING) as i32 + DOT_RADIUS;
+        let center_y = y as i32 + DOT_RADIUS + 2;
+        fill_circle(image, center_x, center_y, DOT_RADIUS, color);
+    }
+}
+
+fn load_sprite(pokemon: &Pokemon, gender: &Gender, is_shiny: bool) -> Option<RgbaImage> {
+    let emoji_data = emoji::get_emoji_data(pokemon, gender, is_shiny, false).ok()?;
+    let sprite = image::load_from_memory(&emoji_data.data).ok()?;
+    let scale = SPRITE_SIZE as f32 / sprite.width().max(sprite.height()) as f32;
+    // Nearest keeps pixel art crisp when upscaling, but drops too many details when shrinking.
+    let filter = if scale < 1.0 {
+        FilterType::Triangle
+    } else {
+        FilterType::Nearest
+    };
+    Some(
+        sprite
+            .resize(
+                ((sprite.width() as f32 * scale) as u32).max(1),
+                ((sprite.height() as f32 * scale) as u32).max(1),
+                filter,
+            )
+            .to_rgba8(),
+    )
+}
+
+/// Renders a PNG trainer card for the given character.
+pub async fn render_character_card(
+    database: &Pool<Sqlite>,
+    game_data: &GameData,
+    character_id: i64,
+) -> Result<Vec<u8>, Error> {
+    let record = sqlx::query!(
+        "SELECT name, guild_id, experience, money, species_api_id, species_override_for_stats, phenotype, is_shiny, \
+        stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight, \
+        stat_tough, stat_cool, stat_beauty, stat_cute, stat_clever \
+        FROM character WHERE id = ?",
+        character_id
+    )
+    .fetch_one(database)
+    .await?;
+
+    let rules = get_progression_rules(database, record.guild_id).await?;
+    let level = level_calculations::calculate_level_from_experience(&rules, record.experience);
+    let rank = MysteryDungeonRank::from_level(level);
+    let pokemon = game_data
+        .pokemon_by_api_id
+        .get(&PokemonApiId(
+            record
+                .species_api_id
+                .try_into()
+                .expect("Should always be in valid range."),
+        ))
+        .expect("All mons inside the Database should have a valid API ID assigned.");
+    let gender = Gender::from_phenotype(record.phenotype);
+
+    let combat_stats = GenericCharacterStats::from_combat(
+        level_calculations::get_usual_evolution_stage_for_level(
+            &rules,
+            level,
+            pokemon,
+            game_data,
+            record.species_override_for_stats,
+        ),
+        record.stat_strength,
+        record.stat_dexterity,
+        record.stat_vitality,
+        record.stat_special,
+        record.stat_insight,
+    );
+    let social_stats = GenericCharacterStats::from_social(
+        record.stat_tough,
+        record.stat_cool,
+        record.stat_beauty,
+        record.stat_cute,
+        record.stat_clever,
+    );
+
+    let mut card = RgbaImage::from_pixel(CARD_WIDTH, CARD_HEIGHT, type_color(&pokemon.types.type1));
+    fill_rect(
+        &mut card,
+        BORDER,
+        BORDER,
+        CARD_WIDTH - BORDER * 2,
+        CARD_HEIGHT - BORDER * 2,
+        BACKGROUND,
+    );
+
+    fill_rect(
+        &mut card,
+        PADDING,
+        PADDING,
+        SPRITE_PANEL_SIZE,
+        SPRITE_PANEL_SIZE,
+        PANEL,
+    );
+    if let Some(sprite) = load_sprite(pokemon, &gender, record.is_shiny) {
+        let x = PADDING + SPRITE_PANEL_SIZE.saturating_sub(sprite.width()) / 2;
+        let y = PADDING + SPRITE_PANEL_SIZE.saturating_sub(sprite.height()) / 2;
+        image::imageops::overlay(&mut card, &sprite, x as i64, y as i64);
+    }
+
+    let mut badge_y = PADDING + SPRITE_PANEL_SIZE + 10;
+    for pokemon_type in [Some(pokemon.types.type1), pokemon.types.type2]
+        .into_iter()
+        .flatten()
+    {
+        draw_badge(
+            &mut card,
+            &format!("{:?}", pokemon_type),
+            PADDING,
+            badge_y,
+            SPRITE_PANEL_SIZE,
+            22,
+            type_color(&pokemon_type),
+        );
+        badge_y += 26;
+    }
+
+    draw_text(&mut card, &record.name, INFO_X, PADDING, 3, TEXT);
+    draw_text(&mut card, &pokemon.name, INFO_X, PADDING + 30, 2, TEXT_DIM);
+
+    let level_text = format!("Lv. {}", level);
+    draw_text(&mut card, &level_text, INFO_X, 74, 2, TEXT);
+    let rank_x = INFO_X + measure_text(&level_text, 2) + 12;
+    draw_badge(
+        &mut card,
+        rank_name(&rank),
+        rank_x,
+        70,
+        112,
+        22,
+        rank_color(&rank),
+    );
+    draw_text(
+        &mut card,
+        &format!("{} P", record.money),
+        INFO_X,
+        100,
+        2,
+        TEXT,
+    );
+
+    let combat_rows = [
+        ("Str", SingleCharacterStatType::Strength),
+        ("Dex", SingleCharacterStatType::Dexterity),
+        ("Vit", SingleCharacterStatType::Vitality),
+        ("Spe", SingleCharacterStatType::Special),
+        ("Ins", SingleCharacterStatType::Insight),
+    ];
+    let social_rows = [
+        ("Tou", SingleCharacterStatType::Tough),
+        ("Coo", SingleCharacterStatType::Cool),
+        ("Bea", SingleCharacterStatType::Beauty),
+        ("Cut", SingleCharacterStatType::Cute),
+        ("Cle", SingleCharacterStatType::Clever),
+    ];
+    for (i, ((combat_label, combat_stat), (social_label, social_stat))) in
+        combat_rows.iter().zip(social_rows.iter()).enumerate()
+    {
+        let y = STAT_ROW_Y + i as u32 * STAT_ROW_HEIGHT;
+        draw_stat_row(
+            &mut card,
+            combat_label,
+            combat_stats.get(*combat_stat),
+            INFO_X,
+            y,
+        );
+        draw_stat_row(
+            &mut card,
+            social_label,
+            social_stats.get(*social_stat),
+            SOCIAL_X,
+            y,
+        );
+    }
+
+    let mut cursor = Cursor::new(Vec::new());
+    DynamicImage::ImageRgba8(card).write_to(&mut cursor, ImageFormat::Png)?;
+    Ok(cursor.into_inner())
+}
@@ -0,0 +1,165 @@
+use crate::Error;
+use crate::shared::character_stats::GenericCharacterStats;
+use crate::shared::errors::ValidationError;
+use crate::shared::game_data::{GameData, PokemonApiId};
+use crate::shared::guild_settings::get_progression_rules;
+use crate::shared::utility::level_calculations;
+use sqlx::SqliteConnection;
+
+/// A character's HP, Will and Wounds between sessions.
+/// Only the lost amounts are stored, so the max values can still change with the character's stats.
+pub struct CharacterCondition {
+    pub max_hp: i64,
+    pub hp_lost: i64,
+    pub max_will: i64,
+    pub will_lost: i64,
+    pub wounds: i64,
+}
+
+impl CharacterCondition {
+    pub fn new(
+        combat_stats: &GenericCharacterStats,
+        hp_lost: i64,
+        will_lost: i64,
+        wounds: i64,
+    ) -> Self {
+        CharacterCondition {
+            max_hp: combat_stats.calculate_max_hp(),
+            hp_lost,
+            max_will: combat_stats.calculate_max_will(),
+            will_lost,
+            wounds,
+        }
+    }
+
+    pub fn current_hp(&self) -> i64 {
+        (self.max_hp - self.hp_lost).max(0)
+    }
+
+    pub fn current_will(&self) -> i64 {
+        (self.max_will - self.will_lost).max(0)
+    }
+
+    pub fn build_string(&self) -> String {
+        let mut result = format!(
+            "❤️ HP: `{} / {}` | 💠 Will: `{} / {}`",
+            self.current_hp(),
+            self.max_hp,
+            self.current_will(),
+            self.max_will
+        );
+        if self.wounds > 0 {
+            result.push_str(&format!(" | 🩹 Wounds: `{}`", self.wounds));
+        }
+
+        result
+    }
+}
+
+pub async fn get_character_condition(
+    connection: &mut SqliteConnection,
+    game_data: &GameData,
+    character_id: i64,
+) -> Result<CharacterCondition, Error> {
+    let record = sqlx::query!(
+        "SELECT guild_id, experience, species_api_id, species_override_for_stats, \
+        stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight, \
+        hp_lost, will_lost, wounds \
+        FROM character WHERE id = ?",
+        character_id
+    )
+    .fetch_one(&mut *connection)
+    .await?;
+
+    let rules = get_progression_rules(&mut *connection, record.guild_id).await?;
+    let level = level_calculations::calculate_level_from_experience(&rules, record.experience);
+    let pokemon = game_data
+        .pokemon_by_api_id
+        .get(&PokemonApiId(
+            record
+                .species_api_id
+                .try_into()
+                .expect("Should always be in valid range."),
+        ))
+        .expect("All mons inside the Database should have a valid API ID assigned.");
+
+    let combat_stats = GenericCharacterStats::from_combat(
+        level_calculations::get_usual_evolution_stage_for_level(
+            &rules,
+            level,
+            pokemon,
+            game_data,
+            record.species_override_for_stats,
+        ),
+        record.stat_strength,
+        record.stat_dexterity,
+        record.stat_vitality,
+        record.stat_special,
+        record.stat_insight,
+    );
+
+    Ok(CharacterCondition::new(
+        &combat_stats,
+        record.hp_lost,
+        record.will_lost,
+        record.wounds,
+    ))
+}
+
+/// Changes the lost HP and Will as well as the wounds of a character by the given amounts.
+/// Results are clamped so nothing can drop below 0 or above the max. Returns the actually applied changes.
+/// Fails in case the condition has been changed by someone else in the meantime.
+pub async fn change_character_condition(
+    connection: &mut SqliteConnection,
+    game_data: &GameData,
+    character_id: i64,
+    hp_lost_delta: i64,
+    will_lost_delta: i64,
+    wounds_delta: i64,
+) -> Result<ConditionChange, Error> {
+    let condition = get_character_condition(&mut *connection, game_data, character_id).await?;
+
+    let hp_lost = (condition.hp_lost + hp_lost_delta).clamp(0, condition.max_hp.max(0));
+    let will_lost = (condition.will_lost + will_lost_delta).clamp(0, condition.max_will.max(0));
+    let wounds = (condition.wounds + wounds_delta).max(0);
+
+    let result = sqlx::query!(
+        "UPDATE character SET hp_lost = ?, will_lost = ?, wounds = ? \
+        WHERE id = ? AND hp_lost = ? AND will_lost = ? AND wounds = ?",
+        hp_lost,
+        will_lost,
+        wounds,
+        character_id,
+        condition.hp_lost,
+        condition.will_lost,
+        condition.wounds
+    )
+    .execute(connection)
+    .await?;
+
+    if result.rows_affected() != 1 {
+        return Err(Box::new(ValidationError::new(
+            "The character's condition has been changed by someone else while this was running. Please try again!",
+        )));
+    }
+
+    Ok(ConditionChange {
+        hp_lost: hp_lost - condition.hp_lost,
+        will_lost: will_lost - condition.will_lost,
+        wounds: wounds - condition.wounds,
+        condition: CharacterCondition {
+            hp_lost,
+            will_lost,
+            wounds,
+            ..condition
+        },
+    })
+}
+
+/// The result of [change_character_condition].
+pub struct ConditionChange {
+    pub hp_lost: i64,
+    pub will_lost: i64,
+    pub wounds: i64,
+    pub condition: CharacterCondition,
+}
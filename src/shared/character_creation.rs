@@ -0,0 +1,68 @@
+use crate::shared::enums::Gender;
+use crate::shared::game_data::GameData;
+use crate::shared::game_data::pokemon::Pokemon;
+use crate::shared::guild_settings::ProgressionRules;
+use crate::shared::utility::level_calculations;
+use sqlx::SqliteExecutor;
+
+pub const DEFAULT_STARTING_EXPERIENCE: i64 = 0;
+pub const DEFAULT_STARTING_MONEY: i64 = 500;
+
+pub struct NewCharacter<'a> {
+    pub user_id: i64,
+    pub name: String,
+    pub pokemon: &'a Pokemon,
+    pub is_shiny: bool,
+    pub gender: Gender,
+    pub exp: i64,
+    pub money: i64,
+}
+
+/// Persists a new character whose stat post has already been sent into the given message.
+pub async fn insert_new_character(
+    executor: impl SqliteExecutor<'_>,
+    base_data: &GameData,
+    guild_id: i64,
+    stat_channel_id: i64,
+    stat_message_id: i64,
+    rules: &ProgressionRules,
+    character: &NewCharacter<'_>,
+) -> Result<i64, sqlx::Error> {
+    let creation_date = chrono::Utc::now().date_naive();
+    let phenotype = character.gender as i64;
+
+    let level = level_calculations::calculate_level_from_experience(rules, character.exp);
+    let mon = level_calculations::get_usual_evolution_stage_for_level(
+        rules,
+        level,
+        character.pokemon,
+        base_data,
+        None,
+    );
+
+    let record = sqlx::query!(
+        "INSERT INTO character (user_id, guild_id, name, stat_message_id, stat_channel_id, creation_date, experience, money, species_api_id, is_shiny, phenotype,\
+                                stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight
+) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING id",
+        character.user_id,
+        guild_id,
+        character.name,
+        stat_message_id,
+        stat_channel_id,
+        creation_date,
+        character.exp,
+        character.money,
+        character.pokemon.poke_api_id.0,
+        character.is_shiny,
+        phenotype,
+        mon.strength.min,
+        mon.dexterity.min,
+        mon.vitality.min,
+        mon.special.min,
+        mon.insight.min,
+    )
+    .fetch_one(executor)
+    .await?;
+
+    Ok(record.id)
+}
@@ -0,0 +1,311 @@
+use crate::Error;
+use crate::shared::data::Data;
+use crate::shared::enums::{MysteryDungeonRank, Stat};
+use crate::shared::game_data::pokemon::Pokemon;
+use crate::shared::game_data::{GameData, PokemonApiId};
+use crate::shared::guild_settings::get_progression_rules;
+use crate::shared::move_dice_pools::DicePoolStats;
+use crate::shared::utility::level_calculations;
+use crate::shared::{SerenityContext, emoji};
+use serenity::all::{
+    ButtonStyle, CreateActionRow, CreateButton, CreateSelectMenu, CreateSelectMenuKind,
+};
+use serenity::builder::CreateSelectMenuOption;
+use sqlx::{Pool, Sqlite, SqliteExecutor};
+
+const SELECT_MENU_OPTION_LIMIT: usize = 25;
+const SELECT_MENU_ROW_LIMIT: usize = 5;
+
+/// Once the moves don't fit into one message, a row is needed for the buttons to switch between screens.
+fn select_menus_per_screen(page_count: usize) -> usize {
+    if page_count <= SELECT_MENU_ROW_LIMIT {
+        SELECT_MENU_ROW_LIMIT
+    } else {
+        SELECT_MENU_ROW_LIMIT - 1
+    }
+}
+
+/// Moves inherited from pre-evolutions are marked with ¹ or ² inside [Pokemon::moves].
+pub fn strip_pre_evolution_marker(move_name: &str) -> &str {
+    move_name.trim_end_matches('¹').trim_end_matches('²')
+}
+
+pub fn calculate_move_limit(insight: i64) -> i64 {
+    insight + 2
+}
+
+/// Rank moves at or below the character's rank, plus anything a GM unlocked for them.
+pub fn get_selectable_moves(
+    pokemon: &Pokemon,
+    rank: &MysteryDungeonRank,
+    unlocked_moves: &[String],
+) -> Vec<String> {
+    let mut result: Vec<String> = pokemon
+        .moves
+        .by_pokerole_rank
+        .iter()
+        .filter(|x| &x.rank <= rank)
+        .map(|x| strip_pre_evolution_marker(&x.name).to_string())
+        .chain(unlocked_moves.iter().cloned())
+        .collect();
+
+    result.sort();
+    result.dedup_by(|a, b| a.to_lowercase() == b.to_lowercase());
+    result
+}
+
+/// Moves which can only be learned with GM approval.
+pub fn can_be_unlocked(pokemon: &Pokemon, move_name: &str) -> bool {
+    let move_name = move_name.to_lowercase();
+    pokemon
+        .moves
+        .by_machine
+        .iter()
+        .chain(pokemon.moves.by_tutor.iter())
+        .chain(pokemon.moves.by_egg.iter())
+        .any(|x| strip_pre_evolution_marker(x).to_lowercase() == move_name)
+}
+
+pub async fn get_known_moves(
+    executor: impl SqliteExecutor<'_>,
+    character_id: i64,
+) -> Result<Vec<String>, Error> {
+    let records = sqlx::query!(
+        "SELECT move_name FROM character_move WHERE character_id = ? ORDER BY move_name",
+        character_id
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(records.into_iter().map(|x| x.move_name).collect())
+}
+
+pub async fn get_unlocked_moves(
+    executor: impl SqliteExecutor<'_>,
+    character_id: i64,
+) -> Result<Vec<String>, Error> {
+    let records = sqlx::query!(
+        "SELECT move_name FROM character_unlocked_move WHERE character_id = ? ORDER BY move_name",
+        character_id
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(records.into_iter().map(|x| x.move_name).collect())
+}
+
+pub fn build_move_list(
+    game_data: &GameData,
+    known_moves: &[String],
+    stats: &DicePoolStats,
+) -> String {
+    let mut result = String::new();
+    for move_name in known_moves {
+        let Some(m) = game_data.moves.get(&move_name.to_lowercase()) else {
+            result.push_str(&format!("- **{}** – Not Found!\n", move_name));
+            continue;
+        };
+
+        if m.damage1.unwrap_or(Stat::Strength) == Stat::Copy {
+            result.push_str(&format!("- {} **{}** | ACC: Copy\n", m.typing, m.name));
+            continue;
+        }
+
+        let damage = stats.calculate_damage(m);
+        if damage > 0 {
+            result.push_str(&format!(
+                "- {} **{}** | ACC: {} | DMG: {}\n",
+                m.typing,
+                m.name,
+                stats.calculate_accuracy(m),
+                damage
+            ));
+        } else {
+            result.push_str(&format!(
+                "- {} **{}** | ACC: {}\n",
+                m.typing,
+                m.name,
+                stats.calculate_accuracy(m)
+            ));
+        }
+    }
+
+    result
+}
+
+pub struct MoveEditData {
+    pub name: String,
+    pub user_id: i64,
+    pub move_limit: i64,
+    pub known_moves: Vec<String>,
+    pub selectable_moves: Vec<String>,
+}
+
+impl MoveEditData {
+    /// Select menus can only hold a limited amount of options, so we might need to split them up.
+    pub fn get_page(&self, page: usize) -> &[String] {
+        self.selectable_moves
+            .chunks(SELECT_MENU_OPTION_LIMIT)
+            .nth(page)
+            .unwrap_or_default()
+    }
+}
+
+pub async fn get_move_edit_data(
+    database: &Pool<Sqlite>,
+    game_data: &GameData,
+    character_id: i64,
+) -> Result<MoveEditData, Error> {
+    let record = sqlx::query!(
+        "SELECT name, user_id, guild_id, experience, species_api_id, stat_insight FROM character WHERE id = ?",
+        character_id
+    )
+    .fetch_one(database)
+    .await?;
+
+    let rules = get_progression_rules(database, record.guild_id).await?;
+    let level = level_calculations::calculate_level_from_experience(&rules, record.experience);
+    let rank = MysteryDungeonRank::from_level(level);
+    let pokemon = game_data
+        .pokemon_by_api_id
+        .get(&PokemonApiId(
+            record
+                .species_api_id
+                .try_into()
+                .expect("Should always be in valid range."),
+        ))
+        .expect("All mons inside the Database should have a valid API ID assigned.");
+
+    let unlocked_moves = get_unlocked_moves(database, character_id).await?;
+    Ok(MoveEditData {
+        name: record.name,
+        user_id: record.user_id,
+        move_limit: calculate_move_limit(record.stat_insight),
+        known_moves: get_known_moves(database, character_id).await?,
+        selectable_moves: get_selectable_moves(pokemon, &rank, &unlocked_moves),
+    })
+}
+
+/// Shows the screen containing the select menu for the given page.
+pub async fn create_move_edit_message(
+    context: &SerenityContext,
+    bot_data: &Data,
+    game_data: &GameData,
+    character_id: i64,
+    page: usize,
+) -> Result<(String, Vec<CreateActionRow>), Error> {
+    let data = get_move_edit_data(&bot_data.database, game_data, character_id).await?;
+    let emoji = emoji::get_character_emoji(context, &bot_data.database, game_data, character_id)
+        .await
+        .unwrap_or_default();
+
+    let pages: Vec<&[String]> = data
+        .selectable_moves
+        .chunks(SELECT_MENU_OPTION_LIMIT)
+        .collect();
+    let menus_per_screen = select_menus_per_screen(pages.len());
+    let screen_count = pages.len().div_ceil(menus_per_screen).max(1);
+    let screen = (page / menus_per_screen).min(screen_count - 1);
+
+    let mut components = Vec::new();
+    for (page, moves) in pages
+        .iter()
+        .enumerate()
+        .skip(screen * menus_per_screen)
+        .take(menus_per_screen)
+    {
+        let options = moves
+            .iter()
+            .map(|move_name| {
+                let mut option = CreateSelectMenuOption::new(move_name, move_name)
+                    .default_selection(
+                        data.known_moves
+                            .iter()
+                            .any(|x| x.to_lowercase() == move_name.to_lowercase()),
+                    );
+                if let Some(m) = game_data.moves.get(&move_name.to_lowercase()) {
+                    option = option.description(format!(
+                        "{} | {}",
+                        m.typing.name_without_emoji(),
+                        m.category.name_without_emoji()
+                    ));
+                }
+                option
+            })
+            .collect();
+
+        components.push(CreateActionRow::SelectMenu(
+            CreateSelectMenu::new(
+                format!("move-edit_{}_{}", character_id, page),
+                CreateSelectMenuKind::String { options },
+            )
+            .min_values(0)
+            .max_values(moves.len().min(data.move_limit.max(0) as usize) as u8)
+            .placeholder("Select the moves you want to use"),
+        ));
+    }
+
+    if screen_count > 1 {
+        components.push(CreateActionRow::Buttons(vec![
+            CreateButton::new(format!(
+                "move-edit-page_{}_{}",
+                character_id,
+                screen.saturating_sub(1) * menus_per_screen
+            ))
+            .label("Previous")
+            .style(ButtonStyle::Secondary)
+            .disabled(screen == 0),
+            CreateButton::new(format!(
+                "move-edit-page_{}_{}",
+                character_id,
+                (screen + 1) * menus_per_screen
+            ))
+            .label("Next")
+            .style(ButtonStyle::Secondary)
+            .disabled(screen + 1 >= screen_count),
+        ]));
+    }
+
+    let mut content = if data.selectable_moves.is_empty() {
+        format!(
+            "### {}{}\nThere don't seem to be any moves available for this character yet.",
+            emoji, data.name
+        )
+    } else {
+        format!(
+            "### {}{}\n**{} / {}** active moves selected.\n*Moves which aren't learned by rank need to be unlocked by a GM first.*",
+            emoji,
+            data.name,
+            data.known_moves.len(),
+            data.move_limit
+        )
+    };
+    if screen_count > 1 {
+        content.push_str(&format!("\n*Page {} / {}*", screen + 1, screen_count));
+    }
+
+    Ok((content, components))
+}
+
+/// Builds the move list for character posts, or an empty string in case the character doesn't know any moves yet.
+pub async fn build_character_move_section(
+    database: &Pool<Sqlite>,
+    game_data: &GameData,
+    character_id: i64,
+    stats: &DicePoolStats,
+) -> String {
+    let known_moves = get_known_moves(database, character_id)
+        .await
+        .unwrap_or_default();
+    if known_moves.is_empty() {
+        return String::new();
+    }
+
+    let move_limit = calculate_move_limit(stats.insight);
+    format!(
+        "### Moves `({} / {})`\n{}",
+        known_moves.len(),
+        move_limit,
+        build_move_list(game_data, &known_moves, stats)
+    )
+}
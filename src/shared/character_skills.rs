@@ -0,0 +1,124 @@
+use crate::Error;
+use crate::shared::emoji;
+use crate::shared::enums::Skill;
+use sqlx::SqliteExecutor;
+use std::collections::HashMap;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+/// Skills without a row inside character_skill are at rank 0.
+#[derive(Default)]
+pub struct CharacterSkills {
+    ranks: HashMap<Skill, i64>,
+}
+
+impl CharacterSkills {
+    pub fn get(&self, skill: Skill) -> i64 {
+        self.ranks.get(&skill).copied().unwrap_or_default()
+    }
+
+    pub fn calculate_invested_skill_points(&self) -> i64 {
+        self.ranks.values().sum()
+    }
+
+    /// Lists all skills grouped by category, with dots up to the given max rank.
+    pub fn build_string(&self, max_rank: i64) -> String {
+        let mut result = String::new();
+        let mut last_category = "";
+        for skill in Skill::iter() {
+            if skill.category() != last_category {
+                last_category = skill.category();
+                result.push_str(&format!("{}\n", last_category));
+            }
+
+            let rank = self.get(skill);
+            result.push_str(&format!("  {:<10} ", skill.to_string()));
+            for i in 0..rank.max(max_rank) {
+                if i < rank {
+                    result.push(emoji::DOT_FILLED);
+                } else {
+                    result.push(emoji::DOT_EMPTY);
+                }
+            }
+            result.push('\n');
+        }
+
+        result
+    }
+
+    /// Only lists skills with at least one rank, for use within the character post.
+    pub fn build_compact_string(&self) -> String {
+        Skill::iter()
+            .filter(|x| self.get(*x) > 0)
+            .map(|x| format!("{} {}", x, self.get(x)))
+            .collect::<Vec<String>>()
+            .join(" | ")
+    }
+}
+
+fn parse_skill_ranks(records: Vec<(String, i64)>) -> CharacterSkills {
+    let mut ranks = HashMap::new();
+    for (skill, rank) in records {
+        if let Ok(skill) = Skill::from_str(&skill) {
+            ranks.insert(skill, rank);
+        }
+    }
+
+    CharacterSkills { ranks }
+}
+
+pub async fn get_character_skills(
+    executor: impl SqliteExecutor<'_>,
+    character_id: i64,
+) -> Result<CharacterSkills, Error> {
+    let records = sqlx::query!(
+        "SELECT skill, rank FROM character_skill WHERE character_id = ?",
+        character_id
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(parse_skill_ranks(
+        records.into_iter().map(|x| (x.skill, x.rank)).collect(),
+    ))
+}
+
+/// The ranks currently staged inside the character stat editor.
+pub async fn get_character_skill_edits(
+    executor: impl SqliteExecutor<'_>,
+    character_id: i64,
+) -> Result<CharacterSkills, Error> {
+    let records = sqlx::query!(
+        "SELECT skill, edit_rank FROM character_skill WHERE character_id = ?",
+        character_id
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(parse_skill_ranks(
+        records
+            .into_iter()
+            .map(|x| (x.skill, x.edit_rank))
+            .collect(),
+    ))
+}
+
+pub async fn set_skill_edit_rank(
+    executor: impl SqliteExecutor<'_>,
+    character_id: i64,
+    skill: Skill,
+    edit_rank: i64,
+) -> Result<(), Error> {
+    let skill = skill.to_string();
+    sqlx::query!(
+        "INSERT INTO character_skill (character_id, skill, rank, edit_rank) VALUES (?, ?, 0, ?) \
+        ON CONFLICT (character_id, skill) DO UPDATE SET edit_rank = excluded.edit_rank",
+        character_id,
+        skill,
+        edit_rank
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
@@ -1,5 +1,7 @@
 use crate::shared::emoji;
 use crate::shared::game_data::pokemon::{Pokemon, PokemonStat};
+use crate::shared::guild_settings::ProgressionRules;
+use crate::shared::utility::level_calculations;
 use std::cmp::Ordering;
 
 enum CharacterStatType {
@@ -7,7 +9,7 @@ enum CharacterStatType {
     Social,
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, poise::ChoiceParameter)]
 pub enum SingleCharacterStatType {
     Strength,
     Dexterity,
@@ -228,9 +230,23 @@ impl GenericCharacterStats {
         }
     }
 
-    pub fn build_string(&self) -> String {
+    /// Always 0 for social stats.
+    pub fn calculate_max_hp(&self) -> i64 {
         match self.kind {
             CharacterStatType::Combat { base_hp } => {
+                (base_hp as i64 + self.vitality_or_beauty.current) * 2
+            }
+            CharacterStatType::Social => 0,
+        }
+    }
+
+    pub fn calculate_max_will(&self) -> i64 {
+        self.insight_or_clever.current + 2
+    }
+
+    pub fn build_string(&self) -> String {
+        match self.kind {
+            CharacterStatType::Combat { .. } => {
                 format!(
                     "\
 HP: {}
@@ -241,8 +257,8 @@ Defense: {}
 Special Defense: {}
 Active Move Limit: {}
 ",
-                    (base_hp as i64 + self.vitality_or_beauty.current) * 2,
-                    self.insight_or_clever.current + 2,
+                    self.calculate_max_hp(),
+                    self.calculate_max_will(),
                     self.build_stat_block(),
                     (self.vitality_or_beauty.current as f32 * 0.5).ceil(),
                     (self.insight_or_clever.current as f32 * 0.5).ceil(),
@@ -357,16 +373,11 @@ Active Move Limit: {}
         result
     }
 
-    pub fn calculate_invested_stat_points(&self) -> i64 {
-        let limit_breaks = self.count_limit_breaks();
-        let used_extra_points_for_limit_breaks = if limit_breaks > 0 {
-            (limit_breaks * limit_breaks + limit_breaks) / 2
-        } else {
-            0
-        };
-
-        used_extra_points_for_limit_breaks
-            + self.strength_or_tough.invested_points()
+    pub fn calculate_invested_stat_points(&self, rules: &ProgressionRules) -> i64 {
+        level_calculations::calculate_extra_points_used_for_limit_breaks(
+            rules,
+            self.count_limit_breaks(),
+        ) + self.strength_or_tough.invested_points()
             + self.insight_or_clever.invested_points()
             + self.special_or_cute.invested_points()
             + self.vitality_or_beauty.invested_points()
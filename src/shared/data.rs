@@ -1,5 +1,5 @@
-use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 
 use sqlx::{Pool, Sqlite};
 
@@ -13,6 +13,8 @@ pub struct Data {
     pub is_backup_thread_running: AtomicBool,
     pub is_weekly_reset_thread_running: AtomicBool,
     pub is_monthly_reset_thread_running: AtomicBool,
+    pub is_quest_reminder_thread_running: AtomicBool,
+    pub is_recurring_quest_thread_running: AtomicBool,
 }
 
 impl Data {
@@ -24,6 +26,8 @@ impl Data {
             is_backup_thread_running: AtomicBool::new(false),
             is_weekly_reset_thread_running: AtomicBool::new(false),
             is_monthly_reset_thread_running: AtomicBool::new(false),
+            is_quest_reminder_thread_running: AtomicBool::new(false),
+            is_recurring_quest_thread_running: AtomicBool::new(false),
         };
 
         result
@@ -1,13 +1,13 @@
 use crate::shared::constants;
-use crate::shared::data::Data;
 use crate::shared::enums::{Gender, PokemonType, RegionalVariant};
 use crate::shared::game_data::pokemon::Pokemon;
-use crate::shared::game_data::PokemonApiId;
+use crate::shared::game_data::{GameData, PokemonApiId};
 use crate::shared::utility::error_handling;
 use crate::Error;
 use image::{DynamicImage, GenericImageView, ImageFormat};
+use log::info;
 use rand::Rng;
-use serenity::all::{CreateAttachment, CreateMessage, Emoji};
+use serenity::all::{CreateAttachment, CreateMessage, Emoji, GuildId};
 use sqlx::{Pool, Sqlite};
 use std::fs::File;
 use std::io::{BufReader, Cursor, Read, Seek};
@@ -39,27 +39,28 @@ pub const DOT_OVERCHARGED: char = '⧳';
 
 pub async fn get_character_emoji(
     context: &serenity::all::Context,
-    data: &Data,
+    database: &Pool<Sqlite>,
+    game_data: &GameData,
     character_id: i64,
 ) -> Option<String> {
     let result = sqlx::query!(
         "SELECT guild_id, species_api_id, is_shiny, phenotype FROM character WHERE id = ?",
         character_id
     )
-    .fetch_one(&data.database)
+    .fetch_one(database)
     .await;
 
     if let Ok(record) = result {
         let gender = Gender::from_phenotype(record.phenotype);
         let api_id = PokemonApiId(record.species_api_id as u16);
-        let Some(pokemon) = data.game.base_data.pokemon_by_api_id.get(&api_id) else {
+        let Some(pokemon) = game_data.pokemon_by_api_id.get(&api_id) else {
             error_handling::log_error(context, format!("DB species ID should always be set, but was unable to find a pokemon for api_id {api_id:?}!")).await;
             return None;
         };
 
         get_pokemon_emoji(
             context,
-            &data.database,
+            database,
             record.guild_id,
             pokemon,
             &gender,
@@ -357,6 +358,133 @@ pub fn get_emoji_data(
     })
 }
 
+pub async fn store_emoji_in_database(
+    database: &Pool<Sqlite>,
+    guild_id: GuildId,
+    emoji: &Emoji,
+    pokemon: &Pokemon,
+    gender: &Gender,
+    is_shiny: bool,
+    is_animated: bool,
+) {
+    let guild_id = guild_id.get() as i64;
+    let api_id = pokemon.poke_api_id.0 as i64;
+    let is_female = pokemon.species_data.has_gender_differences && gender == &Gender::Female;
+    let discord_string = emoji.to_string();
+    match sqlx::query!("INSERT INTO emoji (species_api_id, guild_id, is_female, is_shiny, is_animated, discord_string) VALUES (?, ?, ?, ?, ?, ?)", api_id, guild_id, is_female, is_shiny, is_animated, discord_string).execute(database).await {
+        Ok(_) => {}
+        Err(e) => {info!("{:?}", e);}
+    };
+}
+
+pub async fn does_emoji_exist_in_database(
+    database: &Pool<Sqlite>,
+    guild_id: i64,
+    pokemon: &Pokemon,
+    gender: &Gender,
+    is_shiny: bool,
+    is_animated: bool,
+) -> bool {
+    let api_id = pokemon.poke_api_id.0 as i64;
+    let is_female = pokemon.species_data.has_gender_differences && gender == &Gender::Female;
+
+    let result = sqlx::query!("SELECT COUNT(*) as count FROM emoji WHERE species_api_id = ? AND guild_id = ? AND is_female = ? AND is_shiny = ? AND is_animated = ?", api_id, guild_id, is_female, is_shiny, is_animated)
+        .fetch_one(database)
+        .await;
+
+    if let Ok(result) = result {
+        result.count > 0
+    } else {
+        false
+    }
+}
+
+async fn upload_emoji_to_guild(
+    context: &serenity::all::Context,
+    guild_id: GuildId,
+    emoji_data: EmojiData,
+) -> Result<Emoji, serenity::all::Error> {
+    let attachment = CreateAttachment::bytes(emoji_data.data, &emoji_data.name);
+    guild_id
+        .create_emoji(context, emoji_data.name.as_str(), &attachment.to_base64())
+        .await
+}
+
+async fn create_guild_emoji(
+    context: &serenity::all::Context,
+    database: &Pool<Sqlite>,
+    guild_id: GuildId,
+    pokemon: &Pokemon,
+    gender: &Gender,
+    is_shiny: bool,
+    is_animated: bool,
+) -> Result<Emoji, String> {
+    let emoji_data = match get_emoji_data(pokemon, gender, is_shiny, is_animated) {
+        Ok(emoji_data) => emoji_data,
+        Err(e) => {
+            return Err(format!(
+                "Something went wrong when parsing the emoji: {:?}",
+                e
+            ));
+        }
+    };
+
+    match upload_emoji_to_guild(context, guild_id, emoji_data).await {
+        Ok(emoji) => {
+            store_emoji_in_database(
+                database,
+                guild_id,
+                &emoji,
+                pokemon,
+                gender,
+                is_shiny,
+                is_animated,
+            )
+            .await;
+            Ok(emoji)
+        }
+        // Server is probably at emoji capacity. Too bad!
+        Err(e) => Err(format!(
+            "Something went wrong when uploading the emoji to discord: {:?}",
+            e
+        )),
+    }
+}
+
+/// Uploads the still and animated emojis for this pokemon to the guild, unless they already exist.
+/// Returns one result per attempted upload.
+pub async fn create_missing_guild_emojis(
+    context: &serenity::all::Context,
+    database: &Pool<Sqlite>,
+    guild_id: GuildId,
+    pokemon: &Pokemon,
+    gender: &Gender,
+    is_shiny: bool,
+) -> Vec<Result<Emoji, String>> {
+    let mut results = Vec::new();
+    let guild_id_i64 = guild_id.get() as i64;
+    if !does_emoji_exist_in_database(database, guild_id_i64, pokemon, gender, is_shiny, false).await
+    {
+        results.push(
+            create_guild_emoji(
+                context, database, guild_id, pokemon, gender, is_shiny, false,
+            )
+            .await,
+        );
+    }
+
+    if pokemon.has_animated_sprite()
+        && !does_emoji_exist_in_database(database, guild_id_i64, pokemon, gender, is_shiny, true)
+            .await
+    {
+        results.push(
+            create_guild_emoji(context, database, guild_id, pokemon, gender, is_shiny, true).await,
+        );
+    }
+
+    results
+}
+
 pub async fn upload_emoji_to_application(
     ctx: &serenity::all::Context,
     emoji_data: EmojiData,
@@ -58,7 +58,9 @@ impl Display for PokemonType {
     }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, EnumString, Hash, EnumIter)]
+#[derive(
+    Debug, Clone, Copy, Deserialize, PartialEq, Eq, EnumString, Hash, EnumIter, ChoiceParameter,
+)]
 #[strum(ascii_case_insensitive)]
 pub enum PokemonTypeWithoutShadow {
     Normal,
@@ -82,6 +84,29 @@ pub enum PokemonTypeWithoutShadow {
 }
 
 impl PokemonTypeWithoutShadow {
+    pub fn as_pokemon_type(&self) -> PokemonType {
+        match self {
+            PokemonTypeWithoutShadow::Normal => PokemonType::Normal,
+            PokemonTypeWithoutShadow::Fighting => PokemonType::Fighting,
+            PokemonTypeWithoutShadow::Flying => PokemonType::Flying,
+            PokemonTypeWithoutShadow::Poison => PokemonType::Poison,
+            PokemonTypeWithoutShadow::Ground => PokemonType::Ground,
+            PokemonTypeWithoutShadow::Rock => PokemonType::Rock,
+            PokemonTypeWithoutShadow::Bug => PokemonType::Bug,
+            PokemonTypeWithoutShadow::Ghost => PokemonType::Ghost,
+            PokemonTypeWithoutShadow::Steel => PokemonType::Steel,
+            PokemonTypeWithoutShadow::Fire => PokemonType::Fire,
+            PokemonTypeWithoutShadow::Water => PokemonType::Water,
+            PokemonTypeWithoutShadow::Grass => PokemonType::Grass,
+            PokemonTypeWithoutShadow::Electric => PokemonType::Electric,
+            PokemonTypeWithoutShadow::Psychic => PokemonType::Psychic,
+            PokemonTypeWithoutShadow::Ice => PokemonType::Ice,
+            PokemonTypeWithoutShadow::Dragon => PokemonType::Dragon,
+            PokemonTypeWithoutShadow::Dark => PokemonType::Dark,
+            PokemonTypeWithoutShadow::Fairy => PokemonType::Fairy,
+        }
+    }
+
     pub fn get_names_vec() -> Vec<String> {
         vec![
             String::from("Normal"),
@@ -230,6 +255,31 @@ impl fmt::Display for MoveType {
 }
 
 impl MoveType {
+    pub fn name_without_emoji(&self) -> &str {
+        match self {
+            MoveType::Normal => "Normal",
+            MoveType::Fighting => "Fighting",
+            MoveType::Flying => "Flying",
+            MoveType::Poison => "Poison",
+            MoveType::Ground => "Ground",
+            MoveType::Rock => "Rock",
+            MoveType::Bug => "Bug",
+            MoveType::Ghost => "Ghost",
+            MoveType::Steel => "Steel",
+            MoveType::Fire => "Fire",
+            MoveType::Water => "Water",
+            MoveType::Grass => "Grass",
+            MoveType::Electric => "Electric",
+            MoveType::Psychic => "Psychic",
+            MoveType::Ice => "Ice",
+            MoveType::Dragon => "Dragon",
+            MoveType::Dark => "Dark",
+            MoveType::Fairy => "Fairy",
+            MoveType::Any => "Any",
+            MoveType::None => "Typeless",
+        }
+    }
+
     pub fn has_stab(&self, poke_type: &Option<PokemonType>) -> bool {
         if let Some(poke_type) = poke_type {
             match poke_type {
@@ -270,6 +320,17 @@ pub enum MoveCategory {
     Support,
 }
 
+impl MoveCategory {
+    pub fn name_without_emoji(&self) -> &str {
+        match self {
+            MoveCategory::Physical => "Physical",
+            MoveCategory::Special => "Special",
+            MoveCategory::PhysicalOrSpecial => "Physical / Special",
+            MoveCategory::Support => "Support",
+        }
+    }
+}
+
 impl fmt::Display for MoveCategory {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.write_str(match self {
@@ -281,13 +342,14 @@ impl fmt::Display for MoveCategory {
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Deserialize)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Deserialize, ChoiceParameter, FromRepr)]
+#[repr(i64)]
 pub enum MysteryDungeonRank {
-    Bronze,
-    Silver,
-    Gold,
-    Platinum,
-    Diamond,
+    Bronze = 1,
+    Silver = 2,
+    Gold = 3,
+    Platinum = 4,
+    Diamond = 5,
 }
 
 impl Display for MysteryDungeonRank {
@@ -323,9 +385,9 @@ impl MysteryDungeonRank {
         }
     }
 
-    pub fn from_level(level: u8) -> Self {
+    pub fn from_level(level: i64) -> Self {
         match level {
-            1 => MysteryDungeonRank::Bronze,
+            ..=1 => MysteryDungeonRank::Bronze,
             2..=3 => MysteryDungeonRank::Silver,
             4..=7 => MysteryDungeonRank::Gold,
             8..=15 => MysteryDungeonRank::Platinum,
@@ -333,6 +395,17 @@ impl MysteryDungeonRank {
         }
     }
 
+    /// Exploration teams rank up through the quests their members complete, rather than through levels.
+    pub fn from_team_rank_points(rank_points: i64) -> Self {
+        match rank_points {
+            ..50 => MysteryDungeonRank::Bronze,
+            50..150 => MysteryDungeonRank::Silver,
+            150..300 => MysteryDungeonRank::Gold,
+            300..500 => MysteryDungeonRank::Platinum,
+            _ => MysteryDungeonRank::Diamond,
+        }
+    }
+
     pub fn emoji_string(&self) -> &str {
         match self {
             MysteryDungeonRank::Bronze => emoji::RANK_BRONZE,
@@ -343,17 +416,65 @@ impl MysteryDungeonRank {
         }
     }
 
-    pub fn social_stat_points(&self) -> u8 {
+    pub fn skill_points(&self) -> u8 {
         match self {
-            MysteryDungeonRank::Bronze => 4,
-            MysteryDungeonRank::Silver => 4 + 2,
-            MysteryDungeonRank::Gold => 4 + 4,
-            MysteryDungeonRank::Platinum => 4 + 6,
-            MysteryDungeonRank::Diamond => 4 + 8,
+            MysteryDungeonRank::Bronze => 5,
+            MysteryDungeonRank::Silver => 5 + 4,
+            MysteryDungeonRank::Gold => 5 + 4 + 3,
+            MysteryDungeonRank::Platinum => 5 + 4 + 3 + 2,
+            MysteryDungeonRank::Diamond => 5 + 4 + 3 + 2 + 1,
+        }
+    }
+
+    pub fn max_skill_rank(&self) -> u8 {
+        match self {
+            MysteryDungeonRank::Bronze => 1,
+            MysteryDungeonRank::Silver => 2,
+            MysteryDungeonRank::Gold => 3,
+            MysteryDungeonRank::Platinum => 4,
+            MysteryDungeonRank::Diamond => 5,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, EnumString, EnumIter, ChoiceParameter)]
+#[strum(ascii_case_insensitive)]
+pub enum Skill {
+    Brawl,
+    Channel,
+    Clash,
+    Evasion,
+    Alert,
+    Athletic,
+    Nature,
+    Stealth,
+    Allure,
+    Etiquette,
+    Intimidate,
+    Perform,
+    Crafts,
+    Lore,
+    Medicine,
+    Science,
+}
+
+impl Skill {
+    pub fn category(&self) -> &'static str {
+        match self {
+            Skill::Brawl | Skill::Channel | Skill::Clash | Skill::Evasion => "Fight",
+            Skill::Alert | Skill::Athletic | Skill::Nature | Skill::Stealth => "Survival",
+            Skill::Allure | Skill::Etiquette | Skill::Intimidate | Skill::Perform => "Social",
+            Skill::Crafts | Skill::Lore | Skill::Medicine | Skill::Science => "Knowledge",
+        }
+    }
+}
+
+impl Display for Skill {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize)]
 pub enum RegionalVariant {
     Alola,
@@ -563,4 +684,26 @@ pub enum QuestParticipantSelectionMechanism {
     #[name = "GM Picks"]
     GMPicks = 2,
     Random = 3,
+    #[name = "Weighted Random"]
+    WeightedRandom = 4,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::Type, FromRepr)]
+#[repr(i64)]
+pub enum CharacterApplicationStatus {
+    Pending = 1,
+    ChangesRequested = 2,
+    Approved = 3,
+    Rejected = 4,
+}
+
+impl CharacterApplicationStatus {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CharacterApplicationStatus::Pending => "⏳ Pending",
+            CharacterApplicationStatus::ChangesRequested => "📝 Changes Requested",
+            CharacterApplicationStatus::Approved => "✅ Approved",
+            CharacterApplicationStatus::Rejected => "❌ Rejected",
+        }
+    }
 }
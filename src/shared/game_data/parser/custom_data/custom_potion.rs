@@ -4,4 +4,7 @@ pub struct CustomPotion {
     pub description: String,
     pub effect: String,
     pub recipes: Vec<String>,
+    pub health_restored: Option<u8>,
+    #[serde(default)]
+    pub restores_full_health: bool,
 }
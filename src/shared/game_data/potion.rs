@@ -7,6 +7,9 @@ pub struct Potion {
     pub description: String,
     pub effect: String,
     pub recipes: String,
+    pub health_restored: Option<u8>,
+    /// For potions like Max Potion, which heal all HP instead of a fixed amount.
+    pub restores_full_health: bool,
 }
 
 impl Potion {
@@ -28,6 +31,8 @@ impl Potion {
             description: raw.description,
             effect: raw.effect,
             recipes: Potion::parse_recipes(&raw.recipes),
+            health_restored: raw.health_restored,
+            restores_full_health: raw.restores_full_health,
         }
     }
 
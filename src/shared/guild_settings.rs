@@ -0,0 +1,119 @@
+use crate::Error;
+use crate::shared::enums::MysteryDungeonRank;
+use sqlx::SqliteExecutor;
+
+/// Prices and limits which can be adjusted per server through /guild_settings.
+pub struct GuildEconomySettings {
+    pub backpack_upgrade_base_price: i64,
+    pub backpack_upgrade_price_increase: i64,
+    pub hidden_ability_unlock_price: i64,
+    pub default_backpack_slots: i64,
+    pub maximum_backpack_slots: Option<i64>,
+}
+
+impl Default for GuildEconomySettings {
+    fn default() -> Self {
+        GuildEconomySettings {
+            backpack_upgrade_base_price: 500,
+            backpack_upgrade_price_increase: 500,
+            hidden_ability_unlock_price: 2000,
+            default_backpack_slots: 6,
+            maximum_backpack_slots: None,
+        }
+    }
+}
+
+impl GuildEconomySettings {
+    pub fn backpack_upgrade_price(&self, backpack_upgrade_count: i64) -> i64 {
+        self.backpack_upgrade_base_price
+            + self.backpack_upgrade_price_increase * backpack_upgrade_count
+    }
+
+    pub fn backpack_slots(&self, backpack_upgrade_count: i64) -> i64 {
+        self.default_backpack_slots + backpack_upgrade_count
+    }
+}
+
+/// Falls back to the default values for servers which haven't been set up yet.
+pub async fn get_guild_economy_settings(
+    executor: impl SqliteExecutor<'_>,
+    guild_id: i64,
+) -> Result<GuildEconomySettings, Error> {
+    let settings = sqlx::query_as!(
+        GuildEconomySettings,
+        "SELECT backpack_upgrade_base_price, backpack_upgrade_price_increase, hidden_ability_unlock_price, \
+        default_backpack_slots, maximum_backpack_slots FROM guild WHERE id = ?",
+        guild_id
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(settings.unwrap_or_default())
+}
+
+/// Rules for leveling, stat points and evolutions which can be adjusted per server through /guild_settings.
+#[derive(Clone)]
+pub struct ProgressionRules {
+    pub experience_per_level: i64,
+    pub base_combat_points: i64,
+    pub combat_points_per_level: i64,
+    pub limit_break_base_cost: i64,
+    pub limit_break_cost_increase: i64,
+    pub first_evolution_level: i64,
+    pub second_evolution_level: i64,
+    pub social_points_bronze: i64,
+    pub social_points_silver: i64,
+    pub social_points_gold: i64,
+    pub social_points_platinum: i64,
+    pub social_points_diamond: i64,
+}
+
+impl Default for ProgressionRules {
+    fn default() -> Self {
+        ProgressionRules {
+            experience_per_level: 100,
+            base_combat_points: 3,
+            combat_points_per_level: 1,
+            limit_break_base_cost: 2,
+            limit_break_cost_increase: 1,
+            first_evolution_level: 3,
+            second_evolution_level: 6,
+            social_points_bronze: 4,
+            social_points_silver: 6,
+            social_points_gold: 8,
+            social_points_platinum: 10,
+            social_points_diamond: 12,
+        }
+    }
+}
+
+impl ProgressionRules {
+    pub fn social_stat_points(&self, rank: &MysteryDungeonRank) -> i64 {
+        match rank {
+            MysteryDungeonRank::Bronze => self.social_points_bronze,
+            MysteryDungeonRank::Silver => self.social_points_silver,
+            MysteryDungeonRank::Gold => self.social_points_gold,
+            MysteryDungeonRank::Platinum => self.social_points_platinum,
+            MysteryDungeonRank::Diamond => self.social_points_diamond,
+        }
+    }
+}
+
+/// Falls back to the default rules for servers which haven't been set up yet.
+pub async fn get_progression_rules(
+    executor: impl SqliteExecutor<'_>,
+    guild_id: i64,
+) -> Result<ProgressionRules, Error> {
+    let rules = sqlx::query_as!(
+        ProgressionRules,
+        "SELECT experience_per_level, base_combat_points, combat_points_per_level, limit_break_base_cost, \
+        limit_break_cost_increase, first_evolution_level, second_evolution_level, social_points_bronze, \
+        social_points_silver, social_points_gold, social_points_platinum, social_points_diamond \
+        FROM guild WHERE id = ?",
+        guild_id
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(rules.unwrap_or_default())
+}
@@ -0,0 +1,228 @@
+use crate::Error;
+use crate::shared::errors::{DatabaseError, ValidationError};
+use crate::shared::game_data::GameData;
+use sqlx::{SqliteConnection, SqliteExecutor};
+
+/// How much HP an item restores when it's being used.
+pub enum HealthRestoration {
+    None,
+    Amount(u8),
+    Full,
+}
+
+/// Anything from [GameData::items] or [GameData::potions] that can be put into a backpack.
+pub struct InventoryItem {
+    pub name: String,
+    pub single_use: bool,
+    pub health_restored: Option<u8>,
+    pub restores_full_health: bool,
+    pub effect: Option<String>,
+}
+
+impl InventoryItem {
+    pub fn find(game_data: &GameData, name: &str) -> Option<Self> {
+        let lowercase_name = name.to_lowercase();
+        if let Some(item) = game_data.items.get(&lowercase_name) {
+            return Some(InventoryItem {
+                name: item.name.clone(),
+                single_use: item.single_use,
+                health_restored: item.health_restored,
+                restores_full_health: false,
+                effect: None,
+            });
+        }
+
+        game_data
+            .potions
+            .get(&lowercase_name)
+            .map(|potion| InventoryItem {
+                name: potion.name.clone(),
+                single_use: true,
+                health_restored: potion.health_restored,
+                restores_full_health: potion.restores_full_health,
+                effect: Some(potion.effect.clone()),
+            })
+    }
+
+    pub fn health_restoration(&self) -> HealthRestoration {
+        if self.restores_full_health {
+            return HealthRestoration::Full;
+        }
+
+        match self.health_restored {
+            Some(amount) => HealthRestoration::Amount(amount),
+            None => HealthRestoration::None,
+        }
+    }
+
+    /// Single use items (berries, potions, seeds...) stack within one slot, everything else needs a slot per item.
+    pub fn is_stackable(&self) -> bool {
+        self.single_use
+    }
+
+    fn required_slots(&self, amount: i64) -> i64 {
+        if self.is_stackable() { 1 } else { amount }
+    }
+}
+
+pub struct InventoryEntry {
+    pub item_name: String,
+    pub amount: i64,
+}
+
+pub async fn get_inventory(
+    executor: impl SqliteExecutor<'_>,
+    character_id: i64,
+) -> Result<Vec<InventoryEntry>, Error> {
+    let records = sqlx::query_as!(
+        InventoryEntry,
+        "SELECT item_name, amount FROM character_inventory WHERE character_id = ? ORDER BY item_name",
+        character_id
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(records)
+}
+
+pub async fn get_backpack_slots(
+    executor: impl SqliteExecutor<'_>,
+    character_id: i64,
+) -> Result<i64, Error> {
+    let record = sqlx::query!(
+        "SELECT character.backpack_upgrade_count + guild.default_backpack_slots AS \"slots!: i64\" \
+        FROM character INNER JOIN guild ON guild.id = character.guild_id WHERE character.id = ?",
+        character_id
+    )
+    .fetch_one(executor)
+    .await?;
+
+    Ok(record.slots)
+}
+
+/// Items which can't be found within the game data anymore are treated like stackables.
+pub fn count_used_slots(game_data: &GameData, inventory: &[InventoryEntry]) -> i64 {
+    inventory
+        .iter()
+        .map(
+            |entry| match InventoryItem::find(game_data, &entry.item_name) {
+                Some(item) => item.required_slots(entry.amount),
+                None => 1,
+            },
+        )
+        .sum()
+}
+
+/// Adds the item to the character's inventory, failing in case their backpack would overflow.
+pub async fn add_item(
+    connection: &mut SqliteConnection,
+    game_data: &GameData,
+    character_id: i64,
+    character_name: &str,
+    item: &InventoryItem,
+    amount: i64,
+) -> Result<(), Error> {
+    let inventory = get_inventory(&mut *connection, character_id).await?;
+    let available_slots = get_backpack_slots(&mut *connection, character_id).await?;
+    let used_slots = count_used_slots(game_data, &inventory);
+    let already_has_stack = inventory
+        .iter()
+        .any(|x| x.item_name.to_lowercase() == item.name.to_lowercase());
+
+    let required_slots = if item.is_stackable() && already_has_stack {
+        0
+    } else {
+        item.required_slots(amount)
+    };
+
+    if used_slots + required_slots > available_slots {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{}'s backpack is too full!**\n*Adding {}x {} would require {} more slot(s), but only {} of {} slots are free.*",
+            character_name,
+            amount,
+            item.name,
+            required_slots,
+            (available_slots - used_slots).max(0),
+            available_slots
+        ))));
+    }
+
+    sqlx::query!(
+        "INSERT INTO character_inventory (character_id, item_name, amount) VALUES (?, ?, ?) \
+        ON CONFLICT (character_id, item_name) DO UPDATE SET amount = amount + excluded.amount",
+        character_id,
+        item.name,
+        amount
+    )
+    .execute(connection)
+    .await?;
+
+    Ok(())
+}
+
+/// Removes the item from the character's inventory, failing in case they don't own enough of it.
+pub async fn remove_item(
+    connection: &mut SqliteConnection,
+    character_id: i64,
+    character_name: &str,
+    item_name: &str,
+    amount: i64,
+) -> Result<(), Error> {
+    let owned_amount = sqlx::query!(
+        "SELECT amount FROM character_inventory WHERE character_id = ? AND item_name = ?",
+        character_id,
+        item_name
+    )
+    .fetch_optional(&mut *connection)
+    .await?
+    .map(|x| x.amount)
+    .unwrap_or(0);
+
+    if owned_amount < amount {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{} only has {}x {} in their backpack.**",
+            character_name, owned_amount, item_name
+        ))));
+    }
+
+    let result = if owned_amount == amount {
+        sqlx::query!(
+            "DELETE FROM character_inventory WHERE character_id = ? AND item_name = ?",
+            character_id,
+            item_name
+        )
+        .execute(connection)
+        .await?
+    } else {
+        sqlx::query!(
+            "UPDATE character_inventory SET amount = amount - ? WHERE character_id = ? AND item_name = ? AND amount = ?",
+            amount,
+            character_id,
+            item_name,
+            owned_amount
+        )
+        .execute(connection)
+        .await?
+    };
+
+    if result.rows_affected() != 1 {
+        return Err(Box::new(DatabaseError::new(format!(
+            "Was unable to remove {}x {} from {}.",
+            amount, item_name, character_name
+        ))));
+    }
+
+    Ok(())
+}
+
+pub fn build_inventory_string(inventory: &[InventoryEntry]) -> String {
+    if inventory.is_empty() {
+        return String::from("*Empty*");
+    }
+
+    inventory
+        .iter()
+        .map(|x| format!("- {}x {}", x.amount, x.item_name))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -4,6 +4,12 @@ use crate::shared::data::Data;
 pub mod action_log;
 pub mod cache;
 pub mod character;
+pub mod character_application;
+pub mod character_card;
+pub mod character_condition;
+pub mod character_creation;
+pub mod character_moves;
+pub mod character_skills;
 pub mod character_stats;
 pub mod clunky_stuff;
 pub mod constants;
@@ -14,9 +20,23 @@ pub mod emoji;
 pub mod enums;
 pub mod errors;
 pub mod game_data;
+pub mod guild_settings;
+pub mod inventory;
 pub mod metronome;
+pub mod move_dice_pools;
 pub mod permissions;
+pub mod quest_archive;
+pub mod quest_board;
+pub mod quest_creation;
+pub mod quest_gm;
+pub mod quest_poll;
+pub mod quest_restrictions;
+pub mod quest_reward;
+pub mod quest_signup;
+pub mod quest_template;
+pub mod quest_waitlist;
 pub mod retire_character;
+pub mod team;
 pub mod utility;
 
 pub type PoiseContext<'a> = poise::Context<'a, Data, Error>;
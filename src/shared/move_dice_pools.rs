@@ -0,0 +1,126 @@
+use crate::shared::character_stats::{GenericCharacterStats, SingleCharacterStatType};
+use crate::shared::enums::{CombatOrSocialStat, MysteryDungeonRank, Stat};
+use crate::shared::game_data::r#move::Move;
+
+/// The stats which are relevant for a move's accuracy and damage dice pools.
+pub struct DicePoolStats {
+    pub rank: MysteryDungeonRank,
+    pub hp: i64,
+    pub strength: i64,
+    pub dexterity: i64,
+    pub vitality: i64,
+    pub special: i64,
+    pub insight: i64,
+    pub tough: i64,
+    pub cool: i64,
+    pub beauty: i64,
+    pub clever: i64,
+    pub cute: i64,
+}
+
+impl DicePoolStats {
+    pub fn from_character_stats(
+        combat_stats: &GenericCharacterStats,
+        social_stats: &GenericCharacterStats,
+        rank: MysteryDungeonRank,
+        base_hp: u8,
+    ) -> Self {
+        let combat = |stat| combat_stats.get(stat).current;
+        let social = |stat| social_stats.get(stat).current;
+        let vitality = combat(SingleCharacterStatType::Vitality);
+        DicePoolStats {
+            rank,
+            hp: (base_hp as i64 + vitality) * 2,
+            strength: combat(SingleCharacterStatType::Strength),
+            dexterity: combat(SingleCharacterStatType::Dexterity),
+            vitality,
+            special: combat(SingleCharacterStatType::Special),
+            insight: combat(SingleCharacterStatType::Insight),
+            tough: social(SingleCharacterStatType::Tough),
+            cool: social(SingleCharacterStatType::Cool),
+            beauty: social(SingleCharacterStatType::Beauty),
+            clever: social(SingleCharacterStatType::Clever),
+            cute: social(SingleCharacterStatType::Cute),
+        }
+    }
+
+    pub fn get_die_count_for_stat(&self, stat: CombatOrSocialStat) -> i64 {
+        match stat {
+            CombatOrSocialStat::Strength => self.strength,
+            CombatOrSocialStat::Dexterity => self.dexterity,
+            CombatOrSocialStat::Vitality => self.vitality,
+            CombatOrSocialStat::Special => self.special,
+            CombatOrSocialStat::Insight => self.insight,
+            CombatOrSocialStat::VitalityOrInsight => self.insight.max(self.vitality),
+            CombatOrSocialStat::HP => self.hp,
+            CombatOrSocialStat::Tough => self.tough,
+            CombatOrSocialStat::Cool => self.cool,
+            CombatOrSocialStat::Beauty => self.beauty,
+            CombatOrSocialStat::Clever => self.clever,
+            CombatOrSocialStat::Cute => self.cute,
+            CombatOrSocialStat::Copied => 0,
+            CombatOrSocialStat::ToughOrCute => self.tough.max(self.cute),
+            CombatOrSocialStat::MissingBeauty => 5 - self.beauty,
+            CombatOrSocialStat::Brawl
+            | CombatOrSocialStat::Channel
+            | CombatOrSocialStat::Clash
+            | CombatOrSocialStat::Evasion
+            | CombatOrSocialStat::Alert
+            | CombatOrSocialStat::Athletic
+            | CombatOrSocialStat::Nature
+            | CombatOrSocialStat::Stealth
+            | CombatOrSocialStat::Allure
+            | CombatOrSocialStat::Etiquette
+            | CombatOrSocialStat::Intimidate
+            | CombatOrSocialStat::Perform
+            | CombatOrSocialStat::Will
+            | CombatOrSocialStat::BrawlOrChannel
+            | CombatOrSocialStat::Varies
+            | CombatOrSocialStat::Medicine
+            | CombatOrSocialStat::Empathy
+            | CombatOrSocialStat::Rank => self.rank.die_count() as i64,
+        }
+    }
+
+    pub fn get_stat(&self, stat: &Stat) -> i64 {
+        let rank = self.rank.die_count() as i64;
+        match stat {
+            Stat::Strength => self.strength,
+            Stat::Dexterity => self.dexterity,
+            Stat::Vitality => self.vitality,
+            Stat::Special => self.special,
+            Stat::Insight => self.insight,
+            Stat::Copy => 0,
+            Stat::StrengthOrSpecial => self.strength.max(self.special),
+            Stat::Rank => rank,
+            Stat::StrengthPlusRank => self.strength + rank,
+            Stat::StrengthMinusRank => self.strength - rank,
+        }
+    }
+
+    pub fn calculate_accuracy(&self, m: &Move) -> i64 {
+        let mut result = 0;
+        if let Some(accuracy) = m.accuracy1 {
+            result += self.get_die_count_for_stat(accuracy);
+        }
+
+        if m.accuracy2.is_some() {
+            result += self.rank.die_count() as i64;
+        }
+
+        result
+    }
+
+    pub fn calculate_damage(&self, m: &Move) -> i64 {
+        let mut result = m.power as i64;
+        if let Some(stat) = m.damage1 {
+            result += self.get_stat(&stat);
+        }
+
+        if m.happiness_damage.is_some() {
+            result += self.rank.die_count() as i64;
+        }
+
+        result
+    }
+}
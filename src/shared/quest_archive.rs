@@ -0,0 +1,138 @@
+use crate::Error;
+use sqlx::{Pool, Sqlite};
+
+const MAX_SUMMARY_PREVIEW_LENGTH: usize = 300;
+
+/// Optional filters for searching through completed quests. Timestamps are inclusive.
+#[derive(Default)]
+pub struct QuestArchiveFilter {
+    pub character_id: Option<i64>,
+    /// Matches both the quest's creator and its co-GMs.
+    pub gm_id: Option<i64>,
+    pub completed_after: Option<i64>,
+    pub completed_before: Option<i64>,
+    pub text: Option<String>,
+}
+
+pub struct QuestArchiveEntry {
+    pub channel_id: i64,
+    pub creator_id: i64,
+    pub completion_timestamp: i64,
+    pub summary: Option<String>,
+    pub participants: Vec<String>,
+}
+
+impl QuestArchiveEntry {
+    pub fn build_string(&self) -> String {
+        let mut result = format!(
+            "### <#{}>\nCompleted <t:{}:D>, hosted by <@{}>\n",
+            self.channel_id, self.completion_timestamp, self.creator_id
+        );
+        if !self.participants.is_empty() {
+            result.push_str(&format!("Participants: {}\n", self.participants.join(", ")));
+        }
+        match &self.summary {
+            Some(summary) => {
+                let mut preview: String =
+                    summary.chars().take(MAX_SUMMARY_PREVIEW_LENGTH).collect();
+                if preview.len() < summary.len() {
+                    preview.push_str("...");
+                }
+                result.push_str(&format!("> {}\n", preview.replace('\n', "\n> ")));
+            }
+            None => result.push_str("*No summary has been written yet.*\n"),
+        }
+
+        result
+    }
+}
+
+/// Escapes LIKE wildcards, so searching for "100%" doesn't match everything starting with "100".
+fn escape_like_pattern(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Completed quests matching the filter, most recent ones first.
+pub async fn search_quest_archive(
+    database: &Pool<Sqlite>,
+    guild_id: i64,
+    filter: &QuestArchiveFilter,
+    limit: i64,
+) -> Result<Vec<QuestArchiveEntry>, Error> {
+    let text = filter
+        .text
+        .as_ref()
+        .map(|x| format!("%{}%", escape_like_pattern(x)));
+    let quests = sqlx::query!(
+        "SELECT channel_id, creator_id, completion_timestamp as \"completion_timestamp!: i64\", summary FROM quest \
+        WHERE guild_id = ? AND completion_timestamp IS NOT NULL \
+        AND (? IS NULL OR creator_id = ? OR channel_id IN (SELECT quest_id FROM quest_gm WHERE user_id = ?)) \
+        AND (? IS NULL OR completion_timestamp >= ?) \
+        AND (? IS NULL OR completion_timestamp <= ?) \
+        AND (? IS NULL OR channel_id IN (SELECT quest_id FROM quest_completion WHERE character_id = ?)) \
+        AND (? IS NULL OR summary LIKE ? ESCAPE '\\') \
+        ORDER BY completion_timestamp DESC LIMIT ?",
+        guild_id,
+        filter.gm_id,
+        filter.gm_id,
+        filter.gm_id,
+        filter.completed_after,
+        filter.completed_after,
+        filter.completed_before,
+        filter.completed_before,
+        filter.character_id,
+        filter.character_id,
+        text,
+        text,
+        limit
+    )
+    .fetch_all(database)
+    .await?;
+
+    let mut result = Vec::new();
+    for quest in quests {
+        let participants = sqlx::query!(
+            "SELECT character.name FROM quest_completion \
+            INNER JOIN character ON quest_completion.character_id = character.id \
+            WHERE quest_completion.quest_id = ? ORDER BY character.name",
+            quest.channel_id
+        )
+        .fetch_all(database)
+        .await?
+        .into_iter()
+        .map(|x| x.name)
+        .collect();
+
+        result.push(QuestArchiveEntry {
+            channel_id: quest.channel_id,
+            creator_id: quest.creator_id,
+            completion_timestamp: quest.completion_timestamp,
+            summary: quest.summary,
+            participants,
+        });
+    }
+
+    Ok(result)
+}
+
+/// The most recently completed quests of a character.
+pub async fn get_recent_quest_ids(
+    database: &Pool<Sqlite>,
+    character_id: i64,
+    limit: i64,
+) -> Result<Vec<i64>, Error> {
+    let records = sqlx::query!(
+        "SELECT quest.channel_id FROM quest_completion \
+        INNER JOIN quest ON quest_completion.quest_id = quest.channel_id \
+        WHERE quest_completion.character_id = ? \
+        ORDER BY quest.completion_timestamp DESC LIMIT ?",
+        character_id,
+        limit
+    )
+    .fetch_all(database)
+    .await?;
+
+    Ok(records.into_iter().map(|x| x.channel_id).collect())
+}
@@ -0,0 +1,205 @@
+use crate::Error;
+use crate::shared::constants;
+use crate::shared::data::Data;
+use crate::shared::enums::QuestParticipantSelectionMechanism;
+use crate::shared::quest_restrictions::get_signup_candidates;
+use crate::shared::utility::error_handling::log_error;
+use poise::ChoiceParameter;
+use serenity::all::{
+    ChannelId, Context, CreateAllowedMentions, CreateMessage, EditMessage, HttpError, MessageId,
+};
+use sqlx::{Pool, Sqlite};
+
+const MAX_QUEST_BOARD_LENGTH: usize = 1900;
+
+pub struct OpenQuest {
+    pub channel_id: i64,
+    pub creator_id: i64,
+    pub maximum_participant_count: i64,
+    pub participant_selection_mechanism: i64,
+    pub scheduled_start_timestamp: Option<i64>,
+    pub signup_count: i64,
+    pub accepted_count: i64,
+}
+
+impl OpenQuest {
+    pub fn selection_mechanism(&self) -> QuestParticipantSelectionMechanism {
+        QuestParticipantSelectionMechanism::from_repr(self.participant_selection_mechanism)
+            .expect("Should always be valid!")
+    }
+
+    /// Mirrors [crate::shared::quest_waitlist::get_quest_participants]: First come first serve quests accept everyone within the participant limit.
+    pub fn participant_count(&self) -> i64 {
+        if self.selection_mechanism() == QuestParticipantSelectionMechanism::FirstComeFirstServe {
+            self.accepted_count
+                .max(self.signup_count.min(self.maximum_participant_count))
+        } else {
+            self.accepted_count
+        }
+    }
+
+    fn build_line(&self) -> String {
+        let start = match self.scheduled_start_timestamp {
+            Some(timestamp) => format!(" | 🕒 <t:{}:f>", timestamp),
+            None => String::new(),
+        };
+
+        format!(
+            "- <#{}> by <@{}> | {} | {} / {} participants{}\n",
+            self.channel_id,
+            self.creator_id,
+            self.selection_mechanism().name(),
+            self.participant_count(),
+            self.maximum_participant_count,
+            start
+        )
+    }
+}
+
+/// Quests which haven't been completed yet, with scheduled ones coming first.
+pub async fn get_open_quests(
+    database: &Pool<Sqlite>,
+    guild_id: i64,
+) -> Result<Vec<OpenQuest>, Error> {
+    let quests = sqlx::query_as!(
+        OpenQuest,
+        "SELECT channel_id, creator_id, maximum_participant_count, participant_selection_mechanism, scheduled_start_timestamp, \
+        (SELECT COUNT(*) FROM quest_signup WHERE quest_signup.quest_id = quest.channel_id) as \"signup_count!: i64\", \
+        (SELECT COUNT(*) FROM quest_signup WHERE quest_signup.quest_id = quest.channel_id AND quest_signup.accepted = TRUE) as \"accepted_count!: i64\" \
+        FROM quest WHERE guild_id = ? AND completion_timestamp IS NULL \
+        ORDER BY scheduled_start_timestamp IS NULL, scheduled_start_timestamp, creation_timestamp",
+        guild_id
+    )
+    .fetch_all(database)
+    .await?;
+
+    Ok(quests)
+}
+
+/// Whether the user still has an eligible active character which isn't signed up for the quest yet.
+pub async fn can_user_join_quest(
+    data: &Data,
+    guild_id: i64,
+    quest_id: i64,
+    user_id: i64,
+) -> Result<bool, Error> {
+    let signed_up_character_ids = sqlx::query!(
+        "SELECT character_id FROM quest_signup WHERE quest_id = ?",
+        quest_id
+    )
+    .fetch_all(&data.database)
+    .await?
+    .into_iter()
+    .map(|x| x.character_id)
+    .collect::<Vec<i64>>();
+
+    let candidates = get_signup_candidates(data, guild_id, user_id, quest_id).await?;
+    Ok(candidates
+        .iter()
+        .any(|x| x.ineligibility_reason.is_none() && !signed_up_character_ids.contains(&x.id)))
+}
+
+pub fn build_quest_list(quests: &[OpenQuest]) -> String {
+    if quests.is_empty() {
+        return String::from("*There are no open quests right now.*");
+    }
+
+    quests.iter().map(|x| x.build_line()).collect()
+}
+
+fn build_quest_board(quests: &[OpenQuest]) -> String {
+    let mut result = String::from("## 📜 Quest Board\n");
+    if quests.is_empty() {
+        result.push_str(&build_quest_list(quests));
+        return result;
+    }
+
+    for (index, quest) in quests.iter().enumerate() {
+        let line = quest.build_line();
+        if result.len() + line.len() > MAX_QUEST_BOARD_LENGTH {
+            result.push_str(&format!("*...and {} more!*", quests.len() - index));
+            break;
+        }
+        result.push_str(&line);
+    }
+
+    result
+}
+
+/// Edits the quest board message, or posts a new one in case it got deleted. Does nothing if no board channel was set up.
+pub async fn update_quest_board(
+    context: &Context,
+    database: &Pool<Sqlite>,
+    guild_id: i64,
+) -> Result<(), Error> {
+    let Some(guild) = sqlx::query!(
+        "SELECT quest_board_channel_id, quest_board_message_id FROM guild WHERE id = ?",
+        guild_id
+    )
+    .fetch_optional(database)
+    .await?
+    else {
+        return Ok(());
+    };
+    let Some(channel_id) = guild.quest_board_channel_id else {
+        return Ok(());
+    };
+
+    let quests = get_open_quests(database, guild_id).await?;
+    let content = build_quest_board(&quests);
+    let channel_id = ChannelId::new(channel_id as u64);
+    if let Some(message_id) = guild.quest_board_message_id {
+        let result = channel_id
+            .edit_message(
+                context,
+                MessageId::new(message_id as u64),
+                EditMessage::new()
+                    .content(&content)
+                    .allowed_mentions(CreateAllowedMentions::new()),
+            )
+            .await;
+        match result {
+            Ok(_) => return Ok(()),
+            Err(serenity::Error::Http(HttpError::UnsuccessfulRequest(e)))
+                if e.error.code == constants::discord_error_codes::UNKNOWN_MESSAGE => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    let message = channel_id
+        .send_message(
+            context,
+            CreateMessage::new()
+                .content(content)
+                .allowed_mentions(CreateAllowedMentions::new()),
+        )
+        .await?;
+    let message_id = message.id.get() as i64;
+    sqlx::query!(
+        "UPDATE guild SET quest_board_message_id = ? WHERE id = ?",
+        message_id,
+        guild_id
+    )
+    .execute(database)
+    .await?;
+
+    Ok(())
+}
+
+/// For places where the quest board is just a side effect, which shouldn't fail the actual command.
+pub async fn update_quest_board_or_log_error(
+    context: &Context,
+    database: &Pool<Sqlite>,
+    guild_id: i64,
+) {
+    if let Err(e) = update_quest_board(context, database, guild_id).await {
+        log_error(
+            context,
+            format!(
+                "Failed to update the quest board for guild {}: {}",
+                guild_id, e
+            ),
+        )
+        .await;
+    }
+}
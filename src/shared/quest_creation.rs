@@ -0,0 +1,174 @@
+use crate::Error;
+use crate::shared::enums::QuestParticipantSelectionMechanism;
+use crate::shared::quest_restrictions::{QuestRestrictions, set_quest_restrictions};
+use crate::shared::quest_reward::{QuestReward, add_quest_reward_item, set_quest_reward_values};
+use chrono::Utc;
+use sqlx::{Pool, Sqlite, SqliteConnection, SqliteExecutor};
+
+pub async fn create_quest_impl(
+    executor: impl SqliteExecutor<'_>,
+    guild_id: i64,
+    channel_id: i64,
+    creator_id: i64,
+    bot_message_id: i64,
+    max_participants: i64,
+    selection_mechanism: QuestParticipantSelectionMechanism,
+    quest_description_message_id: i64,
+) -> Result<(), String> {
+    let timestamp = Utc::now().timestamp();
+
+    let result = sqlx::query!("INSERT INTO quest (guild_id, channel_id, creator_id, bot_message_id, creation_timestamp, maximum_participant_count, participant_selection_mechanism, quest_description_message_id) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        guild_id, channel_id, creator_id, bot_message_id, timestamp, max_participants, selection_mechanism, quest_description_message_id
+    )
+        .execute(executor).await;
+
+    match result {
+        Ok(result) => {
+            if result.rows_affected() > 0 {
+                Ok(())
+            } else {
+                Err(String::from("Unable to persist quest entry!"))
+            }
+        }
+        Err(e) => Err(format!("**Something went wrong!**\n{}", e)),
+    }
+}
+
+/// Persists the optional rewards (including items) and restrictions of a freshly created quest.
+pub async fn apply_quest_settings(
+    connection: &mut SqliteConnection,
+    quest_id: i64,
+    reward: &QuestReward,
+    restrictions: &QuestRestrictions,
+) -> Result<(), Error> {
+    if !reward.is_empty() {
+        set_quest_reward_values(&mut *connection, quest_id, reward).await?;
+    }
+    for item in &reward.items {
+        add_quest_reward_item(&mut *connection, quest_id, &item.item_name, item.amount).await?;
+    }
+    if !restrictions.is_empty() {
+        set_quest_restrictions(&mut *connection, quest_id, restrictions).await?;
+    }
+
+    Ok(())
+}
+
+/// Removes a quest which never got past its creation, e.g. because its post couldn't be set up.
+pub async fn delete_unused_quest(database: &Pool<Sqlite>, quest_id: i64) -> Result<(), Error> {
+    let mut transaction = database.begin().await?;
+    sqlx::query!("DELETE FROM quest_reward_item WHERE quest_id = ?", quest_id)
+        .execute(&mut *transaction)
+        .await?;
+    sqlx::query!("DELETE FROM quest_reward WHERE quest_id = ?", quest_id)
+        .execute(&mut *transaction)
+        .await?;
+    sqlx::query!("DELETE FROM quest WHERE channel_id = ?", quest_id)
+        .execute(&mut *transaction)
+        .await?;
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shared::enums::QuestParticipantSelectionMechanism;
+    use crate::shared::quest_creation::create_quest_impl;
+    use crate::{Error, database_mocks};
+    use chrono::Utc;
+    use more_asserts::{assert_ge, assert_le};
+    use sqlx::{Pool, Sqlite};
+
+    #[sqlx::test]
+    async fn create_quest(db: Pool<Sqlite>) -> Result<(), Error> {
+        let data = database_mocks::create_mock::data(db).await;
+        let channel_id = 100;
+        let creator_id = 200;
+        let guild_id = 300;
+        let bot_message_id = 400;
+        let max_participants = 5;
+        let mechanism = QuestParticipantSelectionMechanism::FirstComeFirstServe;
+        let quest_description_message_id = 700;
+
+        database_mocks::create_mock::guild(&data.database, guild_id).await;
+        database_mocks::create_mock::user(&data.database, creator_id).await;
+        let timestamp_before = Utc::now().timestamp();
+        create_quest_impl(
+            &data.database,
+            guild_id,
+            channel_id,
+            creator_id,
+            bot_message_id,
+            max_participants,
+            mechanism,
+            quest_description_message_id,
+        )
+        .await?;
+        let timestamp_after = Utc::now().timestamp();
+
+        let quests = sqlx::query!(
+            "SELECT guild_id, creator_id, channel_id, creation_timestamp, completion_timestamp, maximum_participant_count, participant_selection_mechanism FROM quest"
+        )
+        .fetch_all(&data.database)
+        .await?;
+
+        let quest = quests.first().unwrap();
+        assert_eq!(creator_id, quest.creator_id);
+        assert_eq!(guild_id, quest.guild_id);
+        assert_eq!(channel_id, quest.channel_id);
+        assert_le!(timestamp_before, quest.creation_timestamp);
+        assert_ge!(timestamp_after, quest.creation_timestamp);
+        assert_eq!(None, quest.completion_timestamp);
+        assert_eq!(max_participants, quest.maximum_participant_count);
+        assert_eq!(
+            mechanism,
+            QuestParticipantSelectionMechanism::from_repr(quest.participant_selection_mechanism)
+                .unwrap()
+        );
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn create_quest_called_twice(db: Pool<Sqlite>) -> Result<(), Error> {
+        let data = database_mocks::create_mock::data(db).await;
+        let channel_id = 100;
+        let creator_id = 200;
+        let guild_id = 300;
+        let bot_message_id = 400;
+        let max_participants = 5;
+        let selection_mechanism = QuestParticipantSelectionMechanism::FirstComeFirstServe;
+        let quest_description_message_id = 700;
+
+        database_mocks::create_mock::guild(&data.database, guild_id).await;
+        database_mocks::create_mock::user(&data.database, creator_id).await;
+
+        create_quest_impl(
+            &data.database,
+            guild_id,
+            channel_id,
+            creator_id,
+            bot_message_id,
+            max_participants,
+            selection_mechanism,
+            quest_description_message_id,
+        )
+        .await?;
+        let result = create_quest_impl(
+            &data.database,
+            guild_id,
+            channel_id,
+            creator_id,
+            bot_message_id,
+            max_participants,
+            selection_mechanism,
+            quest_description_message_id,
+        )
+        .await;
+
+        assert!(result.is_err());
+
+        Ok(())
+    }
+}
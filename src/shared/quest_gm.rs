@@ -0,0 +1,97 @@
+use crate::Error;
+use sqlx::SqliteExecutor;
+
+/// Whether the user created the quest or was added as a co-GM.
+pub async fn is_quest_gm(
+    executor: impl SqliteExecutor<'_>,
+    quest_id: i64,
+    user_id: i64,
+) -> Result<bool, Error> {
+    let record = sqlx::query!(
+        "SELECT EXISTS(SELECT 1 FROM quest WHERE channel_id = ? AND creator_id = ?) \
+        OR EXISTS(SELECT 1 FROM quest_gm WHERE quest_id = ? AND user_id = ?) as \"is_gm!: bool\"",
+        quest_id,
+        user_id,
+        quest_id,
+        user_id
+    )
+    .fetch_one(executor)
+    .await?;
+
+    Ok(record.is_gm)
+}
+
+/// The quest creator, followed by all co-GMs.
+pub async fn get_quest_gm_ids(
+    executor: impl SqliteExecutor<'_>,
+    quest_id: i64,
+) -> Result<Vec<i64>, Error> {
+    let records = sqlx::query!(
+        "SELECT creator_id as user_id FROM quest WHERE channel_id = ? \
+        UNION ALL \
+        SELECT user_id FROM quest_gm WHERE quest_id = ?",
+        quest_id,
+        quest_id
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(records.into_iter().map(|x| x.user_id).collect())
+}
+
+pub fn build_gm_mention_list(user_ids: &[i64]) -> String {
+    user_ids
+        .iter()
+        .map(|x| format!("<@{}>", x))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shared::enums::QuestParticipantSelectionMechanism;
+    use crate::shared::quest_gm::{get_quest_gm_ids, is_quest_gm};
+    use crate::{Error, database_mocks};
+    use sqlx::{Pool, Sqlite};
+
+    #[sqlx::test]
+    async fn co_gms_count_as_quest_gms(db: Pool<Sqlite>) -> Result<(), Error> {
+        let channel_id = 100;
+        let creator_id = 200;
+        let co_gm_id = 201;
+        let other_user_id = 202;
+        let guild_id = 300;
+
+        database_mocks::create_mock::guild(&db, guild_id).await;
+        for user_id in [creator_id, co_gm_id, other_user_id] {
+            database_mocks::create_mock::user(&db, user_id).await;
+        }
+        database_mocks::create_mock::quest(
+            &db,
+            channel_id,
+            guild_id,
+            creator_id,
+            400,
+            3,
+            QuestParticipantSelectionMechanism::FirstComeFirstServe,
+        )
+        .await;
+        sqlx::query!(
+            "INSERT INTO quest_gm (quest_id, user_id) VALUES (?, ?)",
+            channel_id,
+            co_gm_id
+        )
+        .execute(&db)
+        .await?;
+
+        assert!(is_quest_gm(&db, channel_id, creator_id).await?);
+        assert!(is_quest_gm(&db, channel_id, co_gm_id).await?);
+        assert!(!is_quest_gm(&db, channel_id, other_user_id).await?);
+        assert_eq!(
+            vec![creator_id, co_gm_id],
+            get_quest_gm_ids(&db, channel_id).await?
+        );
+
+        Ok(())
+    }
+}
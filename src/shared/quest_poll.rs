@@ -0,0 +1,266 @@
+use crate::Error;
+use crate::shared::errors::ValidationError;
+use crate::shared::quest_waitlist::get_quest_participants;
+use crate::shared::utility::button_building;
+use chrono::Utc;
+use serenity::all::{ButtonStyle, CreateActionRow};
+use sqlx::{Pool, Sqlite};
+
+const BUTTONS_PER_ROW: usize = 5;
+const MAX_LISTED_VOTERS: usize = 10;
+
+/// A proposed start time, and the users who marked themselves as available for it.
+pub struct QuestPollSlot {
+    pub start_timestamp: i64,
+    pub voter_ids: Vec<i64>,
+}
+
+impl QuestPollSlot {
+    fn available_participant_count(&self, accepted_user_ids: &[i64]) -> usize {
+        self.voter_ids
+            .iter()
+            .filter(|x| accepted_user_ids.contains(x))
+            .count()
+    }
+}
+
+pub async fn get_quest_poll_slots(
+    database: &Pool<Sqlite>,
+    quest_id: i64,
+) -> Result<Vec<QuestPollSlot>, Error> {
+    let records = sqlx::query!(
+        "SELECT quest_poll_slot.start_timestamp, quest_poll_vote.user_id as \"user_id?: i64\" \
+        FROM quest_poll_slot \
+        LEFT JOIN quest_poll_vote ON quest_poll_vote.quest_id = quest_poll_slot.quest_id \
+            AND quest_poll_vote.start_timestamp = quest_poll_slot.start_timestamp \
+        WHERE quest_poll_slot.quest_id = ? \
+        ORDER BY quest_poll_slot.start_timestamp",
+        quest_id
+    )
+    .fetch_all(database)
+    .await?;
+
+    let mut result: Vec<QuestPollSlot> = Vec::new();
+    for record in records {
+        if result
+            .last()
+            .is_none_or(|x| x.start_timestamp != record.start_timestamp)
+        {
+            result.push(QuestPollSlot {
+                start_timestamp: record.start_timestamp,
+                voter_ids: Vec::new(),
+            });
+        }
+        if let Some(user_id) = record.user_id {
+            result
+                .last_mut()
+                .expect("Was just pushed.")
+                .voter_ids
+                .push(user_id);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Removes any previous poll for the quest and proposes the new start times instead.
+/// Fails in case any of the start times already lies in the past.
+pub async fn replace_quest_poll(
+    database: &Pool<Sqlite>,
+    quest_id: i64,
+    start_timestamps: &[i64],
+    now: i64,
+) -> Result<(), Error> {
+    if let Some(past_timestamp) = start_timestamps.iter().find(|x| **x <= now) {
+        return Err(Box::new(ValidationError::new(format!(
+            "<t:{}:f> lies in the past!",
+            past_timestamp
+        ))));
+    }
+
+    let mut transaction = database.begin().await?;
+    delete_quest_poll(&mut transaction, quest_id).await?;
+    for start_timestamp in start_timestamps {
+        sqlx::query!(
+            "INSERT INTO quest_poll_slot (quest_id, start_timestamp) VALUES (?, ?) ON CONFLICT DO NOTHING",
+            quest_id,
+            start_timestamp
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+    transaction.commit().await?;
+    Ok(())
+}
+
+pub async fn delete_quest_poll(
+    transaction: &mut sqlx::Transaction<'_, Sqlite>,
+    quest_id: i64,
+) -> Result<(), Error> {
+    sqlx::query!("DELETE FROM quest_poll_vote WHERE quest_id = ?", quest_id)
+        .execute(&mut **transaction)
+        .await?;
+    sqlx::query!("DELETE FROM quest_poll_slot WHERE quest_id = ?", quest_id)
+        .execute(&mut **transaction)
+        .await?;
+    Ok(())
+}
+
+/// The slot which works for the most accepted participants. Ties go to the slot with more votes overall, then to the earlier one.
+/// Slots which already lie in the past are skipped. Returns None as long as nobody voted for an upcoming slot.
+pub fn pick_best_slot<'a>(
+    slots: &'a [QuestPollSlot],
+    accepted_user_ids: &[i64],
+    now: i64,
+) -> Option<&'a QuestPollSlot> {
+    slots
+        .iter()
+        .filter(|x| x.start_timestamp > now && !x.voter_ids.is_empty())
+        .max_by(|a, b| {
+            a.available_participant_count(accepted_user_ids)
+                .cmp(&b.available_participant_count(accepted_user_ids))
+                .then(a.voter_ids.len().cmp(&b.voter_ids.len()))
+                .then(b.start_timestamp.cmp(&a.start_timestamp))
+        })
+}
+
+/// Accepted participants of the quest, one entry per user.
+pub async fn get_accepted_user_ids(
+    database: &Pool<Sqlite>,
+    quest_id: i64,
+) -> Result<Vec<i64>, Error> {
+    let mut result: Vec<i64> = get_quest_participants(database, quest_id)
+        .await?
+        .into_iter()
+        .map(|x| x.user_id)
+        .collect();
+    result.sort();
+    result.dedup();
+    Ok(result)
+}
+
+pub async fn build_quest_poll_message(
+    database: &Pool<Sqlite>,
+    quest_id: i64,
+) -> Result<(String, Vec<CreateActionRow>), Error> {
+    let slots = get_quest_poll_slots(database, quest_id).await?;
+    let accepted_user_ids = get_accepted_user_ids(database, quest_id).await?;
+
+    let mut text = String::from(
+        "## 🗓️ When should we play?\nEveryone who signed up can mark when they're available with the buttons below. All times are shown in your local time.\n",
+    );
+    for (index, slot) in slots.iter().enumerate() {
+        text.push_str(&format!(
+            "\n**{}.** <t:{}:F> (<t:{}:R>)\n✅ {} / {} accepted participants",
+            index + 1,
+            slot.start_timestamp,
+            slot.start_timestamp,
+            slot.available_participant_count(&accepted_user_ids),
+            accepted_user_ids.len()
+        ));
+        if !slot.voter_ids.is_empty() {
+            let mut voters = slot
+                .voter_ids
+                .iter()
+                .take(MAX_LISTED_VOTERS)
+                .map(|x| format!("<@{}>", x))
+                .collect::<Vec<String>>()
+                .join(", ");
+            if slot.voter_ids.len() > MAX_LISTED_VOTERS {
+                voters.push_str(&format!(
+                    " and {} more",
+                    slot.voter_ids.len() - MAX_LISTED_VOTERS
+                ));
+            }
+            text.push_str(&format!(" | {}", voters));
+        }
+        text.push('\n');
+    }
+
+    let best_slot = pick_best_slot(&slots, &accepted_user_ids, Utc::now().timestamp());
+    if let Some(best_slot) = best_slot {
+        text.push_str(&format!(
+            "\n⭐ **Suggestion:** <t:{}:F>, which works for {} / {} accepted participants.",
+            best_slot.start_timestamp,
+            best_slot.available_participant_count(&accepted_user_ids),
+            accepted_user_ids.len()
+        ));
+    }
+
+    let slot_buttons = slots
+        .iter()
+        .enumerate()
+        .map(|(index, slot)| {
+            button_building::create_styled_button(
+                &(index + 1).to_string(),
+                &format!("quest-poll-vote_{}", slot.start_timestamp),
+                false,
+                ButtonStyle::Secondary,
+            )
+        })
+        .collect::<Vec<_>>();
+    let mut components = slot_buttons
+        .chunks(BUTTONS_PER_ROW)
+        .map(|x| CreateActionRow::Buttons(x.to_vec()))
+        .collect::<Vec<CreateActionRow>>();
+    components.push(CreateActionRow::Buttons(vec![
+        button_building::create_styled_button(
+            "Schedule Suggestion",
+            "quest-poll-schedule",
+            best_slot.is_none(),
+            ButtonStyle::Success,
+        ),
+    ]));
+
+    Ok((text, components))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shared::quest_poll::{QuestPollSlot, pick_best_slot};
+
+    fn slot(start_timestamp: i64, voter_ids: Vec<i64>) -> QuestPollSlot {
+        QuestPollSlot {
+            start_timestamp,
+            voter_ids,
+        }
+    }
+
+    #[test]
+    fn best_slot_prefers_accepted_participants() {
+        let accepted_user_ids = vec![1, 2];
+        let slots = vec![slot(100, vec![1, 3, 4]), slot(200, vec![1, 2])];
+
+        let best = pick_best_slot(&slots, &accepted_user_ids, 0).unwrap();
+        assert_eq!(200, best.start_timestamp);
+    }
+
+    #[test]
+    fn best_slot_ties_go_to_more_votes_then_earlier_slots() {
+        let accepted_user_ids = vec![1];
+        let slots = vec![
+            slot(100, vec![1]),
+            slot(200, vec![1, 3]),
+            slot(300, vec![1, 3]),
+        ];
+
+        let best = pick_best_slot(&slots, &accepted_user_ids, 0).unwrap();
+        assert_eq!(200, best.start_timestamp);
+    }
+
+    #[test]
+    fn no_best_slot_without_votes() {
+        let slots = vec![slot(100, Vec::new())];
+        assert!(pick_best_slot(&slots, &[1], 0).is_none());
+    }
+
+    #[test]
+    fn past_slots_are_skipped() {
+        let accepted_user_ids = vec![1, 2];
+        let slots = vec![slot(100, vec![1, 2]), slot(200, vec![1])];
+
+        let best = pick_best_slot(&slots, &accepted_user_ids, 150).unwrap();
+        assert_eq!(200, best.start_timestamp);
+        assert!(pick_best_slot(&slots, &accepted_user_ids, 200).is_none());
+    }
+}
@@ -0,0 +1,301 @@
+use crate::Error;
+use crate::shared::data::Data;
+use crate::shared::enums::{MysteryDungeonRank, PokemonType};
+use crate::shared::game_data::PokemonApiId;
+use crate::shared::game_data::pokemon_types::PokemonTypes;
+use crate::shared::guild_settings::get_progression_rules;
+use crate::shared::utility::level_calculations;
+use log::error;
+use sqlx::SqliteExecutor;
+use std::str::FromStr;
+
+/// Limits on which characters may sign up for a quest. Everything is optional.
+#[derive(Default)]
+pub struct QuestRestrictions {
+    pub minimum_level: Option<i64>,
+    pub maximum_level: Option<i64>,
+    pub minimum_rank: Option<MysteryDungeonRank>,
+    pub maximum_rank: Option<MysteryDungeonRank>,
+    pub required_type: Option<PokemonType>,
+    pub forbidden_type: Option<PokemonType>,
+}
+
+/// A non-retired character, and why it can't join the quest, if that's the case.
+pub struct SignupCandidate {
+    pub id: i64,
+    pub name: String,
+    pub ineligibility_reason: Option<String>,
+}
+
+impl QuestRestrictions {
+    /// Ranks are stored as their discriminant, types by their name.
+    pub fn from_columns(
+        minimum_level: Option<i64>,
+        maximum_level: Option<i64>,
+        minimum_rank: Option<i64>,
+        maximum_rank: Option<i64>,
+        required_type: Option<String>,
+        forbidden_type: Option<String>,
+    ) -> Self {
+        QuestRestrictions {
+            minimum_level,
+            maximum_level,
+            minimum_rank: minimum_rank.and_then(MysteryDungeonRank::from_repr),
+            maximum_rank: maximum_rank.and_then(MysteryDungeonRank::from_repr),
+            required_type: required_type.and_then(|x| PokemonType::from_str(&x).ok()),
+            forbidden_type: forbidden_type.and_then(|x| PokemonType::from_str(&x).ok()),
+        }
+    }
+
+    /// The counterpart to [QuestRestrictions::from_columns] for everything which isn't stored as is.
+    pub fn rank_and_type_columns(
+        &self,
+    ) -> (Option<i64>, Option<i64>, Option<String>, Option<String>) {
+        (
+            self.minimum_rank.map(|x| x as i64),
+            self.maximum_rank.map(|x| x as i64),
+            self.required_type.map(|x| format!("{:?}", x)),
+            self.forbidden_type.map(|x| format!("{:?}", x)),
+        )
+    }
+
+    /// Returns an error message if the restrictions contradict themselves.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if let (Some(min), Some(max)) = (self.minimum_level, self.maximum_level)
+            && min > max
+        {
+            return Err("The minimum level can't be higher than the maximum level!");
+        }
+        if let (Some(min), Some(max)) = (self.minimum_rank, self.maximum_rank)
+            && min > max
+        {
+            return Err("The minimum rank can't be higher than the maximum rank!");
+        }
+        if self.required_type.is_some() && self.required_type == self.forbidden_type {
+            return Err("The same type can't be both required and forbidden!");
+        }
+
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.minimum_level.is_none()
+            && self.maximum_level.is_none()
+            && self.minimum_rank.is_none()
+            && self.maximum_rank.is_none()
+            && self.required_type.is_none()
+            && self.forbidden_type.is_none()
+    }
+
+    pub fn build_string(&self) -> String {
+        let mut result = String::new();
+        match (self.minimum_level, self.maximum_level) {
+            (Some(min), Some(max)) => result.push_str(&format!("\nLevel: **{} - {}**", min, max)),
+            (Some(min), None) => result.push_str(&format!("\nLevel: **{}+**", min)),
+            (None, Some(max)) => result.push_str(&format!("\nLevel: **{} or below**", max)),
+            (None, None) => {}
+        }
+        match (self.minimum_rank, self.maximum_rank) {
+            (Some(min), Some(max)) if min == max => {
+                result.push_str(&format!("\nRank: **{} only**", min))
+            }
+            (Some(min), Some(max)) => result.push_str(&format!("\nRank: **{} - {}**", min, max)),
+            (Some(min), None) => result.push_str(&format!("\nRank: **{}+**", min)),
+            (None, Some(max)) => result.push_str(&format!("\nRank: **{} or below**", max)),
+            (None, None) => {}
+        }
+        if let Some(required_type) = self.required_type {
+            result.push_str(&format!("\nRequired Type: **{}**", required_type));
+        }
+        if let Some(forbidden_type) = self.forbidden_type {
+            result.push_str(&format!("\nForbidden Type: **{}**", forbidden_type));
+        }
+
+        result
+    }
+
+    /// Returns None if a character with the given level and types may join.
+    pub fn get_ineligibility_reason(&self, level: i64, types: &PokemonTypes) -> Option<String> {
+        if let Some(minimum_level) = self.minimum_level
+            && level < minimum_level
+        {
+            return Some(format!("needs to be at least level {}", minimum_level));
+        }
+        if let Some(maximum_level) = self.maximum_level
+            && level > maximum_level
+        {
+            return Some(format!("needs to be level {} or below", maximum_level));
+        }
+
+        let rank = MysteryDungeonRank::from_level(level);
+        if let Some(minimum_rank) = self.minimum_rank
+            && rank < minimum_rank
+        {
+            return Some(format!(
+                "needs to be at least {}",
+                minimum_rank.name_without_emoji()
+            ));
+        }
+        if let Some(maximum_rank) = self.maximum_rank
+            && rank > maximum_rank
+        {
+            return Some(format!(
+                "needs to be {} or below",
+                maximum_rank.name_without_emoji()
+            ));
+        }
+
+        let has_type = |x: PokemonType| types.type1 == x || types.type2 == Some(x);
+        if let Some(required_type) = self.required_type
+            && !has_type(required_type)
+        {
+            return Some(format!("needs to be {} type", required_type));
+        }
+        if let Some(forbidden_type) = self.forbidden_type
+            && has_type(forbidden_type)
+        {
+            return Some(format!("can't be {} type", forbidden_type));
+        }
+
+        None
+    }
+}
+
+pub async fn get_quest_restrictions(
+    executor: impl SqliteExecutor<'_>,
+    quest_id: i64,
+) -> Result<QuestRestrictions, Error> {
+    let Some(record) = sqlx::query!(
+        "SELECT minimum_level, maximum_level, minimum_rank, maximum_rank, required_type, forbidden_type \
+        FROM quest WHERE channel_id = ?",
+        quest_id
+    )
+    .fetch_optional(executor)
+    .await?
+    else {
+        return Ok(QuestRestrictions::default());
+    };
+
+    Ok(QuestRestrictions::from_columns(
+        record.minimum_level,
+        record.maximum_level,
+        record.minimum_rank,
+        record.maximum_rank,
+        record.required_type,
+        record.forbidden_type,
+    ))
+}
+
+pub async fn set_quest_restrictions(
+    executor: impl SqliteExecutor<'_>,
+    quest_id: i64,
+    restrictions: &QuestRestrictions,
+) -> Result<(), Error> {
+    let (minimum_rank, maximum_rank, required_type, forbidden_type) =
+        restrictions.rank_and_type_columns();
+    sqlx::query!(
+        "UPDATE quest SET minimum_level = ?, maximum_level = ?, minimum_rank = ?, maximum_rank = ?, \
+        required_type = ?, forbidden_type = ? WHERE channel_id = ?",
+        restrictions.minimum_level,
+        restrictions.maximum_level,
+        minimum_rank,
+        maximum_rank,
+        required_type,
+        forbidden_type,
+        quest_id
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+struct CandidateCharacter {
+    id: i64,
+    name: String,
+    experience: i64,
+    species_api_id: i64,
+}
+
+/// All non-retired characters of the user, checked against the restrictions of the quest.
+pub async fn get_signup_candidates(
+    data: &Data,
+    guild_id: i64,
+    user_id: i64,
+    quest_id: i64,
+) -> Result<Vec<SignupCandidate>, Error> {
+    let characters = sqlx::query_as!(
+        CandidateCharacter,
+        "SELECT id, name, experience, species_api_id FROM character \
+        WHERE user_id = ? AND guild_id = ? AND is_retired = false",
+        user_id,
+        guild_id
+    )
+    .fetch_all(&data.database)
+    .await?;
+
+    check_signup_candidates(data, guild_id, quest_id, characters).await
+}
+
+/// All non-retired members of the team, checked against the restrictions of the quest.
+pub async fn get_team_signup_candidates(
+    data: &Data,
+    guild_id: i64,
+    team_id: i64,
+    quest_id: i64,
+) -> Result<Vec<SignupCandidate>, Error> {
+    let characters = sqlx::query_as!(
+        CandidateCharacter,
+        "SELECT character.id, character.name, character.experience, character.species_api_id FROM character \
+        INNER JOIN team_member ON character.id = team_member.character_id \
+        WHERE team_member.team_id = ? AND character.is_retired = false \
+        ORDER BY character.name",
+        team_id
+    )
+    .fetch_all(&data.database)
+    .await?;
+
+    check_signup_candidates(data, guild_id, quest_id, characters).await
+}
+
+async fn check_signup_candidates(
+    data: &Data,
+    guild_id: i64,
+    quest_id: i64,
+    characters: Vec<CandidateCharacter>,
+) -> Result<Vec<SignupCandidate>, Error> {
+    let restrictions = get_quest_restrictions(&data.database, quest_id).await?;
+    if restrictions.is_empty() {
+        return Ok(characters
+            .into_iter()
+            .map(|x| SignupCandidate {
+                id: x.id,
+                name: x.name,
+                ineligibility_reason: None,
+            })
+            .collect());
+    }
+
+    let rules = get_progression_rules(&data.database, guild_id).await?;
+    let mut result = Vec::new();
+    for character in characters {
+        let level =
+            level_calculations::calculate_level_from_experience(&rules, character.experience);
+        let api_id = PokemonApiId(character.species_api_id as u16);
+        let Some(pokemon) = data.game.base_data.pokemon_by_api_id.get(&api_id) else {
+            error!(
+                "Database values should always be valid, but couldn't find an API entry for character with id {} and poke_api id {:?}",
+                character.id, api_id
+            );
+            continue;
+        };
+
+        result.push(SignupCandidate {
+            id: character.id,
+            name: character.name,
+            ineligibility_reason: restrictions.get_ineligibility_reason(level, &pokemon.types),
+        });
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,185 @@
+use crate::Error;
+use crate::shared::cache::CharacterCacheItem;
+use crate::shared::emoji;
+use crate::shared::game_data::GameData;
+use crate::shared::inventory::{self, InventoryItem};
+use sqlx::{SqliteConnection, SqliteExecutor};
+
+/// Rewards which get handed out to every participant once a quest is completed.
+#[derive(Default)]
+pub struct QuestReward {
+    pub experience: i64,
+    pub money: i64,
+    pub battle_points: i64,
+    pub gm_experience: i64,
+    pub items: Vec<QuestRewardItem>,
+}
+
+#[derive(Clone)]
+pub struct QuestRewardItem {
+    pub item_name: String,
+    pub amount: i64,
+}
+
+impl QuestReward {
+    pub fn is_empty(&self) -> bool {
+        self.experience == 0
+            && self.money == 0
+            && self.battle_points == 0
+            && self.gm_experience == 0
+            && self.items.is_empty()
+    }
+
+    pub fn build_string(&self) -> String {
+        if self.is_empty() {
+            return String::from("*No rewards set up yet.*");
+        }
+
+        let mut result = String::new();
+        if self.experience > 0 {
+            result.push_str(&format!("- {} Experience\n", self.experience));
+        }
+        if self.money > 0 {
+            result.push_str(&format!("- {} {}\n", self.money, emoji::POKE_COIN));
+        }
+        if self.battle_points > 0 {
+            result.push_str(&format!(
+                "- {} {}\n",
+                self.battle_points,
+                emoji::BATTLE_POINT
+            ));
+        }
+        for item in &self.items {
+            result.push_str(&format!("- {}x {}\n", item.amount, item.item_name));
+        }
+        if self.gm_experience > 0 {
+            result.push_str(&format!(
+                "- {} GM Experience for every GM\n",
+                self.gm_experience
+            ));
+        }
+
+        result
+    }
+}
+
+/// Quests without any rewards set up return an empty bundle.
+pub async fn get_quest_reward(
+    connection: &mut SqliteConnection,
+    quest_id: i64,
+) -> Result<QuestReward, Error> {
+    let record = sqlx::query!(
+        "SELECT experience, money, battle_points, gm_experience FROM quest_reward WHERE quest_id = ?",
+        quest_id
+    )
+    .fetch_optional(&mut *connection)
+    .await?;
+
+    let items = sqlx::query_as!(
+        QuestRewardItem,
+        "SELECT item_name, amount FROM quest_reward_item WHERE quest_id = ? ORDER BY item_name",
+        quest_id
+    )
+    .fetch_all(&mut *connection)
+    .await?;
+
+    Ok(match record {
+        Some(record) => QuestReward {
+            experience: record.experience,
+            money: record.money,
+            battle_points: record.battle_points,
+            gm_experience: record.gm_experience,
+            items,
+        },
+        None => QuestReward {
+            items,
+            ..QuestReward::default()
+        },
+    })
+}
+
+pub async fn set_quest_reward_values(
+    executor: impl SqliteExecutor<'_>,
+    quest_id: i64,
+    reward: &QuestReward,
+) -> Result<(), Error> {
+    sqlx::query!(
+        "INSERT INTO quest_reward (quest_id, experience, money, battle_points, gm_experience) VALUES (?, ?, ?, ?, ?) \
+        ON CONFLICT (quest_id) DO UPDATE SET experience = excluded.experience, money = excluded.money, \
+        battle_points = excluded.battle_points, gm_experience = excluded.gm_experience",
+        quest_id,
+        reward.experience,
+        reward.money,
+        reward.battle_points,
+        reward.gm_experience
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn add_quest_reward_item(
+    executor: impl SqliteExecutor<'_>,
+    quest_id: i64,
+    item_name: &str,
+    amount: i64,
+) -> Result<(), Error> {
+    sqlx::query!(
+        "INSERT INTO quest_reward_item (quest_id, item_name, amount) VALUES (?, ?, ?) \
+        ON CONFLICT (quest_id, item_name) DO UPDATE SET amount = amount + excluded.amount",
+        quest_id,
+        item_name,
+        amount
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Hands out the reward bundle to the given characters. Fails without partial changes if any backpack overflows,
+/// as long as the connection is part of a transaction.
+pub async fn distribute_quest_reward(
+    connection: &mut SqliteConnection,
+    game_data: &GameData,
+    reward: &QuestReward,
+    characters: &[CharacterCacheItem],
+) -> Result<(), Error> {
+    for character in characters {
+        sqlx::query!(
+            "UPDATE character SET experience = experience + ?, money = money + ?, battle_points = battle_points + ? WHERE id = ?",
+            reward.experience,
+            reward.money,
+            reward.battle_points,
+            character.id
+        )
+        .execute(&mut *connection)
+        .await?;
+
+        for reward_item in &reward.items {
+            let item = match InventoryItem::find(game_data, &reward_item.item_name) {
+                Some(item) => item,
+                None => InventoryItem {
+                    name: reward_item.item_name.clone(),
+                    single_use: true,
+                    health_restored: None,
+                    restores_full_health: false,
+                    effect: None,
+                },
+            };
+
+            inventory::add_item(
+                &mut *connection,
+                game_data,
+                character.id,
+                &character.name,
+                &item,
+                reward_item.amount,
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
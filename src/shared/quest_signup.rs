@@ -0,0 +1,89 @@
+use sqlx::{Pool, Sqlite};
+
+/// Writes the signup. Whether it counts as accepted or waiting is decided by the quest's selection mechanism.
+pub async fn persist_signup(
+    database: &Pool<Sqlite>,
+    channel_id: i64,
+    character_id: i64,
+    timestamp: i64,
+) -> Result<(), String> {
+    let result = sqlx::query!(
+        "INSERT INTO quest_signup (quest_id, character_id, timestamp) VALUES (?, ?, ?)",
+        channel_id,
+        character_id,
+        timestamp
+    )
+    .execute(database)
+    .await;
+
+    match result {
+        Ok(result) => {
+            if result.rows_affected() > 0 {
+                Ok(())
+            } else {
+                Err(String::from("Unable to persist quest signup!"))
+            }
+        }
+        Err(e) => Err(format!("**Something went wrong!**\n{}", e)),
+    }
+}
+
+/// Whether the error returned by [persist_signup] just means that the character already is signed up.
+pub fn is_already_signed_up_error(error: &str) -> bool {
+    error.contains("UNIQUE constraint failed")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shared::enums::QuestParticipantSelectionMechanism;
+    use crate::shared::quest_signup::persist_signup;
+    use crate::{Error, database_mocks};
+    use chrono::Utc;
+    use sqlx::{Pool, Sqlite};
+
+    #[sqlx::test]
+    async fn sign_up(db: Pool<Sqlite>) -> Result<(), Error> {
+        let data = database_mocks::create_mock::data(db).await;
+        let channel_id = 100;
+        let creator_id = 200;
+        let guild_id = 300;
+        let bot_message_id = 400;
+        let character_id = 500;
+        let character_name = String::from("test");
+
+        database_mocks::create_mock::guild(&data.database, guild_id).await;
+        database_mocks::create_mock::user(&data.database, creator_id).await;
+        database_mocks::create_mock::quest(
+            &data.database,
+            channel_id,
+            guild_id,
+            creator_id,
+            bot_message_id,
+            5,
+            QuestParticipantSelectionMechanism::Random,
+        )
+        .await;
+        database_mocks::create_mock::character(
+            &data,
+            guild_id,
+            creator_id,
+            character_id,
+            &character_name,
+        )
+        .await;
+
+        let timestamp = Utc::now().timestamp();
+        persist_signup(&data.database, channel_id, character_id, timestamp).await?;
+
+        let signups = sqlx::query!("SELECT quest_id, character_id, timestamp FROM quest_signup")
+            .fetch_all(&data.database)
+            .await?;
+
+        let signup = signups.first().unwrap();
+        assert_eq!(channel_id, signup.quest_id);
+        assert_eq!(character_id, signup.character_id);
+        assert_eq!(timestamp, signup.timestamp);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+use crate::Error;
+use crate::shared::enums::QuestParticipantSelectionMechanism;
+use crate::shared::quest_restrictions::QuestRestrictions;
+use crate::shared::quest_reward::{QuestReward, QuestRewardItem};
+use poise::ChoiceParameter;
+use sqlx::SqliteExecutor;
+
+/// Saved quest settings which can be applied with /create_quest, or get posted automatically on a schedule.
+pub struct QuestTemplate {
+    pub guild_id: i64,
+    pub name: String,
+    pub creator_id: i64,
+    pub title: String,
+    pub description: String,
+    pub maximum_participant_count: i64,
+    pub participant_selection_mechanism: i64,
+    pub reward_experience: i64,
+    pub reward_money: i64,
+    pub reward_battle_points: i64,
+    pub reward_gm_experience: i64,
+    pub minimum_level: Option<i64>,
+    pub maximum_level: Option<i64>,
+    pub minimum_rank: Option<i64>,
+    pub maximum_rank: Option<i64>,
+    pub required_type: Option<String>,
+    pub forbidden_type: Option<String>,
+    pub recurring_forum_channel_id: Option<i64>,
+    pub recurrence_interval_days: Option<i64>,
+    pub next_recurrence_timestamp: Option<i64>,
+}
+
+impl QuestTemplate {
+    pub fn selection_mechanism(&self) -> QuestParticipantSelectionMechanism {
+        QuestParticipantSelectionMechanism::from_repr(self.participant_selection_mechanism)
+            .expect("Should always be valid!")
+    }
+
+    /// Items are stored separately, see [get_quest_template_reward_items].
+    pub fn reward(&self, items: Vec<QuestRewardItem>) -> QuestReward {
+        QuestReward {
+            experience: self.reward_experience,
+            money: self.reward_money,
+            battle_points: self.reward_battle_points,
+            gm_experience: self.reward_gm_experience,
+            items,
+        }
+    }
+
+    pub fn restrictions(&self) -> QuestRestrictions {
+        QuestRestrictions::from_columns(
+            self.minimum_level,
+            self.maximum_level,
+            self.minimum_rank,
+            self.maximum_rank,
+            self.required_type.clone(),
+            self.forbidden_type.clone(),
+        )
+    }
+
+    pub fn build_string(&self, items: &[QuestRewardItem]) -> String {
+        let mut result = format!(
+            "## {}\n**{}**\n{}\n\nParticipant Selection Method: **{}**\nMaximum Participants: **{}**",
+            self.name,
+            self.title,
+            self.description,
+            self.selection_mechanism().name(),
+            self.maximum_participant_count
+        );
+        result.push_str(&self.restrictions().build_string());
+
+        let reward = self.reward(items.to_vec());
+        if !reward.is_empty() {
+            result.push_str(&format!("\n**Rewards:**\n{}", reward.build_string()));
+        }
+
+        if let (Some(channel_id), Some(interval), Some(timestamp)) = (
+            self.recurring_forum_channel_id,
+            self.recurrence_interval_days,
+            self.next_recurrence_timestamp,
+        ) {
+            result.push_str(&format!(
+                "\nPosted every **{} day(s)** in <#{}>, next time <t:{}:f>.",
+                interval, channel_id, timestamp
+            ));
+        }
+
+        result
+    }
+}
+
+pub async fn get_quest_template(
+    executor: impl SqliteExecutor<'_>,
+    guild_id: i64,
+    name: &str,
+) -> Result<Option<QuestTemplate>, Error> {
+    let template = sqlx::query_as!(
+        QuestTemplate,
+        "SELECT * FROM quest_template WHERE guild_id = ? AND name = ?",
+        guild_id,
+        name
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(template)
+}
+
+pub async fn get_quest_templates(
+    executor: impl SqliteExecutor<'_>,
+    guild_id: i64,
+) -> Result<Vec<QuestTemplate>, Error> {
+    let templates = sqlx::query_as!(
+        QuestTemplate,
+        "SELECT * FROM quest_template WHERE guild_id = ? ORDER BY name",
+        guild_id
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(templates)
+}
+
+/// Recurring templates whose next post is due.
+pub async fn get_due_recurring_quest_templates(
+    executor: impl SqliteExecutor<'_>,
+    now: i64,
+) -> Result<Vec<QuestTemplate>, Error> {
+    let templates = sqlx::query_as!(
+        QuestTemplate,
+        "SELECT * FROM quest_template \
+        WHERE recurring_forum_channel_id IS NOT NULL AND recurrence_interval_days IS NOT NULL \
+        AND next_recurrence_timestamp <= ?",
+        now
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(templates)
+}
+
+pub async fn get_quest_template_reward_items(
+    executor: impl SqliteExecutor<'_>,
+    guild_id: i64,
+    name: &str,
+) -> Result<Vec<QuestRewardItem>, Error> {
+    let items = sqlx::query_as!(
+        QuestRewardItem,
+        "SELECT item_name, amount FROM quest_template_reward_item WHERE guild_id = ? AND template_name = ? ORDER BY item_name",
+        guild_id,
+        name
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(items)
+}
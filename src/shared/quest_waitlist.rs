@@ -0,0 +1,375 @@
+use crate::Error;
+use crate::shared::data::Data;
+use crate::shared::enums::QuestParticipantSelectionMechanism;
+use crate::shared::quest_gm::{build_gm_mention_list, get_quest_gm_ids};
+use crate::shared::utility::button_building;
+use serenity::all::{
+    ButtonStyle, ChannelId, Context, CreateActionRow, CreateAllowedMentions, CreateMessage, UserId,
+};
+use sqlx::{Pool, Sqlite};
+
+#[derive(Debug, PartialEq)]
+pub struct QuestParticipant {
+    pub character_id: i64,
+    pub character_name: String,
+    pub user_id: i64,
+}
+
+/// Everyone who'd currently join the quest. For first come first serve quests, everyone within the participant
+/// limit counts as accepted.
+pub async fn get_quest_participants(
+    database: &Pool<Sqlite>,
+    quest_id: i64,
+) -> Result<Vec<QuestParticipant>, Error> {
+    let quest = sqlx::query!(
+        "SELECT maximum_participant_count, participant_selection_mechanism FROM quest WHERE channel_id = ?",
+        quest_id
+    )
+    .fetch_one(database)
+    .await?;
+    let selection_mechanism =
+        QuestParticipantSelectionMechanism::from_repr(quest.participant_selection_mechanism)
+            .expect("Should always be valid!");
+
+    let signups = sqlx::query!(
+        "SELECT character.id as character_id, character.name as character_name, character.user_id, quest_signup.accepted \
+        FROM quest_signup \
+        INNER JOIN character ON quest_signup.character_id = character.id \
+        WHERE quest_signup.quest_id = ? \
+        ORDER BY quest_signup.accepted DESC, quest_signup.timestamp",
+        quest_id
+    )
+    .fetch_all(database)
+    .await?;
+
+    Ok(signups
+        .into_iter()
+        .enumerate()
+        .filter(|(index, signup)| {
+            signup.accepted
+                || (selection_mechanism == QuestParticipantSelectionMechanism::FirstComeFirstServe
+                    && (*index as i64) < quest.maximum_participant_count)
+        })
+        .map(|(_, signup)| QuestParticipant {
+            character_id: signup.character_id,
+            character_name: signup.character_name,
+            user_id: signup.user_id,
+        })
+        .collect())
+}
+
+/// What needs to be announced after signups were removed from a quest.
+#[derive(Debug, PartialEq)]
+pub enum FreedSpotsNotice {
+    Nothing,
+    /// First come first serve quests promote the next signups on their own, so those just get notified.
+    Promoted(Vec<QuestParticipant>),
+    /// For all other mechanisms, the GMs need to fill the freed spots.
+    PingGms {
+        gm_ids: Vec<i64>,
+        freed_spots: usize,
+        waiting_signups: i64,
+        selection_mechanism: QuestParticipantSelectionMechanism,
+    },
+}
+
+/// Call this after signups were removed, with the participants from before the removal.
+pub async fn get_freed_spots_notice(
+    database: &Pool<Sqlite>,
+    quest_id: i64,
+    previous_participants: &[QuestParticipant],
+) -> Result<FreedSpotsNotice, Error> {
+    let quest = sqlx::query!(
+        "SELECT participant_selection_mechanism, completion_timestamp FROM quest WHERE channel_id = ?",
+        quest_id
+    )
+    .fetch_one(database)
+    .await?;
+    if quest.completion_timestamp.is_some() {
+        return Ok(FreedSpotsNotice::Nothing);
+    }
+
+    let selection_mechanism =
+        QuestParticipantSelectionMechanism::from_repr(quest.participant_selection_mechanism)
+            .expect("Should always be valid!");
+    let current_participants = get_quest_participants(database, quest_id).await?;
+
+    if selection_mechanism == QuestParticipantSelectionMechanism::FirstComeFirstServe {
+        let promoted = current_participants
+            .into_iter()
+            .filter(|x| {
+                previous_participants
+                    .iter()
+                    .all(|previous| previous.character_id != x.character_id)
+            })
+            .collect::<Vec<QuestParticipant>>();
+        if promoted.is_empty() {
+            return Ok(FreedSpotsNotice::Nothing);
+        }
+
+        return Ok(FreedSpotsNotice::Promoted(promoted));
+    }
+
+    let freed_spots = previous_participants
+        .iter()
+        .filter(|x| {
+            current_participants
+                .iter()
+                .all(|current| current.character_id != x.character_id)
+        })
+        .count();
+    if freed_spots == 0 {
+        return Ok(FreedSpotsNotice::Nothing);
+    }
+
+    let waiting = sqlx::query!(
+        "SELECT COUNT(*) as count FROM quest_signup WHERE quest_id = ? AND accepted = false",
+        quest_id
+    )
+    .fetch_one(database)
+    .await?;
+    if waiting.count == 0 {
+        return Ok(FreedSpotsNotice::Nothing);
+    }
+
+    Ok(FreedSpotsNotice::PingGms {
+        gm_ids: get_quest_gm_ids(database, quest_id).await?,
+        freed_spots,
+        waiting_signups: waiting.count,
+        selection_mechanism,
+    })
+}
+
+/// Call this after signups were removed, with the participants from before the removal.
+/// Notifies promoted signups or pings the GMs, depending on the [FreedSpotsNotice].
+pub async fn handle_freed_spots(
+    context: &Context,
+    data: &Data,
+    quest_id: i64,
+    previous_participants: &[QuestParticipant],
+) -> Result<(), Error> {
+    let channel_id = ChannelId::new(quest_id as u64);
+    match get_freed_spots_notice(&data.database, quest_id, previous_participants).await? {
+        FreedSpotsNotice::Nothing => {}
+        FreedSpotsNotice::Promoted(promoted) => {
+            let mut text = String::from("🎉 A spot opened up! Moved up from the waiting queue:\n");
+            for x in &promoted {
+                text.push_str(&format!("- {} (<@{}>)\n", x.character_name, x.user_id));
+            }
+            let user_ids = promoted
+                .iter()
+                .map(|x| UserId::new(x.user_id as u64))
+                .collect::<Vec<UserId>>();
+
+            let _ = channel_id
+                .send_message(
+                    context,
+                    CreateMessage::new()
+                        .content(text)
+                        .allowed_mentions(CreateAllowedMentions::new().users(user_ids)),
+                )
+                .await;
+        }
+        FreedSpotsNotice::PingGms {
+            gm_ids,
+            freed_spots,
+            waiting_signups,
+            selection_mechanism,
+        } => {
+            let mut message = CreateMessage::new()
+                .content(format!(
+                    "{} {} spot(s) opened up, and {} signup(s) are still waiting. Use `/add_quest_participant` to fill them.",
+                    build_gm_mention_list(&gm_ids),
+                    freed_spots,
+                    waiting_signups
+                ))
+                .allowed_mentions(
+                    CreateAllowedMentions::new().users(
+                        gm_ids
+                            .iter()
+                            .map(|x| UserId::new(*x as u64))
+                            .collect::<Vec<UserId>>(),
+                    ),
+                );
+            if selection_mechanism == QuestParticipantSelectionMechanism::Random
+                || selection_mechanism == QuestParticipantSelectionMechanism::WeightedRandom
+            {
+                message = message.components(vec![CreateActionRow::Buttons(vec![
+                    button_building::create_styled_button(
+                        "Select Random Participants",
+                        "quest-add-random-participants",
+                        false,
+                        ButtonStyle::Secondary,
+                    ),
+                ])]);
+            }
+
+            let _ = channel_id.send_message(context, message).await;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shared::data::Data;
+    use crate::shared::enums::QuestParticipantSelectionMechanism;
+    use crate::shared::quest_waitlist::{
+        FreedSpotsNotice, get_freed_spots_notice, get_quest_participants,
+    };
+    use crate::{Error, database_mocks};
+    use sqlx::{Pool, Sqlite};
+
+    const CHANNEL_ID: i64 = 100;
+    const USER_ID: i64 = 200;
+    const GUILD_ID: i64 = 300;
+    const CHARACTER1_ID: i64 = 501;
+    const CHARACTER2_ID: i64 = 502;
+
+    /// Creates a quest with a single spot, which CHARACTER1 signed up for before CHARACTER2.
+    async fn create_quest_with_two_signups(
+        db: Pool<Sqlite>,
+        mechanism: QuestParticipantSelectionMechanism,
+    ) -> Result<Data, Error> {
+        let data = database_mocks::create_mock::data(db).await;
+        database_mocks::create_mock::guild(&data.database, GUILD_ID).await;
+        database_mocks::create_mock::user(&data.database, USER_ID).await;
+        database_mocks::create_mock::quest(
+            &data.database,
+            CHANNEL_ID,
+            GUILD_ID,
+            USER_ID,
+            400,
+            1,
+            mechanism,
+        )
+        .await;
+        for (character_id, timestamp) in [(CHARACTER1_ID, 1), (CHARACTER2_ID, 2)] {
+            database_mocks::create_mock::character(
+                &data,
+                GUILD_ID,
+                USER_ID,
+                character_id,
+                &character_id.to_string(),
+            )
+            .await;
+            sqlx::query!(
+                "INSERT INTO quest_signup (quest_id, character_id, timestamp) VALUES (?, ?, ?)",
+                CHANNEL_ID,
+                character_id,
+                timestamp
+            )
+            .execute(&data.database)
+            .await?;
+        }
+
+        Ok(data)
+    }
+
+    async fn remove_first_signup(data: &Data) -> Result<(), Error> {
+        sqlx::query!(
+            "DELETE FROM quest_signup WHERE quest_id = ? AND character_id = ?",
+            CHANNEL_ID,
+            CHARACTER1_ID
+        )
+        .execute(&data.database)
+        .await?;
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn next_signup_moves_up_in_first_come_first_serve_quests(
+        db: Pool<Sqlite>,
+    ) -> Result<(), Error> {
+        let data = create_quest_with_two_signups(
+            db,
+            QuestParticipantSelectionMechanism::FirstComeFirstServe,
+        )
+        .await?;
+
+        let participants = get_quest_participants(&data.database, CHANNEL_ID).await?;
+        assert_eq!(1, participants.len());
+        assert_eq!(CHARACTER1_ID, participants[0].character_id);
+
+        remove_first_signup(&data).await?;
+
+        let participants = get_quest_participants(&data.database, CHANNEL_ID).await?;
+        assert_eq!(1, participants.len());
+        assert_eq!(CHARACTER2_ID, participants[0].character_id);
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn promoted_signups_get_notified_in_first_come_first_serve_quests(
+        db: Pool<Sqlite>,
+    ) -> Result<(), Error> {
+        let data = create_quest_with_two_signups(
+            db,
+            QuestParticipantSelectionMechanism::FirstComeFirstServe,
+        )
+        .await?;
+        let previous_participants = get_quest_participants(&data.database, CHANNEL_ID).await?;
+        remove_first_signup(&data).await?;
+
+        let notice =
+            get_freed_spots_notice(&data.database, CHANNEL_ID, &previous_participants).await?;
+        let FreedSpotsNotice::Promoted(promoted) = notice else {
+            panic!("Expected a promotion, got {:?}", notice);
+        };
+        assert_eq!(1, promoted.len());
+        assert_eq!(CHARACTER2_ID, promoted[0].character_id);
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn gms_get_pinged_for_other_selection_mechanisms(db: Pool<Sqlite>) -> Result<(), Error> {
+        let data =
+            create_quest_with_two_signups(db, QuestParticipantSelectionMechanism::Random).await?;
+        sqlx::query!(
+            "UPDATE quest_signup SET accepted = true WHERE quest_id = ? AND character_id = ?",
+            CHANNEL_ID,
+            CHARACTER1_ID
+        )
+        .execute(&data.database)
+        .await?;
+        let previous_participants = get_quest_participants(&data.database, CHANNEL_ID).await?;
+        remove_first_signup(&data).await?;
+
+        let notice =
+            get_freed_spots_notice(&data.database, CHANNEL_ID, &previous_participants).await?;
+        assert_eq!(
+            FreedSpotsNotice::PingGms {
+                gm_ids: vec![USER_ID],
+                freed_spots: 1,
+                waiting_signups: 1,
+                selection_mechanism: QuestParticipantSelectionMechanism::Random,
+            },
+            notice
+        );
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn completed_quests_are_ignored(db: Pool<Sqlite>) -> Result<(), Error> {
+        let data = create_quest_with_two_signups(
+            db,
+            QuestParticipantSelectionMechanism::FirstComeFirstServe,
+        )
+        .await?;
+        let previous_participants = get_quest_participants(&data.database, CHANNEL_ID).await?;
+        database_mocks::create_mock::quest_completion(&data.database, CHANNEL_ID, CHARACTER1_ID, 1)
+            .await;
+        remove_first_signup(&data).await?;
+
+        let notice =
+            get_freed_spots_notice(&data.database, CHANNEL_ID, &previous_participants).await?;
+        assert_eq!(FreedSpotsNotice::Nothing, notice);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use crate::Error;
+use crate::shared::enums::MysteryDungeonRank;
+use sqlx::{SqliteConnection, SqliteExecutor};
+
+/// Rank points a team receives whenever at least one of its members completes a quest.
+pub const RANK_POINTS_PER_COMPLETED_QUEST: i64 = 10;
+
+pub struct Team {
+    pub id: i64,
+    pub name: String,
+    pub emblem: Option<String>,
+    pub wallet_id: i64,
+    pub rank_points: i64,
+}
+
+impl Team {
+    pub fn rank(&self) -> MysteryDungeonRank {
+        MysteryDungeonRank::from_team_rank_points(self.rank_points)
+    }
+
+    pub fn display_name(&self) -> String {
+        match &self.emblem {
+            Some(emblem) => format!("{} {}", emblem, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+pub async fn find_team_by_name(
+    executor: impl SqliteExecutor<'_>,
+    guild_id: i64,
+    name: &str,
+) -> Result<Option<Team>, Error> {
+    let team = sqlx::query_as!(
+        Team,
+        "SELECT id, name, emblem, wallet_id, rank_points FROM team WHERE guild_id = ? AND name = ?",
+        guild_id,
+        name
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(team)
+}
+
+pub async fn get_team_of_character(
+    executor: impl SqliteExecutor<'_>,
+    character_id: i64,
+) -> Result<Option<Team>, Error> {
+    let team = sqlx::query_as!(
+        Team,
+        "SELECT team.id, team.name, team.emblem, team.wallet_id, team.rank_points FROM team \
+        INNER JOIN team_member ON team.id = team_member.team_id \
+        WHERE team_member.character_id = ?",
+        character_id
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(team)
+}
+
+/// Non-retired team members, sorted by name.
+pub async fn get_team_member_ids_and_names(
+    executor: impl SqliteExecutor<'_>,
+    team_id: i64,
+) -> Result<Vec<(i64, String)>, Error> {
+    let records = sqlx::query!(
+        "SELECT character.id, character.name FROM character \
+        INNER JOIN team_member ON character.id = team_member.character_id \
+        WHERE team_member.team_id = ? AND character.is_retired = false \
+        ORDER BY character.name",
+        team_id
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(records.into_iter().map(|x| (x.id, x.name)).collect())
+}
+
+/// Hands out rank points to every team with a member in the given list.
+/// Returns the teams which reached a new rank.
+pub async fn award_rank_points_for_quest_completion(
+    connection: &mut SqliteConnection,
+    character_ids: &[i64],
+) -> Result<Vec<Team>, Error> {
+    let mut awarded_team_ids = Vec::new();
+    let mut ranked_up_teams = Vec::new();
+    for character_id in character_ids {
+        let Some(team) = get_team_of_character(&mut *connection, *character_id).await? else {
+            continue;
+        };
+        if awarded_team_ids.contains(&team.id) {
+            continue;
+        }
+        awarded_team_ids.push(team.id);
+
+        sqlx::query!(
+            "UPDATE team SET rank_points = rank_points + ? WHERE id = ?",
+            RANK_POINTS_PER_COMPLETED_QUEST,
+            team.id
+        )
+        .execute(&mut *connection)
+        .await?;
+
+        let old_rank = team.rank();
+        let team = Team {
+            rank_points: team.rank_points + RANK_POINTS_PER_COMPLETED_QUEST,
+            ..team
+        };
+        if team.rank() != old_rank {
+            ranked_up_teams.push(team);
+        }
+    }
+
+    Ok(ranked_up_teams)
+}
@@ -1,14 +1,13 @@
 use crate::shared::game_data::pokemon::Pokemon;
 use crate::shared::game_data::{GameData, PokemonApiId};
+use crate::shared::guild_settings::ProgressionRules;
 
-pub fn calculate_available_combat_points(level: i64) -> i64 {
-    level + 3
+pub fn calculate_available_combat_points(rules: &ProgressionRules, level: i64) -> i64 {
+    rules.base_combat_points + rules.combat_points_per_level * level
 }
 
-const STAGE1_EVOLUTION_LEVEL_THRESHOLD: i64 = 3;
-const STAGE2_EVOLUTION_LEVEL_THRESHOLD: i64 = 6;
-
 pub fn get_usual_evolution_stage_for_level<'a>(
+    rules: &ProgressionRules,
     level: i64,
     pokemon: &'a Pokemon,
     game_data: &'a GameData,
@@ -24,7 +23,7 @@ pub fn get_usual_evolution_stage_for_level<'a>(
     }
     let evolves_from = pokemon.evolves_from.unwrap();
 
-    if level >= STAGE2_EVOLUTION_LEVEL_THRESHOLD {
+    if level >= rules.second_evolution_level {
         return pokemon;
     }
 
@@ -35,7 +34,7 @@ pub fn get_usual_evolution_stage_for_level<'a>(
 
     if pre_evolution.evolves_from.is_none() {
         // Confirmed one stage evo
-        return if level >= STAGE1_EVOLUTION_LEVEL_THRESHOLD {
+        return if level >= rules.first_evolution_level {
             pokemon
         } else {
             pre_evolution
@@ -43,7 +42,7 @@ pub fn get_usual_evolution_stage_for_level<'a>(
     }
 
     // Confirmed two stage evo
-    if level >= STAGE1_EVOLUTION_LEVEL_THRESHOLD {
+    if level >= rules.first_evolution_level {
         return pre_evolution;
     }
 
@@ -54,14 +53,48 @@ pub fn get_usual_evolution_stage_for_level<'a>(
         .expect("Pre-Evolutions should be implemented!")
 }
 
-pub fn calculate_level_from_experience(experience: i64) -> i64 {
-    experience / 100 + 1
+/// 0 for unevolved pokemon, 1 for the first evolution stage and 2 for the second.
+pub fn get_evolution_stage(pokemon: &Pokemon, game_data: &GameData) -> i64 {
+    let mut stage = 0;
+    let mut current = pokemon;
+    while let Some(evolves_from) = current.evolves_from {
+        let Some(pre_evolution) = game_data.pokemon_by_api_id.get(&evolves_from) else {
+            break;
+        };
+
+        stage += 1;
+        current = pre_evolution;
+    }
+
+    stage
+}
+
+pub fn get_required_level_for_evolution_stage(rules: &ProgressionRules, stage: i64) -> i64 {
+    match stage {
+        0 => 1,
+        1 => rules.first_evolution_level,
+        _ => rules.second_evolution_level,
+    }
+}
+
+pub fn calculate_level_from_experience(rules: &ProgressionRules, experience: i64) -> i64 {
+    experience / rules.experience_per_level + 1
+}
+
+pub fn calculate_current_experience(rules: &ProgressionRules, experience: i64) -> i64 {
+    experience % rules.experience_per_level
 }
 
-pub fn calculate_current_experience(experience: i64) -> i64 {
-    experience % 100
+pub fn calculate_next_limit_break_cost(rules: &ProgressionRules, limit_break_count: i64) -> i64 {
+    rules.limit_break_base_cost + rules.limit_break_cost_increase * limit_break_count
 }
 
-pub fn calculate_next_limit_break_cost(limit_break_count: i64) -> i64 {
-    2 + limit_break_count
+/// Points spent on limit breaks on top of the points invested into the stats themselves.
+pub fn calculate_extra_points_used_for_limit_breaks(
+    rules: &ProgressionRules,
+    limit_break_count: i64,
+) -> i64 {
+    (0..limit_break_count)
+        .map(|x| calculate_next_limit_break_cost(rules, x) - 1)
+        .sum()
 }
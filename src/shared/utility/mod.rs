@@ -4,4 +4,6 @@ pub mod error_handling;
 pub mod input_validation;
 pub mod level_calculations;
 pub mod message_splitting;
+pub mod pixel_font;
 pub mod quest_message_utils;
+pub mod time_offset;
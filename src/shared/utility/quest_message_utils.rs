@@ -1,14 +1,18 @@
-use crate::shared::data::Data;
-use crate::shared::emoji;
+use crate::shared::{emoji, quest_board};
 use crate::shared::enums::QuestParticipantSelectionMechanism;
+use crate::shared::game_data::GameData;
+use crate::shared::guild_settings::get_progression_rules;
+use crate::shared::quest_restrictions::get_quest_restrictions;
 use crate::shared::utility::button_building;
 use crate::shared::utility::channel_id_ext::ChannelIdExt;
+use crate::shared::utility::level_calculations;
 use crate::Error;
 use serenity::all::{ButtonStyle, ChannelId, Context, CreateActionRow, EditMessage, MessageId};
+use sqlx::{Pool, Sqlite};
 
 struct QuestSignup {
     character_name: String,
-    character_experience: i64,
+    character_level: i64,
     stat_channel_id: ChannelId,
     user_id: i64,
     accepted: bool,
@@ -19,14 +23,16 @@ const MAX_SIGNUP_DISPLAY_COUNT: usize = 18;
 
 pub async fn generate_quest_post_message_content(
     context: &Context,
-    data: &Data,
+    database: &Pool<Sqlite>,
+    game_data: &GameData,
     channel_id: i64,
     maximum_participants: i64,
     selection_mechanism: QuestParticipantSelectionMechanism,
 ) -> Result<(String, bool), Error> {
     let (mut text, too_many_signups) = create_quest_participant_list(
         context,
-        data,
+        database,
+        game_data,
         channel_id,
         maximum_participants,
         selection_mechanism,
@@ -41,13 +47,27 @@ pub async fn generate_quest_post_message_content(
         )
         .as_str(),
     );
+
+    let scheduled_start_timestamp = sqlx::query!(
+        "SELECT scheduled_start_timestamp FROM quest WHERE channel_id = ?",
+        channel_id
+    )
+    .fetch_optional(database)
+    .await?
+    .and_then(|x| x.scheduled_start_timestamp);
+    if let Some(timestamp) = scheduled_start_timestamp {
+        text.push_str(&format!("\nStart: **<t:{0}:f>** (<t:{0}:R>)", timestamp));
+    }
+    let restrictions = get_quest_restrictions(database, channel_id).await?;
+    text.push_str(&restrictions.build_string());
     text.push_str("\n**Use the buttons below to sign up!**");
     Ok((text, too_many_signups))
 }
 
 pub async fn create_quest_participant_list(
     context: &Context,
-    data: &Data,
+    database: &Pool<Sqlite>,
+    game_data: &GameData,
     channel_id: i64,
     maximum_participants: i64,
     selection_mechanism: QuestParticipantSelectionMechanism,
@@ -65,19 +85,31 @@ ORDER BY quest_signup.accepted DESC, quest_signup.timestamp
 ",
         channel_id
     )
-        .fetch_all(&data.database)
+        .fetch_all(database)
         .await?;
 
+    let guild_id = sqlx::query!(
+        "SELECT guild_id FROM quest WHERE channel_id = ?",
+        channel_id
+    )
+    .fetch_one(database)
+    .await?
+    .guild_id;
+    let rules = get_progression_rules(database, guild_id).await?;
+
     let mut quest_signups = Vec::new();
     for record in records {
-        let emoji = match emoji::get_character_emoji(context, data, record.character_id).await {
+        let emoji = match emoji::get_character_emoji(context, database, game_data, record.character_id).await {
             Some(emoji) => format!("{} ", emoji),
             None => String::new(),
         };
 
         quest_signups.push(QuestSignup {
             character_name: record.character_name.clone(),
-            character_experience: record.character_experience,
+            character_level: level_calculations::calculate_level_from_experience(
+                &rules,
+                record.character_experience,
+            ),
             stat_channel_id: ChannelId::new(record.stat_channel_id as u64),
             user_id: record.user_id,
             accepted: record.accepted,
@@ -135,6 +167,7 @@ ORDER BY quest_signup.accepted DESC, quest_signup.timestamp
                 }
             }
             QuestParticipantSelectionMechanism::Random
+            | QuestParticipantSelectionMechanism::WeightedRandom
             | QuestParticipantSelectionMechanism::GMPicks => {
                 if accepted_participants.is_empty() {
                     text.push_str("**Signups:**\n");
@@ -193,7 +226,7 @@ fn add_character_names(
                 record.emoji,
                 record.character_name,
                 record.user_id,
-                1 + record.character_experience / 100,
+                record.character_level,
             )
             .as_str(),
         );
@@ -219,7 +252,9 @@ pub fn create_quest_signup_buttons(
         ),
     ];
 
-    if signup_mechanism == QuestParticipantSelectionMechanism::Random {
+    if signup_mechanism == QuestParticipantSelectionMechanism::Random
+        || signup_mechanism == QuestParticipantSelectionMechanism::WeightedRandom
+    {
         buttons.push(button_building::create_styled_button(
             "Select Random Participants",
             "quest-add-random-participants",
@@ -245,14 +280,15 @@ pub fn create_quest_signup_buttons(
 
 pub async fn update_quest_message(
     context: &Context,
-    data: &Data,
+    database: &Pool<Sqlite>,
+    game_data: &GameData,
     channel_id: i64,
 ) -> Result<(), Error> {
     let quest_record = sqlx::query!(
-        "SELECT bot_message_id, maximum_participant_count, participant_selection_mechanism FROM quest WHERE channel_id = ?",
+        "SELECT guild_id, bot_message_id, maximum_participant_count, participant_selection_mechanism FROM quest WHERE channel_id = ?",
         channel_id
     )
-        .fetch_one(&data.database)
+        .fetch_one(database)
         .await?;
 
     let selection_mechanism =
@@ -261,7 +297,8 @@ pub async fn update_quest_message(
 
     let (text, too_many_signups) = generate_quest_post_message_content(
         context,
-        data,
+        database,
+        game_data,
         channel_id,
         quest_record.maximum_participant_count,
         selection_mechanism,
@@ -288,5 +325,8 @@ pub async fn update_quest_message(
             )
             .await?;
     }
+
+    quest_board::update_quest_board_or_log_error(context, database, quest_record.guild_id).await;
+
     Ok(())
 }
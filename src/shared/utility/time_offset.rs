@@ -0,0 +1,54 @@
+use chrono::{Datelike, FixedOffset, NaiveDate, TimeZone, Timelike, Utc};
+use sqlx::SqliteExecutor;
+
+/// The offset a user has selected with /setting_time_offset, in hours and minutes.
+/// Returns None for users who haven't set up their timezone yet.
+pub async fn get_user_time_offset(
+    executor: impl SqliteExecutor<'_>,
+    user_id: i64,
+) -> Option<(i64, i64)> {
+    let user = sqlx::query!(
+        "SELECT setting_time_offset_hours, setting_time_offset_minutes FROM user WHERE id = ?",
+        user_id
+    )
+    .fetch_optional(executor)
+    .await
+    .ok()??;
+
+    match (
+        user.setting_time_offset_hours,
+        user.setting_time_offset_minutes,
+    ) {
+        (Some(hours), Some(minutes)) => Some((hours, minutes)),
+        _ => None,
+    }
+}
+
+/// Converts a local date and time into a unix timestamp. Missing values default to the current local time.
+/// Returns None if the resulting date doesn't exist.
+pub fn local_time_to_unix_timestamp(
+    minute: Option<u8>,
+    hour: Option<u8>,
+    day: Option<u8>,
+    month: Option<u8>,
+    year: Option<u16>,
+    hour_offset: i64,
+    minute_offset: i64,
+) -> Option<i64> {
+    let second_offset = ((hour_offset * 60 + minute_offset) * 60) as i32;
+    let offset = FixedOffset::east_opt(second_offset)?;
+
+    let local_datetime = offset.from_utc_datetime(&Utc::now().naive_utc());
+    let minute = minute.unwrap_or(local_datetime.minute() as u8);
+    let hour = hour.unwrap_or(local_datetime.hour() as u8);
+    let day = day.unwrap_or(local_datetime.day() as u8);
+    let month = month.unwrap_or(local_datetime.month() as u8);
+    let year = year.unwrap_or(local_datetime.year() as u16);
+    let timestamp = NaiveDate::from_ymd_opt(year.into(), month.into(), day.into())?.and_hms_opt(
+        hour.into(),
+        minute.into(),
+        0,
+    )?;
+
+    Some(timestamp.and_utc().timestamp() - second_offset as i64)
+}